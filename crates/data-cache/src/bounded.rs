@@ -0,0 +1,1071 @@
+//! Size-bounded in-memory cache implementation.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheDataKind, CacheEntryStats, CacheKey, CacheStats, Cached, CompanyInfo, CoveredRange,
+    DataCache, DateRange, FinancialStatement, KeyMetrics, NegativeKey, OhlcvCacheKey, OhlcvLookup,
+    PeriodType, Result, Symbol, Tick, stitch_ohlcv,
+};
+use polars::prelude::DataFrame;
+use tracing::{debug, instrument};
+
+use crate::memory::{
+    CacheEntry, FinancialsKey, LookupCounter, MetricsKey, OhlcvSeries, SymbolKey, TickBatch,
+    UniverseKey, UniverseSnapshots, filter_dates,
+};
+
+/// Which entry a [`BoundedMemoryCache`] evicts when it is over budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Evict the least recently used entry.
+    #[default]
+    Lru,
+    /// Evict the least frequently used entry, breaking ties by recency.
+    Lfu,
+}
+
+/// In-memory cache with a byte-size budget.
+///
+/// Each entry's size is estimated when it is stored, and entries are evicted
+/// according to the [`EvictionPolicy`] whenever the total exceeds the budget.
+/// Values are held behind `Arc`s, so lookups and writes only hold the lock
+/// long enough to take or swap a reference; filtering and stitching OHLCV
+/// frames happens outside it. A lookup covering a whole series returns a
+/// frame sharing its column buffers with the cache.
+#[derive(Debug)]
+pub struct BoundedMemoryCache {
+    store: Mutex<Store>,
+    lookups: LookupCounter,
+    max_bytes: usize,
+    policy: EvictionPolicy,
+}
+
+impl BoundedMemoryCache {
+    /// Create an empty cache holding at most about `max_bytes` of data.
+    #[must_use]
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            store: Mutex::default(),
+            lookups: LookupCounter::default(),
+            max_bytes,
+            policy: EvictionPolicy::default(),
+        }
+    }
+
+    /// Set the eviction policy.
+    #[must_use]
+    pub const fn with_eviction_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the byte budget.
+    #[must_use]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the eviction policy.
+    #[must_use]
+    pub const fn eviction_policy(&self) -> EvictionPolicy {
+        self.policy
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Look up an entry, counting a hit if `hit` accepts its value.
+    fn lookup<T>(&self, key: &EntryKey, hit: impl FnOnce(&Value) -> Option<T>) -> Option<T> {
+        let value = self.lock().touch(key, self.policy);
+        let found = value.and_then(|value| hit(&value));
+        self.lookups.record(found.is_some());
+        found
+    }
+
+    /// Returns the OHLCV entry stored under `key` without marking it as used.
+    fn ohlcv_entry(&self, key: &EntryKey) -> Option<Arc<CacheEntry<OhlcvSeries>>> {
+        match self.lock().entries.get(key).map(|slot| &slot.value) {
+            Some(Value::Ohlcv(entry)) => Some(Arc::clone(entry)),
+            _ => None,
+        }
+    }
+
+    /// Store an entry, evicting others as needed to stay within budget.
+    fn insert(&self, key: EntryKey, value: Value) {
+        self.lock().insert(key, value, self.policy, self.max_bytes);
+    }
+}
+
+/// Identifies an entry across all kinds of cached data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum EntryKey {
+    Ohlcv(OhlcvCacheKey),
+    Ticks(SymbolKey),
+    Financials(FinancialsKey),
+    Metrics(MetricsKey),
+    CompanyInfo(SymbolKey),
+    Universe(UniverseKey),
+    Negative(NegativeKey),
+}
+
+/// A cached value. Cloning only bumps a reference count.
+#[derive(Debug, Clone)]
+enum Value {
+    Ohlcv(Arc<CacheEntry<OhlcvSeries>>),
+    Ticks(Arc<Vec<CacheEntry<TickBatch>>>),
+    Financials(Arc<CacheEntry<Vec<FinancialStatement>>>),
+    Metrics(Arc<CacheEntry<KeyMetrics>>),
+    CompanyInfo(Arc<CacheEntry<CompanyInfo>>),
+    Universe(Arc<UniverseSnapshots>),
+    Negative(Arc<CacheEntry<String>>),
+}
+
+impl Value {
+    /// Estimated heap and inline size in bytes.
+    fn weight(&self) -> usize {
+        match self {
+            Self::Ohlcv(entry) => entry.data.data.estimated_size(),
+            Self::Ticks(batches) => batches
+                .iter()
+                .flat_map(|b| &b.data.ticks)
+                .map(tick_weight)
+                .sum(),
+            Self::Financials(entry) => entry.data.len() * std::mem::size_of::<FinancialStatement>(),
+            Self::Metrics(_) => std::mem::size_of::<KeyMetrics>(),
+            Self::CompanyInfo(entry) => company_info_weight(&entry.data),
+            Self::Universe(snapshots) => snapshots
+                .values()
+                .flat_map(|e| &e.data)
+                .map(symbol_weight)
+                .sum(),
+            Self::Negative(entry) => std::mem::size_of::<String>() + entry.data.len(),
+        }
+    }
+
+    /// Remove the parts of the value stored longer than `ttl` ago.
+    ///
+    /// Returns the number of parts removed and what remains, if anything.
+    fn without_stale(self, ttl: Duration) -> (usize, Option<Self>) {
+        match self {
+            Self::Ticks(batches) => {
+                let mut batches = Arc::unwrap_or_clone(batches);
+                let before = batches.len();
+                batches.retain(|b| !b.is_stale(ttl));
+                let removed = before - batches.len();
+                (
+                    removed,
+                    (!batches.is_empty()).then(|| Self::Ticks(Arc::new(batches))),
+                )
+            }
+            Self::Universe(snapshots) => {
+                let mut snapshots = Arc::unwrap_or_clone(snapshots);
+                let before = snapshots.len();
+                snapshots.retain(|_, e| !e.is_stale(ttl));
+                let removed = before - snapshots.len();
+                (
+                    removed,
+                    (!snapshots.is_empty()).then(|| Self::Universe(Arc::new(snapshots))),
+                )
+            }
+            value => {
+                if value.is_stale(ttl) {
+                    (1, None)
+                } else {
+                    (0, Some(value))
+                }
+            }
+        }
+    }
+
+    fn is_stale(&self, ttl: Duration) -> bool {
+        match self {
+            Self::Ohlcv(entry) => entry.is_stale(ttl),
+            Self::Financials(entry) => entry.is_stale(ttl),
+            Self::Metrics(entry) => entry.is_stale(ttl),
+            Self::CompanyInfo(entry) => entry.is_stale(ttl),
+            Self::Negative(entry) => entry.is_stale(ttl),
+            Self::Ticks(batches) => batches.iter().all(|b| b.is_stale(ttl)),
+            Self::Universe(snapshots) => snapshots.values().all(|e| e.is_stale(ttl)),
+        }
+    }
+}
+
+pub(crate) fn symbol_weight(symbol: &Symbol) -> usize {
+    std::mem::size_of::<Symbol>() + symbol.as_str().len()
+}
+
+pub(crate) fn tick_weight(tick: &Tick) -> usize {
+    std::mem::size_of::<Tick>()
+        + tick.symbol.as_str().len()
+        + tick.exchange.as_ref().map_or(0, String::len)
+        + tick
+            .conditions
+            .iter()
+            .map(|c| std::mem::size_of::<String>() + c.len())
+            .sum::<usize>()
+}
+
+pub(crate) fn company_info_weight(info: &CompanyInfo) -> usize {
+    std::mem::size_of::<CompanyInfo>()
+        + info.symbol.as_str().len()
+        + info.name.len()
+        + info.exchange.len()
+        + info.sector.len()
+        + info.industry.len()
+        + info.country.len()
+        + info.currency.len()
+        + info.cik.as_ref().map_or(0, String::len)
+        + info.description.as_ref().map_or(0, String::len)
+}
+
+/// A stored value with its size and eviction rank.
+#[derive(Debug)]
+struct Slot {
+    value: Value,
+    bytes: usize,
+    uses: u64,
+    rank: Rank,
+}
+
+/// Position in eviction order; the smallest rank is evicted first.
+/// The second element is a logical clock, so ranks are unique.
+type Rank = (u64, u64);
+
+/// Entries and eviction order, guarded by one lock.
+#[derive(Debug, Default)]
+struct Store {
+    entries: HashMap<EntryKey, Slot>,
+    order: BTreeMap<Rank, EntryKey>,
+    clock: u64,
+    bytes: usize,
+}
+
+impl Store {
+    fn next_rank(&mut self, uses: u64, policy: EvictionPolicy) -> Rank {
+        self.clock += 1;
+        match policy {
+            EvictionPolicy::Lru => (0, self.clock),
+            EvictionPolicy::Lfu => (uses, self.clock),
+        }
+    }
+
+    /// Returns an entry's value and marks it as used.
+    fn touch(&mut self, key: &EntryKey, policy: EvictionPolicy) -> Option<Value> {
+        let uses = self.entries.get(key)?.uses + 1;
+        let rank = self.next_rank(uses, policy);
+        let slot = self.entries.get_mut(key)?;
+        self.order.remove(&slot.rank);
+        slot.uses = uses;
+        slot.rank = rank;
+        self.order.insert(rank, key.clone());
+        Some(slot.value.clone())
+    }
+
+    /// Removes an entry, returning its value.
+    fn remove(&mut self, key: &EntryKey) -> Option<Value> {
+        let slot = self.entries.remove(key)?;
+        self.order.remove(&slot.rank);
+        self.bytes -= slot.bytes;
+        Some(slot.value)
+    }
+
+    fn insert(&mut self, key: EntryKey, value: Value, policy: EvictionPolicy, max_bytes: usize) {
+        let uses = self.entries.get(&key).map_or(0, |slot| slot.uses) + 1;
+        self.remove(&key);
+
+        let bytes = std::mem::size_of::<EntryKey>() + value.weight();
+        if bytes > max_bytes {
+            debug!(bytes, max_bytes, "Entry exceeds cache budget, not caching");
+            return;
+        }
+
+        let rank = self.next_rank(uses, policy);
+        self.order.insert(rank, key.clone());
+        self.entries.insert(
+            key.clone(),
+            Slot {
+                value,
+                bytes,
+                uses,
+                rank,
+            },
+        );
+        self.bytes += bytes;
+
+        while self.bytes > max_bytes {
+            let Some(victim) = self.order.values().find(|k| **k != key).cloned() else {
+                break;
+            };
+            self.remove(&victim);
+            debug!(entry = ?victim, "Evicted cache entry");
+        }
+    }
+
+    /// Replaces an entry's value without changing its eviction rank.
+    fn replace(&mut self, key: &EntryKey, value: Value) {
+        let bytes = std::mem::size_of::<EntryKey>() + value.weight();
+        if let Some(slot) = self.entries.get_mut(key) {
+            self.bytes = self.bytes - slot.bytes + bytes;
+            slot.value = value;
+            slot.bytes = bytes;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+}
+
+#[async_trait]
+impl DataCache for BoundedMemoryCache {
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        let entry = self
+            .lock()
+            .touch(&EntryKey::Ohlcv(key.clone()), self.policy);
+        let Some(Value::Ohlcv(entry)) = entry else {
+            self.lookups.record(false);
+            debug!("Cache miss for OHLCV data");
+            return Ok(OhlcvLookup::miss(start, end));
+        };
+
+        // Only copy rows out when the request excludes part of the series
+        let whole = entry
+            .data
+            .covered
+            .iter()
+            .all(|c| c.range.start >= start && c.range.end <= end);
+        let data = if whole {
+            entry.data.data.clone()
+        } else {
+            filter_dates(&entry.data.data, start, end)?
+        };
+        let lookup = OhlcvLookup::from_coverage(
+            (data.height() > 0).then_some(data),
+            &entry.data.covered,
+            start,
+            end,
+        );
+        self.lookups.record(lookup.is_complete());
+        debug!(gaps = lookup.missing.len(), "Cache lookup for OHLCV data");
+        Ok(lookup)
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let covered = CoveredRange::new(DateRange::new(start, end), Utc::now());
+        self.put_ohlcv_at(key, covered, data).await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered_range: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let entry_key = EntryKey::Ohlcv(key.clone());
+        // Stitch without holding the lock, starting over if another write
+        // replaced the series in the meantime
+        loop {
+            let existing = self.ohlcv_entry(&entry_key);
+            let (series, cached_at) = match &existing {
+                Some(entry) => {
+                    let mut covered = entry.data.covered.clone();
+                    covered.push(covered_range);
+                    let series = OhlcvSeries {
+                        data: stitch_ohlcv(vec![entry.data.data.clone(), data.clone()])?,
+                        covered,
+                    };
+                    (series, entry.cached_at.max(covered_range.cached_at))
+                }
+                None => {
+                    let series = OhlcvSeries {
+                        data: data.clone(),
+                        covered: vec![covered_range],
+                    };
+                    (series, covered_range.cached_at)
+                }
+            };
+
+            let mut store = self.lock();
+            let current = match store.entries.get(&entry_key).map(|slot| &slot.value) {
+                Some(Value::Ohlcv(entry)) => Some(entry),
+                _ => None,
+            };
+            let unchanged = match (&existing, current) {
+                (Some(existing), Some(current)) => Arc::ptr_eq(existing, current),
+                (None, None) => true,
+                _ => false,
+            };
+            if !unchanged {
+                continue;
+            }
+            store.insert(
+                entry_key,
+                Value::Ohlcv(Arc::new(CacheEntry::at(series, cached_at))),
+                self.policy,
+                self.max_bytes,
+            );
+            debug!("Cached {} OHLCV rows", data.height());
+            return Ok(());
+        }
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        let key = EntryKey::Ticks(SymbolKey::new(provider, symbol));
+        let cached = self.lookup(&key, |value| {
+            let Value::Ticks(batches) = value else {
+                return None;
+            };
+            let entry = batches
+                .iter()
+                .rev()
+                .find(|b| b.data.start <= start && b.data.end >= end)?;
+            let ticks = entry
+                .data
+                .ticks
+                .iter()
+                .filter(|t| t.timestamp >= start && t.timestamp <= end)
+                .cloned()
+                .collect();
+            Some(Cached::new(ticks, entry.cached_at))
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for ticks");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, ticks), fields(provider = %provider, symbol = %symbol, count = ticks.len()))]
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let batch = CacheEntry::new(TickBatch {
+            start,
+            end,
+            ticks: ticks.to_vec(),
+        });
+        let key = EntryKey::Ticks(SymbolKey::new(provider, symbol));
+        let mut store = self.lock();
+        let mut batches = match store.remove(&key) {
+            Some(Value::Ticks(batches)) => Arc::unwrap_or_clone(batches),
+            _ => Vec::new(),
+        };
+        batches.push(batch);
+        store.insert(
+            key,
+            Value::Ticks(Arc::new(batches)),
+            self.policy,
+            self.max_bytes,
+        );
+        debug!("Cached {} ticks", ticks.len());
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        let key = EntryKey::Financials(FinancialsKey {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            period_type,
+        });
+        let cached = self.lookup(&key, |value| match value {
+            Value::Financials(entry) => Some(Cached::new(entry.data.clone(), entry.cached_at)),
+            _ => None,
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for financials");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[FinancialStatement],
+    ) -> Result<()> {
+        let now = Utc::now();
+        let statements: Vec<Cached<FinancialStatement>> = statements
+            .iter()
+            .map(|s| Cached::new(s.clone(), now))
+            .collect();
+        self.put_financials_at(provider, symbol, &statements).await
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
+        // Statements are kept per period type with a single timestamp, so each
+        // group is stamped with its oldest statement's time
+        for period_type in [PeriodType::Quarterly, PeriodType::Annual] {
+            let group: Vec<&Cached<FinancialStatement>> = statements
+                .iter()
+                .filter(|s| s.value.period_type == period_type)
+                .collect();
+            let Some(cached_at) = group.iter().map(|s| s.cached_at).min() else {
+                continue;
+            };
+            let key = EntryKey::Financials(FinancialsKey {
+                provider: provider.to_string(),
+                symbol: symbol.to_string(),
+                period_type,
+            });
+            let group = group.into_iter().map(|s| s.value.clone()).collect();
+            self.insert(
+                key,
+                Value::Financials(Arc::new(CacheEntry::at(group, cached_at))),
+            );
+        }
+        debug!("Cached {} financial statements", statements.len());
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        let key = EntryKey::Metrics(MetricsKey {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            date,
+        });
+        let cached = self.lookup(&key, |value| match value {
+            Value::Metrics(entry) => Some(Cached::new(entry.data.clone(), entry.cached_at)),
+            _ => None,
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for metrics");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &KeyMetrics,
+    ) -> Result<()> {
+        self.put_metrics_at(provider, symbol, &Cached::new(metrics.clone(), Utc::now()))
+            .await
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
+    ) -> Result<()> {
+        let key = EntryKey::Metrics(MetricsKey {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+            date: metrics.value.date,
+        });
+        self.insert(
+            key,
+            Value::Metrics(Arc::new(CacheEntry::at(
+                metrics.value.clone(),
+                metrics.cached_at,
+            ))),
+        );
+        debug!("Cached metrics");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        let key = EntryKey::CompanyInfo(SymbolKey::new(provider, symbol));
+        let cached = self.lookup(&key, |value| match value {
+            Value::CompanyInfo(entry) => Some(Cached::new(entry.data.clone(), entry.cached_at)),
+            _ => None,
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for company info");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, info), fields(provider = %provider, symbol = %symbol))]
+    async fn put_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+    ) -> Result<()> {
+        self.insert(
+            EntryKey::CompanyInfo(SymbolKey::new(provider, symbol)),
+            Value::CompanyInfo(Arc::new(CacheEntry::new(info.clone()))),
+        );
+        debug!("Cached company info");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, universe = %universe_id))]
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        let key = EntryKey::Universe(UniverseKey {
+            provider: provider.to_string(),
+            universe_id: universe_id.to_string(),
+        });
+        let cached = self.lookup(&key, |value| {
+            let Value::Universe(snapshots) = value else {
+                return None;
+            };
+            let (_, entry) = snapshots.range(..=as_of).next_back()?;
+            Some(Cached::new(entry.data.clone(), entry.cached_at))
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for universe");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, symbols), fields(provider = %provider, universe = %universe_id, count = symbols.len()))]
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()> {
+        let key = EntryKey::Universe(UniverseKey {
+            provider: provider.to_string(),
+            universe_id: universe_id.to_string(),
+        });
+        let mut store = self.lock();
+        let mut snapshots = match store.remove(&key) {
+            Some(Value::Universe(snapshots)) => Arc::unwrap_or_clone(snapshots),
+            _ => UniverseSnapshots::new(),
+        };
+        snapshots.insert(as_of, CacheEntry::new(symbols.to_vec()));
+        store.insert(
+            key,
+            Value::Universe(Arc::new(snapshots)),
+            self.policy,
+            self.max_bytes,
+        );
+        debug!("Cached universe snapshot");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        let cached = self.lookup(&EntryKey::Negative(key.clone()), |value| match value {
+            Value::Negative(entry) => Some(Cached::new(entry.data.clone(), entry.cached_at)),
+            _ => None,
+        });
+        debug!(hit = cached.is_some(), "Cache lookup for negative entry");
+        Ok(cached)
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        self.insert(
+            EntryKey::Negative(key.clone()),
+            Value::Negative(Arc::new(CacheEntry::new(message.to_string()))),
+        );
+        debug!("Cached negative entry");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        let store = self.lock();
+        let keys = store
+            .entries
+            .keys()
+            .filter_map(|key| match key {
+                EntryKey::Ohlcv(key) => Some(CacheKey::Ohlcv(key.clone())),
+                EntryKey::Financials(key) => Some(key.cache_key()),
+                EntryKey::Metrics(key) => Some(key.cache_key()),
+                EntryKey::Ticks(_)
+                | EntryKey::CompanyInfo(_)
+                | EntryKey::Universe(_)
+                | EntryKey::Negative(_) => None,
+            })
+            .collect();
+        Ok(keys)
+    }
+
+    #[instrument(skip(self))]
+    async fn inspect(&self) -> Result<CacheStats> {
+        let values: Vec<(EntryKey, Value)> = self
+            .lock()
+            .entries
+            .iter()
+            .map(|(key, slot)| (key.clone(), slot.value.clone()))
+            .collect();
+
+        let mut entries = Vec::new();
+        let mut metrics: HashMap<(String, String), CacheEntryStats> = HashMap::new();
+        for (key, value) in values {
+            match (key, value) {
+                (EntryKey::Ohlcv(key), Value::Ohlcv(entry)) => {
+                    let mut stats = CacheEntryStats::ohlcv(&key, entry.cached_at);
+                    for covered in &entry.data.covered {
+                        stats.observe(covered.cached_at);
+                        stats.covered.push(covered.range);
+                    }
+                    stats.rows = entry.data.data.height();
+                    stats.bytes = entry.data.data.estimated_size();
+                    entries.push(stats);
+                }
+                (EntryKey::Ticks(key), Value::Ticks(batches)) => {
+                    let Some(first) = batches.first() else {
+                        continue;
+                    };
+                    let mut stats = CacheEntryStats::new(
+                        CacheDataKind::Ticks,
+                        &key.provider,
+                        &key.symbol,
+                        first.cached_at,
+                    );
+                    for batch in batches.iter() {
+                        stats.observe(batch.cached_at);
+                        stats.covered.push(DateRange::new(
+                            batch.data.start.date_naive(),
+                            batch.data.end.date_naive(),
+                        ));
+                        stats.rows += batch.data.ticks.len();
+                        stats.bytes += batch.data.ticks.iter().map(tick_weight).sum::<usize>();
+                    }
+                    entries.push(stats);
+                }
+                (EntryKey::Financials(key), Value::Financials(entry)) => {
+                    let mut stats = CacheEntryStats::new(
+                        CacheDataKind::Financials,
+                        &key.provider,
+                        &key.symbol,
+                        entry.cached_at,
+                    )
+                    .with_period_type(key.period_type);
+                    stats.rows = entry.data.len();
+                    stats.bytes = entry.data.len() * std::mem::size_of::<FinancialStatement>();
+                    entries.push(stats);
+                }
+                (EntryKey::Metrics(key), Value::Metrics(entry)) => {
+                    let stats = metrics
+                        .entry((key.provider.clone(), key.symbol.clone()))
+                        .or_insert_with(|| {
+                            CacheEntryStats::new(
+                                CacheDataKind::Metrics,
+                                &key.provider,
+                                &key.symbol,
+                                entry.cached_at,
+                            )
+                        });
+                    stats.observe(entry.cached_at);
+                    stats.rows += 1;
+                    stats.bytes += std::mem::size_of::<KeyMetrics>();
+                }
+                (EntryKey::CompanyInfo(key), Value::CompanyInfo(entry)) => {
+                    let mut stats = CacheEntryStats::new(
+                        CacheDataKind::CompanyInfo,
+                        &key.provider,
+                        &key.symbol,
+                        entry.cached_at,
+                    );
+                    stats.rows = 1;
+                    stats.bytes = company_info_weight(&entry.data);
+                    entries.push(stats);
+                }
+                (EntryKey::Universe(key), Value::Universe(snapshots)) => {
+                    let Some(first) = snapshots.values().next() else {
+                        continue;
+                    };
+                    let mut stats = CacheEntryStats::new(
+                        CacheDataKind::Universe,
+                        &key.provider,
+                        &key.universe_id,
+                        first.cached_at,
+                    );
+                    for entry in snapshots.values() {
+                        stats.observe(entry.cached_at);
+                        stats.rows += 1;
+                        stats.bytes += entry.data.iter().map(symbol_weight).sum::<usize>();
+                    }
+                    entries.push(stats);
+                }
+                _ => {}
+            }
+        }
+        entries.extend(metrics.into_values());
+
+        Ok(CacheStats::new(
+            entries,
+            self.lookups.hits(),
+            self.lookups.misses(),
+        ))
+    }
+
+    #[instrument(skip(self))]
+    async fn invalidate_stale(&self, ttl: Duration) -> Result<usize> {
+        let mut store = self.lock();
+        let keys: Vec<EntryKey> = store.entries.keys().cloned().collect();
+        let mut total_removed = 0usize;
+        for key in keys {
+            let value = store.entries[&key].value.clone();
+            let (removed, remaining) = value.without_stale(ttl);
+            if removed == 0 {
+                continue;
+            }
+            total_removed += removed;
+            match remaining {
+                Some(value) => store.replace(&key, value),
+                None => {
+                    store.remove(&key);
+                }
+            }
+        }
+
+        if total_removed > 0 {
+            debug!("Invalidated {} stale cache entries", total_removed);
+        }
+
+        Ok(total_removed)
+    }
+
+    #[instrument(skip(self))]
+    async fn clear(&self) -> Result<()> {
+        self.lock().clear();
+        debug!("Cleared all cache entries");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_core::DataFrequency;
+    use polars::prelude::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn ohlcv_frame(days: u32) -> DataFrame {
+        let dates: Vec<NaiveDate> = (1..=days).map(|d| date(2024, 1, d)).collect();
+        let closes: Vec<f64> = (0..days).map(f64::from).collect();
+        DataFrame::new(vec![
+            Column::new("date".into(), dates),
+            Column::new("close".into(), closes),
+        ])
+        .unwrap()
+    }
+
+    fn key(symbol: &str) -> OhlcvCacheKey {
+        OhlcvCacheKey::new("test", &Symbol::new(symbol), DataFrequency::Daily)
+    }
+
+    async fn put(cache: &BoundedMemoryCache, symbol: &str) {
+        cache
+            .put_ohlcv(
+                &key(symbol),
+                date(2024, 1, 1),
+                date(2024, 1, 10),
+                &ohlcv_frame(10),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn is_cached(cache: &BoundedMemoryCache, symbol: &str) -> bool {
+        cache
+            .get_ohlcv(&key(symbol), date(2024, 1, 1), date(2024, 1, 10))
+            .await
+            .unwrap()
+            .is_complete()
+    }
+
+    /// Budget for roughly `n` of the frames used in these tests.
+    fn budget(n: usize) -> usize {
+        n * (ohlcv_frame(10).estimated_size() + std::mem::size_of::<EntryKey>())
+    }
+
+    #[tokio::test]
+    async fn test_lru_evicts_least_recently_used() {
+        let cache = BoundedMemoryCache::new(budget(2));
+        put(&cache, "AAPL").await;
+        put(&cache, "MSFT").await;
+        assert!(is_cached(&cache, "AAPL").await);
+
+        put(&cache, "GOOG").await;
+        assert!(is_cached(&cache, "AAPL").await);
+        assert!(!is_cached(&cache, "MSFT").await);
+        assert!(is_cached(&cache, "GOOG").await);
+
+        let stats = cache.inspect().await.unwrap();
+        assert_eq!(stats.entries.len(), 2);
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.misses, 1);
+        assert!(stats.entries.iter().map(|e| e.bytes).sum::<usize>() <= cache.max_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_lfu_evicts_least_frequently_used() {
+        let cache = BoundedMemoryCache::new(budget(2)).with_eviction_policy(EvictionPolicy::Lfu);
+        put(&cache, "AAPL").await;
+        put(&cache, "MSFT").await;
+        for _ in 0..3 {
+            assert!(is_cached(&cache, "AAPL").await);
+        }
+        assert!(is_cached(&cache, "MSFT").await);
+
+        put(&cache, "GOOG").await;
+        assert!(is_cached(&cache, "AAPL").await);
+        assert!(!is_cached(&cache, "MSFT").await);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_entries_are_not_cached() {
+        let cache = BoundedMemoryCache::new(16);
+        put(&cache, "AAPL").await;
+        assert!(!is_cached(&cache, "AAPL").await);
+        assert!(cache.inspect().await.unwrap().entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_and_clear_release_bytes() {
+        let cache = BoundedMemoryCache::new(budget(4));
+        put(&cache, "AAPL").await;
+        cache
+            .put_company_info(
+                "test",
+                &Symbol::new("AAPL"),
+                &CompanyInfo::new(
+                    Symbol::new("AAPL"),
+                    "Apple Inc.",
+                    "NASDAQ",
+                    "Technology",
+                    "Consumer Electronics",
+                    "US",
+                    "USD",
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(cache.inspect().await.unwrap().entries.len(), 2);
+
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        assert_eq!(
+            cache
+                .invalidate_stale(Duration::from_secs(3600))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(cache.invalidate_stale(Duration::ZERO).await.unwrap(), 2);
+        assert_eq!(cache.lock().bytes, 0);
+
+        put(&cache, "AAPL").await;
+        cache.clear().await.unwrap();
+        assert!(cache.inspect().await.unwrap().entries.is_empty());
+        assert_eq!(cache.lock().bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_whole_series_lookups_share_frames() {
+        let cache = BoundedMemoryCache::new(budget(2));
+        put(&cache, "AAPL").await;
+        let series = match cache.ohlcv_entry(&EntryKey::Ohlcv(key("AAPL"))) {
+            Some(entry) => entry.data.data.clone(),
+            None => panic!("series should be cached"),
+        };
+        let shares_buffers = |frame: &DataFrame| {
+            let cached = series.column("close").unwrap().f64().unwrap();
+            let returned = frame.column("close").unwrap().f64().unwrap();
+            std::ptr::eq(
+                cached.downcast_iter().next().unwrap().values().as_ptr(),
+                returned.downcast_iter().next().unwrap().values().as_ptr(),
+            )
+        };
+
+        let whole = cache
+            .get_ohlcv(&key("AAPL"), date(2023, 12, 1), date(2024, 2, 1))
+            .await
+            .unwrap();
+        assert!(shares_buffers(&whole.data.unwrap()));
+
+        let part = cache
+            .get_ohlcv(&key("AAPL"), date(2024, 1, 3), date(2024, 1, 5))
+            .await
+            .unwrap();
+        let part = part.data.unwrap();
+        assert_eq!(part.height(), 3);
+        assert!(!shares_buffers(&part));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_writes_keep_every_range() {
+        let cache = Arc::new(BoundedMemoryCache::new(budget(4)));
+        let writes = (1..=10u32).map(|day| {
+            let cache = Arc::clone(&cache);
+            tokio::spawn(async move {
+                let frame = DataFrame::new(vec![
+                    Column::new("date".into(), vec![date(2024, 1, day)]),
+                    Column::new("close".into(), vec![f64::from(day)]),
+                ])
+                .unwrap();
+                cache
+                    .put_ohlcv(&key("AAPL"), date(2024, 1, day), date(2024, 1, day), &frame)
+                    .await
+                    .unwrap();
+            })
+        });
+        for write in writes.collect::<Vec<_>>() {
+            write.await.unwrap();
+        }
+
+        let lookup = cache
+            .get_ohlcv(&key("AAPL"), date(2024, 1, 1), date(2024, 1, 10))
+            .await
+            .unwrap();
+        assert!(lookup.is_complete());
+        assert_eq!(lookup.data.unwrap().height(), 10);
+    }
+}
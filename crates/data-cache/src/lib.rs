@@ -9,13 +9,27 @@
 //! This crate provides implementations of the [`DataCache`] trait from `data-core`:
 //!
 //! - [`SqliteCache`] - Persistent SQLite-based cache (default, requires `sqlite` feature)
+//! - [`ParquetCache`] - Partitioned Parquet files on disk, suited to large OHLCV histories
 //! - [`InMemoryCache`] - Simple in-memory cache for testing
+//! - [`BoundedMemoryCache`] - In-memory cache with a byte budget and LRU/LFU eviction
 //! - [`NoopCache`] - No-op cache that doesn't store anything
+//! - [`TieredCache`] - Layers several caches, e.g. memory in front of SQLite
+//!
+//! [`export_snapshot`] and [`import_snapshot`] move a cache's contents between
+//! backends through a portable archive of Parquet files.
 
+/// Size-bounded in-memory cache implementation.
+pub mod bounded;
 /// In-memory cache implementation.
 pub mod memory;
 /// No-op cache implementation.
 pub mod noop;
+/// Parquet-backed on-disk cache implementation.
+pub mod parquet;
+/// Portable cache snapshots.
+pub mod snapshot;
+/// Tiered cache implementation.
+pub mod tiered;
 
 /// SQLite-based cache implementation.
 #[cfg(feature = "sqlite")]
@@ -25,8 +39,15 @@ pub mod sqlite;
 pub use data_core::DataCache;
 
 // Re-export implementations
+pub use bounded::{BoundedMemoryCache, EvictionPolicy};
 pub use memory::InMemoryCache;
 pub use noop::NoopCache;
+pub use parquet::ParquetCache;
+pub use snapshot::{
+    OhlcvSnapshot, SNAPSHOT_FORMAT_VERSION, SnapshotFile, SnapshotManifest, export_snapshot,
+    import_snapshot,
+};
+pub use tiered::{TieredCache, WriteMode};
 
 #[cfg(feature = "sqlite")]
 pub use sqlite::SqliteCache;
@@ -1,69 +1,160 @@
 //! In-memory cache implementation.
 
 use async_trait::async_trait;
-use chrono::{NaiveDate, Utc};
-use data_core::{DataCache, FinancialStatement, KeyMetrics, PeriodType, Result, Symbol};
-use polars::prelude::{ChunkAgg, DataFrame};
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheDataKind, CacheEntryStats, CacheKey, CacheStats, Cached, CompanyInfo, CoveredRange,
+    DataCache, DataError, DateRange, FinancialStatement, KeyMetrics, NegativeKey, OhlcvCacheKey,
+    OhlcvLookup, PeriodType, Result, Symbol, Tick, stitch_ohlcv,
+};
+use polars::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, instrument};
 
+use crate::bounded::{company_info_weight, symbol_weight, tick_weight};
+
 /// Cache entry with timestamp for TTL-based invalidation.
 #[derive(Debug, Clone)]
-struct CacheEntry<T> {
-    data: T,
-    cached_at: chrono::DateTime<Utc>,
+pub(crate) struct CacheEntry<T> {
+    pub(crate) data: T,
+    pub(crate) cached_at: chrono::DateTime<Utc>,
 }
 
 impl<T> CacheEntry<T> {
-    fn new(data: T) -> Self {
-        Self {
-            data,
-            cached_at: Utc::now(),
-        }
+    pub(crate) fn new(data: T) -> Self {
+        Self::at(data, Utc::now())
+    }
+
+    pub(crate) const fn at(data: T, cached_at: chrono::DateTime<Utc>) -> Self {
+        Self { data, cached_at }
     }
 
-    fn is_stale(&self, ttl: Duration) -> bool {
+    pub(crate) fn is_stale(&self, ttl: Duration) -> bool {
         let age = Utc::now().signed_duration_since(self.cached_at);
         age > chrono::TimeDelta::from_std(ttl).unwrap_or(chrono::TimeDelta::MAX)
     }
 }
 
-/// Key for OHLCV cache entries.
+/// Counts lookups that were and were not answered from a cache.
+#[derive(Debug, Default)]
+pub(crate) struct LookupCounter {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl LookupCounter {
+    pub(crate) fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// A cached OHLCV series and the date ranges it covers.
+#[derive(Debug, Clone)]
+pub(crate) struct OhlcvSeries {
+    pub(crate) data: DataFrame,
+    pub(crate) covered: Vec<CoveredRange>,
+}
+
+/// Ticks stored for one covered time range.
+#[derive(Debug, Clone)]
+pub(crate) struct TickBatch {
+    pub(crate) start: DateTime<Utc>,
+    pub(crate) end: DateTime<Utc>,
+    pub(crate) ticks: Vec<Tick>,
+}
+
+/// Key for entries cached per provider and symbol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct SymbolKey {
+    pub(crate) provider: String,
+    pub(crate) symbol: String,
+}
+
+impl SymbolKey {
+    pub(crate) fn new(provider: &str, symbol: &Symbol) -> Self {
+        Self {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+        }
+    }
+}
+
+/// Key for universe snapshots.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct OhlcvKey {
-    provider: String,
-    symbol: String,
-    start: NaiveDate,
-    end: NaiveDate,
+pub(crate) struct UniverseKey {
+    pub(crate) provider: String,
+    pub(crate) universe_id: String,
 }
 
+/// Snapshots of one universe, keyed by the date they were taken.
+pub(crate) type UniverseSnapshots = BTreeMap<NaiveDate, CacheEntry<Vec<Symbol>>>;
+
 /// Key for financials cache entries.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct FinancialsKey {
-    provider: String,
-    symbol: String,
-    period_type: PeriodType,
+pub(crate) struct FinancialsKey {
+    pub(crate) provider: String,
+    pub(crate) symbol: String,
+    pub(crate) period_type: PeriodType,
+}
+
+impl FinancialsKey {
+    pub(crate) fn cache_key(&self) -> CacheKey {
+        CacheKey::Financials {
+            provider: self.provider.clone(),
+            symbol: Symbol::new(&self.symbol),
+            period_type: self.period_type,
+        }
+    }
 }
 
 /// Key for metrics cache entries.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct MetricsKey {
-    provider: String,
-    symbol: String,
-    date: NaiveDate,
+pub(crate) struct MetricsKey {
+    pub(crate) provider: String,
+    pub(crate) symbol: String,
+    pub(crate) date: NaiveDate,
+}
+
+impl MetricsKey {
+    pub(crate) fn cache_key(&self) -> CacheKey {
+        CacheKey::Metrics {
+            provider: self.provider.clone(),
+            symbol: Symbol::new(&self.symbol),
+            date: self.date,
+        }
+    }
 }
 
 /// Simple in-memory cache for testing and development.
 ///
 /// Data is stored in `RwLock`-protected `HashMap`s and is lost when the cache
 /// is dropped. DataFrames and other types are cloned on get/put operations.
+/// Lookups are counted and reported with the contents by
+/// [`inspect`](DataCache::inspect).
+/// The cache grows without bound; long-running services should use
+/// [`BoundedMemoryCache`](crate::BoundedMemoryCache) instead.
 #[derive(Debug, Default)]
 pub struct InMemoryCache {
-    ohlcv: RwLock<HashMap<OhlcvKey, CacheEntry<DataFrame>>>,
+    ohlcv: RwLock<HashMap<OhlcvCacheKey, CacheEntry<OhlcvSeries>>>,
     financials: RwLock<HashMap<FinancialsKey, CacheEntry<Vec<FinancialStatement>>>>,
     metrics: RwLock<HashMap<MetricsKey, CacheEntry<KeyMetrics>>>,
+    ticks: RwLock<HashMap<SymbolKey, Vec<CacheEntry<TickBatch>>>>,
+    company_info: RwLock<HashMap<SymbolKey, CacheEntry<CompanyInfo>>>,
+    universes: RwLock<HashMap<UniverseKey, UniverseSnapshots>>,
+    negative: RwLock<HashMap<NegativeKey, CacheEntry<String>>>,
+    lookups: LookupCounter,
 }
 
 impl InMemoryCache {
@@ -76,47 +167,133 @@ impl InMemoryCache {
 
 #[async_trait]
 impl DataCache for InMemoryCache {
-    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
     async fn get_ohlcv(
         &self,
-        provider: &str,
-        symbol: &Symbol,
+        key: &OhlcvCacheKey,
         start: NaiveDate,
         end: NaiveDate,
-    ) -> Result<Option<DataFrame>> {
-        let key = OhlcvKey {
-            provider: provider.to_string(),
-            symbol: symbol.to_string(),
+    ) -> Result<OhlcvLookup> {
+        let cache = self.ohlcv.read().await;
+        let Some(entry) = cache.get(key) else {
+            self.lookups.record(false);
+            debug!("Cache miss for OHLCV data");
+            return Ok(OhlcvLookup::miss(start, end));
+        };
+
+        let data = filter_dates(&entry.data.data, start, end)?;
+        let lookup = OhlcvLookup::from_coverage(
+            (data.height() > 0).then_some(data),
+            &entry.data.covered,
             start,
             end,
+        );
+        debug!(gaps = lookup.missing.len(), "Cache lookup for OHLCV data");
+        self.lookups.record(lookup.is_complete());
+        Ok(lookup)
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let covered = CoveredRange::new(DateRange::new(start, end), Utc::now());
+        self.put_ohlcv_at(key, covered, data).await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered_range: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let mut cache = self.ohlcv.write().await;
+        let (series, cached_at) = match cache.remove(key) {
+            Some(entry) => {
+                let mut covered = entry.data.covered;
+                covered.push(covered_range);
+                let series = OhlcvSeries {
+                    data: stitch_ohlcv(vec![entry.data.data, data.clone()])?,
+                    covered,
+                };
+                (series, entry.cached_at.max(covered_range.cached_at))
+            }
+            None => {
+                let series = OhlcvSeries {
+                    data: data.clone(),
+                    covered: vec![covered_range],
+                };
+                (series, covered_range.cached_at)
+            }
         };
+        cache.insert(key.clone(), CacheEntry::at(series, cached_at));
+        debug!("Cached {} OHLCV rows", data.height());
+        Ok(())
+    }
 
-        let cache = self.ohlcv.read().await;
-        match cache.get(&key) {
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        let cache = self.ticks.read().await;
+        let batch = cache
+            .get(&SymbolKey::new(provider, symbol))
+            .and_then(|batches| {
+                batches
+                    .iter()
+                    .rev()
+                    .find(|b| b.data.start <= start && b.data.end >= end)
+            });
+        match batch {
             Some(entry) => {
-                debug!("Cache hit for OHLCV data");
-                Ok(Some(entry.data.clone()))
+                self.lookups.record(true);
+                debug!("Cache hit for ticks");
+                let ticks = entry
+                    .data
+                    .ticks
+                    .iter()
+                    .filter(|t| t.timestamp >= start && t.timestamp <= end)
+                    .cloned()
+                    .collect();
+                Ok(Some(Cached::new(ticks, entry.cached_at)))
             }
             None => {
-                debug!("Cache miss for OHLCV data");
+                self.lookups.record(false);
+                debug!("Cache miss for ticks");
                 Ok(None)
             }
         }
     }
 
-    #[instrument(skip(self, data), fields(provider = %provider, symbol = %symbol))]
-    async fn put_ohlcv(&self, provider: &str, symbol: &Symbol, data: &DataFrame) -> Result<()> {
-        let key = OhlcvKey {
-            provider: provider.to_string(),
-            symbol: symbol.to_string(),
-            // Extract date range from DataFrame
-            start: extract_min_date(data).unwrap_or(NaiveDate::MIN),
-            end: extract_max_date(data).unwrap_or(NaiveDate::MAX),
+    #[instrument(skip(self, ticks), fields(provider = %provider, symbol = %symbol, count = ticks.len()))]
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let batch = TickBatch {
+            start,
+            end,
+            ticks: ticks.to_vec(),
         };
-
-        let mut cache = self.ohlcv.write().await;
-        cache.insert(key, CacheEntry::new(data.clone()));
-        debug!("Cached {} OHLCV rows", data.height());
+        let mut cache = self.ticks.write().await;
+        cache
+            .entry(SymbolKey::new(provider, symbol))
+            .or_default()
+            .push(CacheEntry::new(batch));
+        debug!("Cached {} ticks", ticks.len());
         Ok(())
     }
 
@@ -126,7 +303,7 @@ impl DataCache for InMemoryCache {
         provider: &str,
         symbol: &Symbol,
         period_type: PeriodType,
-    ) -> Result<Option<Vec<FinancialStatement>>> {
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
         let key = FinancialsKey {
             provider: provider.to_string(),
             symbol: symbol.to_string(),
@@ -136,10 +313,12 @@ impl DataCache for InMemoryCache {
         let cache = self.financials.read().await;
         match cache.get(&key) {
             Some(entry) => {
+                self.lookups.record(true);
                 debug!("Cache hit for financials");
-                Ok(Some(entry.data.clone()))
+                Ok(Some(Cached::new(entry.data.clone(), entry.cached_at)))
             }
             None => {
+                self.lookups.record(false);
                 debug!("Cache miss for financials");
                 Ok(None)
             }
@@ -153,35 +332,39 @@ impl DataCache for InMemoryCache {
         symbol: &Symbol,
         statements: &[FinancialStatement],
     ) -> Result<()> {
-        // Group statements by period type
-        let mut quarterly: Vec<FinancialStatement> = Vec::new();
-        let mut annual: Vec<FinancialStatement> = Vec::new();
-
-        for stmt in statements {
-            match stmt.period_type {
-                PeriodType::Quarterly => quarterly.push(stmt.clone()),
-                PeriodType::Annual => annual.push(stmt.clone()),
-            }
-        }
+        let now = Utc::now();
+        let statements: Vec<Cached<FinancialStatement>> = statements
+            .iter()
+            .map(|s| Cached::new(s.clone(), now))
+            .collect();
+        self.put_financials_at(provider, symbol, &statements).await
+    }
 
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
+        // Statements are kept per period type with a single timestamp, so each
+        // group is stamped with its oldest statement's time
         let mut cache = self.financials.write().await;
-
-        if !quarterly.is_empty() {
-            let key = FinancialsKey {
-                provider: provider.to_string(),
-                symbol: symbol.to_string(),
-                period_type: PeriodType::Quarterly,
+        for period_type in [PeriodType::Quarterly, PeriodType::Annual] {
+            let group: Vec<&Cached<FinancialStatement>> = statements
+                .iter()
+                .filter(|s| s.value.period_type == period_type)
+                .collect();
+            let Some(cached_at) = group.iter().map(|s| s.cached_at).min() else {
+                continue;
             };
-            cache.insert(key, CacheEntry::new(quarterly));
-        }
-
-        if !annual.is_empty() {
             let key = FinancialsKey {
                 provider: provider.to_string(),
                 symbol: symbol.to_string(),
-                period_type: PeriodType::Annual,
+                period_type,
             };
-            cache.insert(key, CacheEntry::new(annual));
+            let group = group.into_iter().map(|s| s.value.clone()).collect();
+            cache.insert(key, CacheEntry::at(group, cached_at));
         }
 
         debug!("Cached {} financial statements", statements.len());
@@ -194,7 +377,7 @@ impl DataCache for InMemoryCache {
         provider: &str,
         symbol: &Symbol,
         date: NaiveDate,
-    ) -> Result<Option<KeyMetrics>> {
+    ) -> Result<Option<Cached<KeyMetrics>>> {
         let key = MetricsKey {
             provider: provider.to_string(),
             symbol: symbol.to_string(),
@@ -204,10 +387,12 @@ impl DataCache for InMemoryCache {
         let cache = self.metrics.read().await;
         match cache.get(&key) {
             Some(entry) => {
+                self.lookups.record(true);
                 debug!("Cache hit for metrics");
-                Ok(Some(entry.data.clone()))
+                Ok(Some(Cached::new(entry.data.clone(), entry.cached_at)))
             }
             None => {
+                self.lookups.record(false);
                 debug!("Cache miss for metrics");
                 Ok(None)
             }
@@ -220,19 +405,265 @@ impl DataCache for InMemoryCache {
         provider: &str,
         symbol: &Symbol,
         metrics: &KeyMetrics,
+    ) -> Result<()> {
+        self.put_metrics_at(provider, symbol, &Cached::new(metrics.clone(), Utc::now()))
+            .await
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
     ) -> Result<()> {
         let key = MetricsKey {
             provider: provider.to_string(),
             symbol: symbol.to_string(),
-            date: metrics.date,
+            date: metrics.value.date,
         };
 
         let mut cache = self.metrics.write().await;
-        cache.insert(key, CacheEntry::new(metrics.clone()));
+        cache.insert(
+            key,
+            CacheEntry::at(metrics.value.clone(), metrics.cached_at),
+        );
         debug!("Cached metrics");
         Ok(())
     }
 
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        let cache = self.company_info.read().await;
+        match cache.get(&SymbolKey::new(provider, symbol)) {
+            Some(entry) => {
+                self.lookups.record(true);
+                debug!("Cache hit for company info");
+                Ok(Some(Cached::new(entry.data.clone(), entry.cached_at)))
+            }
+            None => {
+                self.lookups.record(false);
+                debug!("Cache miss for company info");
+                Ok(None)
+            }
+        }
+    }
+
+    #[instrument(skip(self, info), fields(provider = %provider, symbol = %symbol))]
+    async fn put_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+    ) -> Result<()> {
+        let mut cache = self.company_info.write().await;
+        cache.insert(
+            SymbolKey::new(provider, symbol),
+            CacheEntry::new(info.clone()),
+        );
+        debug!("Cached company info");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, universe = %universe_id))]
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        let key = UniverseKey {
+            provider: provider.to_string(),
+            universe_id: universe_id.to_string(),
+        };
+
+        let cache = self.universes.read().await;
+        let snapshot = cache
+            .get(&key)
+            .and_then(|snapshots| snapshots.range(..=as_of).next_back());
+        match snapshot {
+            Some((date, entry)) => {
+                self.lookups.record(true);
+                debug!(snapshot = %date, "Cache hit for universe");
+                Ok(Some(Cached::new(entry.data.clone(), entry.cached_at)))
+            }
+            None => {
+                self.lookups.record(false);
+                debug!("Cache miss for universe");
+                Ok(None)
+            }
+        }
+    }
+
+    #[instrument(skip(self, symbols), fields(provider = %provider, universe = %universe_id, count = symbols.len()))]
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()> {
+        let key = UniverseKey {
+            provider: provider.to_string(),
+            universe_id: universe_id.to_string(),
+        };
+
+        let mut cache = self.universes.write().await;
+        cache
+            .entry(key)
+            .or_default()
+            .insert(as_of, CacheEntry::new(symbols.to_vec()));
+        debug!("Cached universe snapshot");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        let cache = self.negative.read().await;
+        Ok(cache
+            .get(key)
+            .map(|entry| Cached::new(entry.data.clone(), entry.cached_at)))
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        let mut cache = self.negative.write().await;
+        cache.insert(key.clone(), CacheEntry::new(message.to_string()));
+        debug!("Cached negative entry");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        let mut keys: Vec<CacheKey> = self
+            .ohlcv
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .map(CacheKey::Ohlcv)
+            .collect();
+        keys.extend(
+            self.financials
+                .read()
+                .await
+                .keys()
+                .map(FinancialsKey::cache_key),
+        );
+        keys.extend(self.metrics.read().await.keys().map(MetricsKey::cache_key));
+        Ok(keys)
+    }
+
+    #[instrument(skip(self))]
+    async fn inspect(&self) -> Result<CacheStats> {
+        let mut entries = Vec::new();
+
+        for (key, entry) in self.ohlcv.read().await.iter() {
+            let mut stats = CacheEntryStats::ohlcv(key, entry.cached_at);
+            for covered in &entry.data.covered {
+                stats.observe(covered.cached_at);
+                stats.covered.push(covered.range);
+            }
+            stats.rows = entry.data.data.height();
+            stats.bytes = entry.data.data.estimated_size();
+            entries.push(stats);
+        }
+
+        for (key, batches) in self.ticks.read().await.iter() {
+            let Some(first) = batches.first() else {
+                continue;
+            };
+            let mut stats = CacheEntryStats::new(
+                CacheDataKind::Ticks,
+                &key.provider,
+                &key.symbol,
+                first.cached_at,
+            );
+            for batch in batches {
+                stats.observe(batch.cached_at);
+                stats.covered.push(DateRange::new(
+                    batch.data.start.date_naive(),
+                    batch.data.end.date_naive(),
+                ));
+                stats.rows += batch.data.ticks.len();
+                stats.bytes += batch.data.ticks.iter().map(tick_weight).sum::<usize>();
+            }
+            entries.push(stats);
+        }
+
+        for (key, entry) in self.financials.read().await.iter() {
+            let mut stats = CacheEntryStats::new(
+                CacheDataKind::Financials,
+                &key.provider,
+                &key.symbol,
+                entry.cached_at,
+            )
+            .with_period_type(key.period_type);
+            stats.rows = entry.data.len();
+            stats.bytes = entry.data.len() * std::mem::size_of::<FinancialStatement>();
+            entries.push(stats);
+        }
+
+        let mut metrics: HashMap<(&str, &str), CacheEntryStats> = HashMap::new();
+        let metrics_cache = self.metrics.read().await;
+        for (key, entry) in metrics_cache.iter() {
+            let stats = metrics
+                .entry((&key.provider, &key.symbol))
+                .or_insert_with(|| {
+                    CacheEntryStats::new(
+                        CacheDataKind::Metrics,
+                        &key.provider,
+                        &key.symbol,
+                        entry.cached_at,
+                    )
+                });
+            stats.observe(entry.cached_at);
+            stats.rows += 1;
+            stats.bytes += std::mem::size_of::<KeyMetrics>();
+        }
+        entries.extend(metrics.into_values());
+
+        for (key, entry) in self.company_info.read().await.iter() {
+            let mut stats = CacheEntryStats::new(
+                CacheDataKind::CompanyInfo,
+                &key.provider,
+                &key.symbol,
+                entry.cached_at,
+            );
+            stats.rows = 1;
+            stats.bytes = company_info_weight(&entry.data);
+            entries.push(stats);
+        }
+
+        for (key, snapshots) in self.universes.read().await.iter() {
+            let Some(first) = snapshots.values().next() else {
+                continue;
+            };
+            let mut stats = CacheEntryStats::new(
+                CacheDataKind::Universe,
+                &key.provider,
+                &key.universe_id,
+                first.cached_at,
+            );
+            for entry in snapshots.values() {
+                stats.observe(entry.cached_at);
+                stats.rows += 1;
+                stats.bytes += entry.data.iter().map(symbol_weight).sum::<usize>();
+            }
+            entries.push(stats);
+        }
+
+        Ok(CacheStats::new(
+            entries,
+            self.lookups.hits(),
+            self.lookups.misses(),
+        ))
+    }
+
     #[instrument(skip(self))]
     async fn invalidate_stale(&self, ttl: Duration) -> Result<usize> {
         let mut total_removed = 0usize;
@@ -261,6 +692,44 @@ impl DataCache for InMemoryCache {
             total_removed += before - cache.len();
         }
 
+        // Invalidate stale tick batches
+        {
+            let mut cache = self.ticks.write().await;
+            for batches in cache.values_mut() {
+                let before = batches.len();
+                batches.retain(|entry| !entry.is_stale(ttl));
+                total_removed += before - batches.len();
+            }
+            cache.retain(|_, batches| !batches.is_empty());
+        }
+
+        // Invalidate stale company info entries
+        {
+            let mut cache = self.company_info.write().await;
+            let before = cache.len();
+            cache.retain(|_, entry| !entry.is_stale(ttl));
+            total_removed += before - cache.len();
+        }
+
+        // Invalidate stale universe snapshots
+        {
+            let mut cache = self.universes.write().await;
+            for snapshots in cache.values_mut() {
+                let before = snapshots.len();
+                snapshots.retain(|_, entry| !entry.is_stale(ttl));
+                total_removed += before - snapshots.len();
+            }
+            cache.retain(|_, snapshots| !snapshots.is_empty());
+        }
+
+        // Invalidate stale negative entries
+        {
+            let mut cache = self.negative.write().await;
+            let before = cache.len();
+            cache.retain(|_, entry| !entry.is_stale(ttl));
+            total_removed += before - cache.len();
+        }
+
         if total_removed > 0 {
             debug!("Invalidated {} stale cache entries", total_removed);
         }
@@ -273,40 +742,50 @@ impl DataCache for InMemoryCache {
         self.ohlcv.write().await.clear();
         self.financials.write().await.clear();
         self.metrics.write().await.clear();
+        self.ticks.write().await.clear();
+        self.company_info.write().await.clear();
+        self.universes.write().await.clear();
+        self.negative.write().await.clear();
         debug!("Cleared all cache entries");
         Ok(())
     }
 }
 
-/// Extract minimum date from a DataFrame's "date" column.
-#[allow(dead_code)]
-fn extract_min_date(df: &DataFrame) -> Option<NaiveDate> {
-    let dates = df.column("date").ok()?;
-    let dates = dates.date().ok()?;
-    // Get the physical i32 representation and find min
-    let physical = dates.0.clone();
-    let min_days: i32 = ChunkAgg::min(&physical)?;
-    // Polars dates are days since Unix epoch (1970-01-01)
-    chrono::NaiveDate::from_num_days_from_ce_opt(min_days + 719_163)
-}
-
-/// Extract maximum date from a DataFrame's "date" column.
-#[allow(dead_code)]
-fn extract_max_date(df: &DataFrame) -> Option<NaiveDate> {
-    let dates = df.column("date").ok()?;
-    let dates = dates.date().ok()?;
-    // Get the physical i32 representation and find max
-    let physical = dates.0.clone();
-    let max_days: i32 = ChunkAgg::max(&physical)?;
-    // Polars dates are days since Unix epoch (1970-01-01)
-    chrono::NaiveDate::from_num_days_from_ce_opt(max_days + 719_163)
+/// Select the rows of an OHLCV frame dated within `start..=end`.
+pub(crate) fn filter_dates(df: &DataFrame, start: NaiveDate, end: NaiveDate) -> Result<DataFrame> {
+    if df.height() == 0 {
+        return Ok(df.clone());
+    }
+    let date = col("date").cast(DataType::Date);
+    df.clone()
+        .lazy()
+        .filter(date.clone().gt_eq(lit(start)).and(date.lt_eq(lit(end))))
+        .collect()
+        .map_err(|e| DataError::Cache(e.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
-    use polars::prelude::*;
+    use data_core::DataFrequency;
+
+    fn ohlcv_frame(dates: &[&str], closes: &[f64]) -> DataFrame {
+        DataFrame::new(vec![
+            Column::new("symbol".into(), vec!["AAPL"; dates.len()]),
+            Column::new("date".into(), dates.to_vec()),
+            Column::new("open".into(), closes.to_vec()),
+            Column::new("high".into(), closes.to_vec()),
+            Column::new("low".into(), closes.to_vec()),
+            Column::new("close".into(), closes.to_vec()),
+            Column::new("volume".into(), vec![1000000.0; dates.len()]),
+        ])
+        .unwrap()
+        .lazy()
+        .with_column(col("date").cast(DataType::Date))
+        .collect()
+        .unwrap()
+    }
 
     #[tokio::test]
     async fn test_memory_cache_ohlcv() {
@@ -315,31 +794,51 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
 
-        // Initially no data
-        let result = cache.get_ohlcv("test", &symbol, start, end).await.unwrap();
-        assert!(result.is_none());
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
 
-        // Create test DataFrame
-        let df = DataFrame::new(vec![
-            Column::new("symbol".into(), vec!["AAPL", "AAPL"]),
-            Column::new("date".into(), vec!["2024-01-02", "2024-01-03"]),
-            Column::new("open".into(), vec![150.0, 151.0]),
-            Column::new("high".into(), vec![152.0, 153.0]),
-            Column::new("low".into(), vec![149.0, 150.0]),
-            Column::new("close".into(), vec![151.0, 152.0]),
-            Column::new("volume".into(), vec![1000000.0, 1100000.0]),
-        ])
-        .unwrap();
+        // Initially no data
+        let result = cache.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(result.is_miss());
 
         // Store data
-        cache.put_ohlcv("test", &symbol, &df).await.unwrap();
+        let df = ohlcv_frame(&["2024-01-02", "2024-01-03"], &[151.0, 152.0]);
+        cache.put_ohlcv(&key, start, end, &df).await.unwrap();
 
-        // Retrieve data - note we need the exact same key
-        let result = cache
-            .get_ohlcv("test", &symbol, NaiveDate::MIN, NaiveDate::MAX)
-            .await
-            .unwrap();
-        assert!(result.is_some());
+        // Retrieve data
+        let result = cache.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(result.is_complete());
+        assert_eq!(result.data.unwrap().height(), 2);
+
+        // A different frequency is a different series
+        let hourly = OhlcvCacheKey::new("test", &symbol, DataFrequency::Hourly);
+        let result = cache.get_ohlcv(&hourly, start, end).await.unwrap();
+        assert!(result.is_miss());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_ohlcv_reports_gaps() {
+        let cache = InMemoryCache::new();
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let date = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        let df = ohlcv_frame(&["2024-01-02", "2024-01-03"], &[151.0, 152.0]);
+        cache.put_ohlcv(&key, date(1), date(5), &df).await.unwrap();
+
+        // Only the uncovered tail is missing
+        let result = cache.get_ohlcv(&key, date(3), date(10)).await.unwrap();
+        assert_eq!(result.data.unwrap().height(), 1);
+        assert_eq!(result.missing, vec![DateRange::new(date(6), date(10))]);
+
+        // Filling the gap, with an overlapping row, completes the range
+        let df = ohlcv_frame(&["2024-01-03", "2024-01-08"], &[152.5, 153.0]);
+        cache.put_ohlcv(&key, date(3), date(10), &df).await.unwrap();
+        let result = cache.get_ohlcv(&key, date(1), date(10)).await.unwrap();
+        assert!(result.is_complete());
+        let data = result.data.unwrap();
+        assert_eq!(data.height(), 3);
+        let closes: Vec<Option<f64>> = data.column("close").unwrap().f64().unwrap().to_vec();
+        assert_eq!(closes, [Some(151.0), Some(152.5), Some(153.0)]);
     }
 
     #[tokio::test]
@@ -368,7 +867,124 @@ mod tests {
         let result = cache.get_metrics("test", &symbol, date).await.unwrap();
         assert!(result.is_some());
         let retrieved = result.unwrap();
-        assert_eq!(retrieved.market_cap, Some(3_000_000_000_000.0));
+        assert_eq!(retrieved.value.market_cap, Some(3_000_000_000_000.0));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_ticks() {
+        let cache = InMemoryCache::new();
+        let symbol = Symbol::new("AAPL");
+        let start = DateTime::parse_from_rfc3339("2024-01-02T14:30:00Z")
+            .unwrap()
+            .to_utc();
+        let end = start + chrono::Duration::hours(1);
+        let ticks = vec![
+            Tick::new(symbol.clone(), start, 150.0, 100.0),
+            Tick::new(symbol.clone(), end, 151.0, 200.0),
+        ];
+
+        cache
+            .put_ticks("test", &symbol, start, end, &ticks)
+            .await
+            .unwrap();
+
+        let inner = cache
+            .get_ticks(
+                "test",
+                &symbol,
+                start,
+                start + chrono::Duration::minutes(30),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(inner.value, ticks[..1]);
+
+        // A range extending past what was stored is a miss
+        let result = cache
+            .get_ticks("test", &symbol, start, end + chrono::Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_reference_data() {
+        let cache = InMemoryCache::new();
+        let symbol = Symbol::new("AAPL");
+        let info = CompanyInfo::new(
+            symbol.clone(),
+            "Apple Inc.",
+            "NASDAQ",
+            "Technology",
+            "Consumer Electronics",
+            "US",
+            "USD",
+        );
+
+        cache
+            .put_company_info("test", &symbol, &info)
+            .await
+            .unwrap();
+        let cached = cache.get_company_info("test", &symbol).await.unwrap();
+        assert_eq!(cached.map(|c| c.value), Some(info));
+
+        let jan = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let feb = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        cache
+            .put_universe("test", "sp500", jan, &[Symbol::new("AAPL")])
+            .await
+            .unwrap();
+        cache
+            .put_universe("test", "sp500", feb, &[Symbol::new("MSFT")])
+            .await
+            .unwrap();
+
+        let mid_jan = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let snapshot = cache.get_universe("test", "sp500", mid_jan).await.unwrap();
+        assert_eq!(snapshot.map(|c| c.value), Some(vec![Symbol::new("AAPL")]));
+        let snapshot = cache.get_universe("test", "sp500", feb).await.unwrap();
+        assert_eq!(snapshot.map(|c| c.value), Some(vec![Symbol::new("MSFT")]));
+        let before = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        assert!(
+            cache
+                .get_universe("test", "sp500", before)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_inspect() {
+        let cache = InMemoryCache::new();
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        let df = ohlcv_frame(&["2024-01-02", "2024-01-03"], &[100.0, 101.0]);
+        cache.put_ohlcv(&key, start, end, &df).await.unwrap();
+        cache
+            .put_universe("test", "sp500", start, std::slice::from_ref(&symbol))
+            .await
+            .unwrap();
+
+        cache.get_ohlcv(&key, start, end).await.unwrap();
+        cache
+            .get_ohlcv(&key, start, end.succ_opt().unwrap())
+            .await
+            .unwrap();
+
+        let stats = cache.inspect().await.unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate(), Some(0.5));
+        assert_eq!(stats.entries.len(), 2);
+        assert_eq!(stats.entries[0].kind, CacheDataKind::Ohlcv);
+        assert_eq!(stats.entries[0].rows, 2);
+        assert_eq!(stats.entries[0].covered, vec![DateRange::new(start, end)]);
+        assert_eq!(stats.entries[1].kind, CacheDataKind::Universe);
+        assert_eq!(stats.entries[1].id, "sp500");
+        assert_eq!(stats.rows(), 3);
     }
 
     #[tokio::test]
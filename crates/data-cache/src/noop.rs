@@ -1,8 +1,11 @@
 //! No-op cache implementation.
 
 use async_trait::async_trait;
-use chrono::NaiveDate;
-use data_core::{DataCache, FinancialStatement, KeyMetrics, PeriodType, Result, Symbol};
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheKey, Cached, CompanyInfo, DataCache, FinancialStatement, KeyMetrics, OhlcvCacheKey,
+    OhlcvLookup, PeriodType, Result, Symbol, Tick,
+};
 use polars::prelude::DataFrame;
 use std::time::Duration;
 use tracing::trace;
@@ -26,17 +29,45 @@ impl NoopCache {
 impl DataCache for NoopCache {
     async fn get_ohlcv(
         &self,
-        _provider: &str,
-        _symbol: &Symbol,
+        _key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        trace!("NoopCache: get_ohlcv called, returning a miss");
+        Ok(OhlcvLookup::miss(start, end))
+    }
+
+    async fn put_ohlcv(
+        &self,
+        _key: &OhlcvCacheKey,
         _start: NaiveDate,
         _end: NaiveDate,
-    ) -> Result<Option<DataFrame>> {
-        trace!("NoopCache: get_ohlcv called, returning None");
+        _data: &DataFrame,
+    ) -> Result<()> {
+        trace!("NoopCache: put_ohlcv called, doing nothing");
+        Ok(())
+    }
+
+    async fn get_ticks(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        trace!("NoopCache: get_ticks called, returning None");
         Ok(None)
     }
 
-    async fn put_ohlcv(&self, _provider: &str, _symbol: &Symbol, _data: &DataFrame) -> Result<()> {
-        trace!("NoopCache: put_ohlcv called, doing nothing");
+    async fn put_ticks(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+        _ticks: &[Tick],
+    ) -> Result<()> {
+        trace!("NoopCache: put_ticks called, doing nothing");
         Ok(())
     }
 
@@ -45,7 +76,7 @@ impl DataCache for NoopCache {
         _provider: &str,
         _symbol: &Symbol,
         _period_type: PeriodType,
-    ) -> Result<Option<Vec<FinancialStatement>>> {
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
         trace!("NoopCache: get_financials called, returning None");
         Ok(None)
     }
@@ -65,7 +96,7 @@ impl DataCache for NoopCache {
         _provider: &str,
         _symbol: &Symbol,
         _date: NaiveDate,
-    ) -> Result<Option<KeyMetrics>> {
+    ) -> Result<Option<Cached<KeyMetrics>>> {
         trace!("NoopCache: get_metrics called, returning None");
         Ok(None)
     }
@@ -80,6 +111,94 @@ impl DataCache for NoopCache {
         Ok(())
     }
 
+    async fn get_company_info(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        trace!("NoopCache: get_company_info called, returning None");
+        Ok(None)
+    }
+
+    async fn put_company_info(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _info: &CompanyInfo,
+    ) -> Result<()> {
+        trace!("NoopCache: put_company_info called, doing nothing");
+        Ok(())
+    }
+
+    async fn get_universe(
+        &self,
+        _provider: &str,
+        _universe_id: &str,
+        _as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        trace!("NoopCache: get_universe called, returning None");
+        Ok(None)
+    }
+
+    async fn put_universe(
+        &self,
+        _provider: &str,
+        _universe_id: &str,
+        _as_of: NaiveDate,
+        _symbols: &[Symbol],
+    ) -> Result<()> {
+        trace!("NoopCache: put_universe called, doing nothing");
+        Ok(())
+    }
+
+    async fn get_ohlcv_as_of(
+        &self,
+        _key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        _knowledge_time: DateTime<Utc>,
+    ) -> Result<OhlcvLookup> {
+        trace!("NoopCache: get_ohlcv_as_of called, returning a miss");
+        Ok(OhlcvLookup::miss(start, end))
+    }
+
+    async fn get_financials_as_of(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _period_type: PeriodType,
+        _knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        trace!("NoopCache: get_financials_as_of called, returning None");
+        Ok(None)
+    }
+
+    async fn get_metrics_as_of(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _date: NaiveDate,
+        _knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        trace!("NoopCache: get_metrics_as_of called, returning None");
+        Ok(None)
+    }
+
+    async fn get_company_info_as_of(
+        &self,
+        _provider: &str,
+        _symbol: &Symbol,
+        _knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        trace!("NoopCache: get_company_info_as_of called, returning None");
+        Ok(None)
+    }
+
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        trace!("NoopCache: keys called, returning nothing");
+        Ok(Vec::new())
+    }
+
     async fn invalidate_stale(&self, _ttl: Duration) -> Result<usize> {
         trace!("NoopCache: invalidate_stale called, returning 0");
         Ok(0)
@@ -95,6 +214,7 @@ impl DataCache for NoopCache {
 mod tests {
     use super::*;
     use chrono::NaiveDate;
+    use data_core::DataFrequency;
     use polars::prelude::*;
 
     #[tokio::test]
@@ -104,13 +224,17 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
 
-        // All get operations should return None
+        // All get operations should miss
         assert!(
             cache
-                .get_ohlcv("test", &symbol, start, end)
+                .get_ohlcv(
+                    &OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily),
+                    start,
+                    end
+                )
                 .await
                 .unwrap()
-                .is_none()
+                .is_miss()
         );
         assert!(
             cache
@@ -126,6 +250,20 @@ mod tests {
                 .unwrap()
                 .is_none()
         );
+        assert!(
+            cache
+                .get_company_info("test", &symbol)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            cache
+                .get_universe("test", "sp500", end)
+                .await
+                .unwrap()
+                .is_none()
+        );
     }
 
     #[tokio::test]
@@ -146,7 +284,17 @@ mod tests {
         .unwrap();
 
         // All put operations should succeed
-        assert!(cache.put_ohlcv("test", &symbol, &df).await.is_ok());
+        assert!(
+            cache
+                .put_ohlcv(
+                    &OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily),
+                    NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                    NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                    &df
+                )
+                .await
+                .is_ok()
+        );
 
         let stmt = FinancialStatement::new(
             symbol.clone(),
@@ -0,0 +1,1857 @@
+//! Parquet-backed on-disk cache implementation.
+//!
+//! OHLCV series are stored as hive-style partitioned Parquet files:
+//!
+//! ```text
+//! <root>/ohlcv/provider=<p>/frequency=<f>/adjustment=<a>/symbol=<s>/year=<y>/part-<n>.parquet
+//! <root>/ohlcv/provider=<p>/frequency=<f>/adjustment=<a>/symbol=<s>/_coverage.json
+//! <root>/financials/provider=<p>/symbol=<s>/period=<annual|quarterly>/data.parquet
+//! <root>/metrics/provider=<p>/symbol=<s>/data.parquet
+//! <root>/ticks/provider=<p>/symbol=<s>/date=<yyyy-mm-dd>/data.parquet
+//! <root>/ticks/provider=<p>/symbol=<s>/_coverage.json
+//! <root>/companies/provider=<p>/data.parquet
+//! <root>/universes/provider=<p>/universe=<u>/data.parquet
+//! ```
+//!
+//! Each `put_ohlcv` appends one file per touched year rather than rewriting
+//! rows, so writes stay cheap for universe-scale history. Reads only open the
+//! year partitions overlapping the requested range and push the date filter
+//! down into the Parquet scan. Once a partition accumulates
+//! [`compaction_threshold`](ParquetCache::with_compaction_threshold) files it is
+//! compacted into a single file on a background blocking task, and the
+//! series' overlapping and adjacent coverage ranges are merged.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use data_core::{
+    CacheKey, Cached, CompanyInfo, CoveredRange, DataCache, DataError, DateRange,
+    FinancialStatement, KeyMetrics, NegativeKey, OhlcvCacheKey, OhlcvLookup, PeriodType, Result,
+    Symbol, Tick, missing_ranges, stitch_ohlcv,
+};
+use polars::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
+
+/// Default number of files in a partition that triggers compaction.
+const DEFAULT_COMPACTION_THRESHOLD: usize = 8;
+
+/// Column holding the time a row was cached, in milliseconds since the epoch.
+const CACHED_AT: &str = "_cached_at";
+
+/// Name of the per-series file recording covered date ranges.
+const COVERAGE_FILE: &str = "_coverage.json";
+
+/// Top-level directories holding cached data.
+const DATA_DIRS: [&str; 7] = [
+    "ohlcv",
+    "financials",
+    "metrics",
+    "ticks",
+    "companies",
+    "universes",
+    "negative",
+];
+
+/// A covered date or time range and when it was stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CoverageEntry<T = NaiveDate> {
+    start: T,
+    end: T,
+    cached_at: DateTime<Utc>,
+}
+
+/// A stored universe snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+struct UniverseRow {
+    as_of: NaiveDate,
+    /// Members as a JSON array.
+    symbols: String,
+}
+
+/// A remembered "not found" answer from a provider.
+#[derive(Debug, Serialize, Deserialize)]
+struct NegativeRow {
+    symbol: String,
+    /// The request that had no data, or empty if the symbol is unknown.
+    request: String,
+    message: String,
+}
+
+/// Parquet-backed on-disk cache.
+///
+/// Suited to large OHLCV histories where SQLite's row-by-row inserts become a
+/// bottleneck. Blocking file I/O runs on tokio's blocking thread pool.
+#[derive(Debug, Clone)]
+pub struct ParquetCache {
+    store: Arc<Store>,
+    compaction_threshold: usize,
+}
+
+impl ParquetCache {
+    /// Create a Parquet cache rooted at the given directory.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created.
+    pub fn new(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root).map_err(|e| DataError::Cache(e.to_string()))?;
+        Ok(Self {
+            store: Arc::new(Store {
+                root,
+                lock: RwLock::new(()),
+                sequence: AtomicU64::new(0),
+            }),
+            compaction_threshold: DEFAULT_COMPACTION_THRESHOLD,
+        })
+    }
+
+    /// Set how many files a year partition may hold before it is compacted.
+    #[must_use]
+    pub fn with_compaction_threshold(mut self, files: usize) -> Self {
+        self.compaction_threshold = files.max(2);
+        self
+    }
+
+    /// Returns the cache root directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.store.root
+    }
+
+    /// Compact every OHLCV partition holding more than one file.
+    ///
+    /// Returns the number of partitions compacted.
+    ///
+    /// # Errors
+    /// Returns an error if a partition cannot be read or rewritten.
+    pub async fn compact(&self) -> Result<usize> {
+        let store = self.store.clone();
+        blocking(move || {
+            let mut compacted = 0;
+            for partition in store.ohlcv_partitions()? {
+                if store.compact_partition(&partition, 2)? {
+                    compacted += 1;
+                }
+            }
+            Ok(compacted)
+        })
+        .await
+    }
+
+    /// Compact the given partitions in the background if they are over the threshold.
+    fn schedule_compaction(&self, partitions: Vec<PathBuf>) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let store = self.store.clone();
+        let threshold = self.compaction_threshold;
+        handle.spawn_blocking(move || {
+            for partition in partitions {
+                // Count the files before taking the write lock in compaction
+                if part_files(&partition).is_ok_and(|files| files.len() < threshold) {
+                    continue;
+                }
+                if let Err(e) = store.compact_partition(&partition, threshold) {
+                    warn!(partition = %partition.display(), error = %e, "Parquet compaction failed");
+                }
+            }
+        });
+    }
+}
+
+/// Shared on-disk state.
+///
+/// Reads hold the lock shared; writes and compaction hold it exclusively so a
+/// reader never observes a partition while its files are being replaced.
+#[derive(Debug)]
+struct Store {
+    root: PathBuf,
+    lock: RwLock<()>,
+    sequence: AtomicU64,
+}
+
+impl Store {
+    fn series_dir(&self, key: &OhlcvCacheKey) -> PathBuf {
+        self.root
+            .join("ohlcv")
+            .join(partition("provider", &key.provider))
+            .join(partition("frequency", key.frequency.as_str()))
+            .join(partition("adjustment", key.adjustment.as_str()))
+            .join(partition("symbol", key.symbol.as_str()))
+    }
+
+    fn financials_file(&self, provider: &str, symbol: &Symbol, period_type: PeriodType) -> PathBuf {
+        let period = match period_type {
+            PeriodType::Annual => "annual",
+            PeriodType::Quarterly => "quarterly",
+        };
+        self.root
+            .join("financials")
+            .join(partition("provider", provider))
+            .join(partition("symbol", symbol.as_str()))
+            .join(partition("period", period))
+            .join("data.parquet")
+    }
+
+    fn metrics_file(&self, provider: &str, symbol: &Symbol) -> PathBuf {
+        self.root
+            .join("metrics")
+            .join(partition("provider", provider))
+            .join(partition("symbol", symbol.as_str()))
+            .join("data.parquet")
+    }
+
+    fn negative_file(&self, provider: &str) -> PathBuf {
+        self.root
+            .join("negative")
+            .join(partition("provider", provider))
+            .join("data.parquet")
+    }
+
+    fn ticks_dir(&self, provider: &str, symbol: &Symbol) -> PathBuf {
+        self.root
+            .join("ticks")
+            .join(partition("provider", provider))
+            .join(partition("symbol", symbol.as_str()))
+    }
+
+    fn companies_file(&self, provider: &str) -> PathBuf {
+        self.root
+            .join("companies")
+            .join(partition("provider", provider))
+            .join("data.parquet")
+    }
+
+    fn universe_file(&self, provider: &str, universe_id: &str) -> PathBuf {
+        self.root
+            .join("universes")
+            .join(partition("provider", provider))
+            .join(partition("universe", universe_id))
+            .join("data.parquet")
+    }
+
+    /// Path for a new part file, ordered after every existing part.
+    fn new_part_path(&self, partition: &Path) -> PathBuf {
+        let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        partition.join(format!("part-{nanos:020}-{sequence:06}.parquet"))
+    }
+
+    fn read_coverage<T: DeserializeOwned>(
+        &self,
+        series_dir: &Path,
+    ) -> Result<Vec<CoverageEntry<T>>> {
+        let path = series_dir.join(COVERAGE_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = fs::read_to_string(&path).map_err(|e| DataError::Cache(e.to_string()))?;
+        serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))
+    }
+
+    fn write_coverage<T: Serialize>(
+        &self,
+        series_dir: &Path,
+        coverage: &[CoverageEntry<T>],
+    ) -> Result<()> {
+        let json = serde_json::to_string(coverage).map_err(|e| DataError::Parse(e.to_string()))?;
+        write_atomic(&series_dir.join(COVERAGE_FILE), |path| {
+            fs::write(path, &json).map_err(|e| DataError::Cache(e.to_string()))
+        })
+    }
+
+    fn get_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        let _guard = self.lock.read().unwrap_or_else(|e| e.into_inner());
+        let series_dir = self.series_dir(key);
+
+        let coverage: Vec<CoveredRange> = self
+            .read_coverage::<NaiveDate>(&series_dir)?
+            .iter()
+            .map(|c| CoveredRange::new(DateRange::new(c.start, c.end), c.cached_at))
+            .collect();
+
+        // Partition pruning: only open the years overlapping the request
+        let mut frames = Vec::new();
+        for year in start.year()..=end.year() {
+            for file in part_files(&series_dir.join(partition("year", &year.to_string())))? {
+                let df = LazyFrame::scan_parquet(&file, ScanArgsParquet::default())
+                    .and_then(|lf| {
+                        lf.filter(
+                            col("date")
+                                .gt_eq(lit(start))
+                                .and(col("date").lt_eq(lit(end))),
+                        )
+                        .collect()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                frames.push(df);
+            }
+        }
+
+        let data = stitch_ohlcv(frames)?;
+        if data.height() == 0 {
+            debug!("No cached OHLCV data found");
+            return Ok(OhlcvLookup::from_coverage(None, &coverage, start, end));
+        }
+        let data = data.drop(CACHED_AT).unwrap_or(data);
+        debug!(
+            coverage = coverage.len(),
+            "Found {} cached OHLCV rows",
+            data.height()
+        );
+        Ok(OhlcvLookup::from_coverage(
+            Some(data),
+            &coverage,
+            start,
+            end,
+        ))
+    }
+
+    /// Write OHLCV rows and record the covered range, returning the touched partitions.
+    fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        covered: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<Vec<PathBuf>> {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let series_dir = self.series_dir(key);
+        let CoveredRange { range, cached_at } = covered;
+        let mut touched = Vec::new();
+
+        if data.height() > 0 {
+            let data = data
+                .clone()
+                .lazy()
+                .with_columns([
+                    col("date").cast(DataType::Date),
+                    lit(cached_at.timestamp_millis()).alias(CACHED_AT),
+                ])
+                .collect()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            let years: Vec<Option<i32>> = data
+                .column("date")
+                .and_then(|c| {
+                    c.date()
+                        .map(|d| d.as_date_iter().map(|d| d.map(|d| d.year())).collect())
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let mut distinct: Vec<i32> = years.iter().flatten().copied().collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+
+            for year in distinct {
+                let mask: BooleanChunked = years.iter().map(|y| *y == Some(year)).collect();
+                let mut rows = data
+                    .filter(&mask)
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                let partition_dir = series_dir.join(partition("year", &year.to_string()));
+                fs::create_dir_all(&partition_dir).map_err(|e| DataError::Cache(e.to_string()))?;
+                write_parquet(&self.new_part_path(&partition_dir), &mut rows)?;
+                touched.push(partition_dir);
+            }
+        } else {
+            fs::create_dir_all(&series_dir).map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+
+        let mut coverage = self.read_coverage(&series_dir)?;
+        coverage.push(CoverageEntry {
+            start: range.start,
+            end: range.end,
+            cached_at,
+        });
+        self.write_coverage(&series_dir, &coverage)?;
+        debug!("Cached {} OHLCV rows", data.height());
+        Ok(touched)
+    }
+
+    /// Returns every OHLCV year partition directory.
+    fn ohlcv_partitions(&self) -> Result<Vec<PathBuf>> {
+        let mut partitions = Vec::new();
+        let mut pending = vec![self.root.join("ohlcv")];
+        while let Some(dir) = pending.pop() {
+            for path in subdirs(&dir)? {
+                let is_year = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("year="));
+                if is_year {
+                    partitions.push(path);
+                } else {
+                    pending.push(path);
+                }
+            }
+        }
+        partitions.sort();
+        Ok(partitions)
+    }
+
+    /// List the OHLCV series, financials and metrics stored under the root.
+    fn keys(&self) -> Result<Vec<CacheKey>> {
+        let _guard = self.lock.read().unwrap_or_else(|e| e.into_inner());
+        let mut keys = Vec::new();
+
+        for provider_dir in subdirs(&self.root.join("ohlcv"))? {
+            let provider = partition_value(&provider_dir, "provider")?;
+            for frequency_dir in subdirs(&provider_dir)? {
+                let frequency = partition_value(&frequency_dir, "frequency")?.parse()?;
+                for adjustment_dir in subdirs(&frequency_dir)? {
+                    let adjustment = partition_value(&adjustment_dir, "adjustment")?.parse()?;
+                    for series_dir in subdirs(&adjustment_dir)? {
+                        if !series_dir.join(COVERAGE_FILE).exists() {
+                            continue;
+                        }
+                        let symbol = Symbol::new(partition_value(&series_dir, "symbol")?);
+                        keys.push(CacheKey::Ohlcv(
+                            OhlcvCacheKey::new(provider.clone(), &symbol, frequency)
+                                .with_adjustment(adjustment),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for provider_dir in subdirs(&self.root.join("financials"))? {
+            let provider = partition_value(&provider_dir, "provider")?;
+            for symbol_dir in subdirs(&provider_dir)? {
+                let symbol = Symbol::new(partition_value(&symbol_dir, "symbol")?);
+                for period_dir in subdirs(&symbol_dir)? {
+                    if !period_dir.join("data.parquet").exists() {
+                        continue;
+                    }
+                    let period_type = match partition_value(&period_dir, "period")?.as_str() {
+                        "annual" => PeriodType::Annual,
+                        "quarterly" => PeriodType::Quarterly,
+                        other => {
+                            return Err(DataError::Parse(format!("unknown period: {other}")));
+                        }
+                    };
+                    keys.push(CacheKey::Financials {
+                        provider: provider.clone(),
+                        symbol: symbol.clone(),
+                        period_type,
+                    });
+                }
+            }
+        }
+
+        for provider_dir in subdirs(&self.root.join("metrics"))? {
+            let provider = partition_value(&provider_dir, "provider")?;
+            for symbol_dir in subdirs(&provider_dir)? {
+                let path = symbol_dir.join("data.parquet");
+                if !path.exists() {
+                    continue;
+                }
+                let symbol = Symbol::new(partition_value(&symbol_dir, "symbol")?);
+                let df = read_parquet(&path)?;
+                let dates = df
+                    .column("date")
+                    .and_then(|c| c.str().cloned())
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                for date in dates.into_iter().flatten() {
+                    keys.push(CacheKey::Metrics {
+                        provider: provider.clone(),
+                        symbol: symbol.clone(),
+                        date: date
+                            .parse()
+                            .map_err(|e: chrono::ParseError| DataError::Parse(e.to_string()))?,
+                    });
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Merge a partition's files into one if it holds at least `min_files`.
+    ///
+    /// Returns true if the partition was compacted.
+    fn compact_partition(&self, partition_dir: &Path, min_files: usize) -> Result<bool> {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let files = part_files(partition_dir)?;
+        if files.len() < min_files.max(2) {
+            return Ok(false);
+        }
+
+        let frames = files
+            .iter()
+            .map(|file| read_parquet(file))
+            .collect::<Result<Vec<_>>>()?;
+        let mut merged = stitch_ohlcv(frames)?;
+        write_parquet(&self.new_part_path(partition_dir), &mut merged)?;
+        for file in &files {
+            fs::remove_file(file).map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+
+        if let Some(series_dir) = partition_dir.parent() {
+            let coverage = self.read_coverage(series_dir)?;
+            let before = coverage.len();
+            let coverage = merge_coverage(coverage);
+            if coverage.len() != before {
+                self.write_coverage(series_dir, &coverage)?;
+            }
+        }
+        debug!(
+            partition = %partition_dir.display(),
+            files = files.len(),
+            "Compacted Parquet partition"
+        );
+        Ok(true)
+    }
+
+    /// Read the records matching `filter` along with when each was stored.
+    fn get_records<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        filter: Option<Expr>,
+    ) -> Result<Vec<Cached<T>>> {
+        let _guard = self.lock.read().unwrap_or_else(|e| e.into_inner());
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut lf = LazyFrame::scan_parquet(path, ScanArgsParquet::default())
+            .map_err(|e| DataError::Cache(e.to_string()))?;
+        if let Some(filter) = filter {
+            lf = lf.filter(filter);
+        }
+        let df = lf.collect().map_err(|e| DataError::Cache(e.to_string()))?;
+        let cached_at = cached_at_column(&df)?;
+        Ok(frame_to_records(&df)?
+            .into_iter()
+            .zip(cached_at)
+            .map(|(record, ms)| {
+                Cached::new(
+                    record,
+                    DateTime::from_timestamp_millis(ms).unwrap_or_default(),
+                )
+            })
+            .collect())
+    }
+
+    /// Merge records into a table, replacing existing rows with the same key.
+    fn put_records<T, K>(&self, path: &Path, records: &[T], key: impl Fn(&T) -> K) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned,
+        K: PartialEq,
+    {
+        let now = Utc::now().timestamp_millis();
+        self.put_records_at(path, records, &vec![now; records.len()], key)
+    }
+
+    /// Like [`put_records`](Self::put_records), with each record's storage time
+    /// in milliseconds given by `cached_at`.
+    fn put_records_at<T, K>(
+        &self,
+        path: &Path,
+        records: &[T],
+        cached_at: &[i64],
+        key: impl Fn(&T) -> K,
+    ) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned,
+        K: PartialEq,
+    {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let mut existing: Vec<(T, i64)> = if path.exists() {
+            let df = read_parquet(path)?;
+            let cached_at = cached_at_column(&df)?;
+            frame_to_records(&df)?.into_iter().zip(cached_at).collect()
+        } else {
+            Vec::new()
+        };
+        existing.retain(|(row, _)| !records.iter().any(|r| key(r) == key(row)));
+
+        let (mut rows, mut stamps): (Vec<&T>, Vec<i64>) =
+            existing.iter().map(|(row, at)| (row, *at)).unzip();
+        rows.extend(records);
+        stamps.extend(cached_at);
+
+        let mut df = records_to_frame(&rows, &stamps)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+        write_parquet(path, &mut df)
+    }
+
+    /// Drop rows and coverage older than `cutoff`, returning the number removed.
+    fn invalidate_stale(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let cutoff_ms = cutoff.timestamp_millis();
+        let mut removed = 0;
+
+        let mut tables = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            pending.extend(subdirs(&dir)?);
+            tables.extend(part_files(&dir)?);
+            if dir.join(COVERAGE_FILE).exists() {
+                if dir.starts_with(self.root.join("ticks")) {
+                    self.prune_coverage::<DateTime<Utc>>(&dir, cutoff)?;
+                } else {
+                    self.prune_coverage::<NaiveDate>(&dir, cutoff)?;
+                }
+            }
+        }
+
+        for table in tables {
+            let df = read_parquet(&table)?;
+            let mut fresh = df
+                .clone()
+                .lazy()
+                .filter(col(CACHED_AT).gt_eq(lit(cutoff_ms)))
+                .collect()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let stale = df.height() - fresh.height();
+            if stale == 0 {
+                continue;
+            }
+            removed += stale;
+            if fresh.height() == 0 {
+                fs::remove_file(&table).map_err(|e| DataError::Cache(e.to_string()))?;
+            } else {
+                write_parquet(&table, &mut fresh)?;
+            }
+        }
+
+        if removed > 0 {
+            debug!("Invalidated {} stale cache entries", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Drop coverage entries stored before `cutoff`.
+    fn prune_coverage<T>(&self, dir: &Path, cutoff: DateTime<Utc>) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut coverage = self.read_coverage::<T>(dir)?;
+        let before = coverage.len();
+        coverage.retain(|c| c.cached_at >= cutoff);
+        if coverage.len() != before {
+            self.write_coverage(dir, &coverage)?;
+        }
+        Ok(())
+    }
+
+    fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        let _guard = self.lock.read().unwrap_or_else(|e| e.into_inner());
+        let series_dir = self.ticks_dir(provider, symbol);
+
+        let covered_at = self
+            .read_coverage::<DateTime<Utc>>(&series_dir)?
+            .iter()
+            .filter(|c| c.start <= start && c.end >= end)
+            .map(|c| c.cached_at)
+            .max();
+        let Some(covered_at) = covered_at else {
+            debug!("No cached ticks found");
+            return Ok(None);
+        };
+
+        let (start_ns, end_ns) = (timestamp_nanos(start)?, timestamp_nanos(end)?);
+        let mut ticks = Vec::new();
+        for day in tick_days(&series_dir, start.date_naive(), end.date_naive())? {
+            for file in part_files(&day)? {
+                let df = LazyFrame::scan_parquet(&file, ScanArgsParquet::default())
+                    .and_then(|lf| {
+                        lf.filter(
+                            col("timestamp_ns")
+                                .gt_eq(lit(start_ns))
+                                .and(col("timestamp_ns").lt_eq(lit(end_ns))),
+                        )
+                        .collect()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                ticks.extend(frame_to_ticks(symbol, &df)?);
+            }
+        }
+        ticks.sort_by_key(|t| t.timestamp);
+        debug!("Found {} cached ticks", ticks.len());
+        Ok(Some(Cached::new(ticks, covered_at)))
+    }
+
+    /// Replace the ticks within `start..=end` and record the range as covered.
+    ///
+    /// Each touched day is rewritten as a single file, since ticks have no
+    /// natural key to deduplicate appended parts on.
+    fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        let series_dir = self.ticks_dir(provider, symbol);
+        let cached_at = Utc::now();
+        let (start_ns, end_ns) = (timestamp_nanos(start)?, timestamp_nanos(end)?);
+
+        let mut by_day: std::collections::BTreeMap<PathBuf, Vec<&Tick>> =
+            tick_days(&series_dir, start.date_naive(), end.date_naive())?
+                .into_iter()
+                .map(|day| (day, Vec::new()))
+                .collect();
+        for tick in ticks {
+            let day = series_dir.join(partition("date", &tick.timestamp.date_naive().to_string()));
+            by_day.entry(day).or_default().push(tick);
+        }
+
+        for (day, new_ticks) in by_day {
+            let mut frames = Vec::new();
+            for file in part_files(&day)? {
+                let kept = read_parquet(&file)?
+                    .lazy()
+                    .filter(
+                        col("timestamp_ns")
+                            .lt(lit(start_ns))
+                            .or(col("timestamp_ns").gt(lit(end_ns))),
+                    )
+                    .collect()
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                frames.push(kept.lazy());
+            }
+            frames.push(ticks_to_frame(&new_ticks, cached_at.timestamp_millis())?.lazy());
+
+            let mut merged = concat(
+                frames,
+                UnionArgs {
+                    to_supertypes: true,
+                    ..Default::default()
+                },
+            )
+            .and_then(|lf| {
+                lf.sort(
+                    ["timestamp_ns"],
+                    SortMultipleOptions::default().with_maintain_order(true),
+                )
+                .collect()
+            })
+            .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            if merged.height() == 0 {
+                if day.exists() {
+                    fs::remove_dir_all(&day).map_err(|e| DataError::Cache(e.to_string()))?;
+                }
+                continue;
+            }
+            fs::create_dir_all(&day).map_err(|e| DataError::Cache(e.to_string()))?;
+            write_parquet(&day.join("data.parquet"), &mut merged)?;
+        }
+
+        fs::create_dir_all(&series_dir).map_err(|e| DataError::Cache(e.to_string()))?;
+        let mut coverage = self.read_coverage::<DateTime<Utc>>(&series_dir)?;
+        coverage.push(CoverageEntry {
+            start,
+            end,
+            cached_at,
+        });
+        self.write_coverage(&series_dir, &coverage)?;
+        debug!("Cached {} ticks", ticks.len());
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        let _guard = self.lock.write().unwrap_or_else(|e| e.into_inner());
+        for dir in DATA_DIRS {
+            let path = self.root.join(dir);
+            if path.exists() {
+                fs::remove_dir_all(&path).map_err(|e| DataError::Cache(e.to_string()))?;
+            }
+        }
+        debug!("Cleared all cache entries");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DataCache for ParquetCache {
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        let store = self.store.clone();
+        let key = key.clone();
+        blocking(move || store.get_ohlcv(&key, start, end)).await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let covered = CoveredRange::new(DateRange::new(start, end), Utc::now());
+        self.put_ohlcv_at(key, covered, data).await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let store = self.store.clone();
+        let key = key.clone();
+        let data = data.clone();
+        let touched = blocking(move || store.put_ohlcv(&key, covered, &data)).await?;
+        if !touched.is_empty() {
+            self.schedule_compaction(touched);
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        let store = self.store.clone();
+        let provider = provider.to_string();
+        let symbol = symbol.clone();
+        blocking(move || store.get_ticks(&provider, &symbol, start, end)).await
+    }
+
+    #[instrument(skip(self, ticks), fields(provider = %provider, symbol = %symbol, count = ticks.len()))]
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let store = self.store.clone();
+        let provider = provider.to_string();
+        let symbol = symbol.clone();
+        let ticks = ticks.to_vec();
+        blocking(move || store.put_ticks(&provider, &symbol, start, end, &ticks)).await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        let records = self
+            .get_financials_entries(provider, symbol, period_type)
+            .await?
+            .unwrap_or_default();
+        let Some(oldest) = records.iter().map(|r| r.cached_at).min() else {
+            debug!("No cached financials found");
+            return Ok(None);
+        };
+        let statements: Vec<FinancialStatement> = records.into_iter().map(|r| r.value).collect();
+        debug!("Found {} cached financial statements", statements.len());
+        Ok(Some(Cached::new(statements, oldest)))
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials_entries(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Vec<Cached<FinancialStatement>>>> {
+        let store = self.store.clone();
+        let path = store.financials_file(provider, symbol, period_type);
+        let mut records: Vec<Cached<FinancialStatement>> =
+            blocking(move || store.get_records(&path, None)).await?;
+        records.sort_by_key(|r| std::cmp::Reverse(r.value.period_end));
+        Ok(Some(records).filter(|r| !r.is_empty()))
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[FinancialStatement],
+    ) -> Result<()> {
+        let now = Utc::now();
+        let statements: Vec<Cached<FinancialStatement>> = statements
+            .iter()
+            .map(|s| Cached::new(s.clone(), now))
+            .collect();
+        self.put_financials_at(provider, symbol, &statements).await
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
+        for period_type in [PeriodType::Annual, PeriodType::Quarterly] {
+            let (batch, cached_at): (Vec<FinancialStatement>, Vec<i64>) = statements
+                .iter()
+                .filter(|s| s.value.period_type == period_type)
+                .map(|s| (s.value.clone(), s.cached_at.timestamp_millis()))
+                .unzip();
+            if batch.is_empty() {
+                continue;
+            }
+            let store = self.store.clone();
+            let path = store.financials_file(provider, symbol, period_type);
+            blocking(move || store.put_records_at(&path, &batch, &cached_at, |s| s.period_end))
+                .await?;
+        }
+        debug!("Cached {} financial statements", statements.len());
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        let store = self.store.clone();
+        let path = store.metrics_file(provider, symbol);
+        let filter = col("date").eq(lit(date.to_string()));
+        let metrics: Vec<Cached<KeyMetrics>> =
+            blocking(move || store.get_records(&path, Some(filter))).await?;
+        Ok(metrics.into_iter().next())
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &KeyMetrics,
+    ) -> Result<()> {
+        self.put_metrics_at(provider, symbol, &Cached::new(metrics.clone(), Utc::now()))
+            .await
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
+    ) -> Result<()> {
+        let store = self.store.clone();
+        let path = store.metrics_file(provider, symbol);
+        let cached_at = metrics.cached_at.timestamp_millis();
+        let metrics = metrics.value.clone();
+        blocking(move || store.put_records_at(&path, &[metrics], &[cached_at], |m| m.date)).await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        let store = self.store.clone();
+        let path = store.companies_file(provider);
+        let filter = col("symbol").eq(lit(symbol.as_str()));
+        let info: Vec<Cached<CompanyInfo>> =
+            blocking(move || store.get_records(&path, Some(filter))).await?;
+        Ok(info.into_iter().next())
+    }
+
+    #[instrument(skip(self, info), fields(provider = %provider, symbol = %symbol))]
+    async fn put_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+    ) -> Result<()> {
+        let store = self.store.clone();
+        let path = store.companies_file(provider);
+        let info = CompanyInfo {
+            symbol: symbol.clone(),
+            ..info.clone()
+        };
+        blocking(move || store.put_records(&path, &[info], |i| i.symbol.clone())).await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, universe = %universe_id))]
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        let store = self.store.clone();
+        let path = store.universe_file(provider, universe_id);
+        let filter = col("as_of").lt_eq(lit(as_of.to_string()));
+        let rows: Vec<Cached<UniverseRow>> =
+            blocking(move || store.get_records(&path, Some(filter))).await?;
+        rows.into_iter()
+            .max_by_key(|row| row.value.as_of)
+            .map(|row| {
+                let symbols = serde_json::from_str(&row.value.symbols)
+                    .map_err(|e| DataError::Parse(e.to_string()))?;
+                Ok(Cached::new(symbols, row.cached_at))
+            })
+            .transpose()
+    }
+
+    #[instrument(skip(self, symbols), fields(provider = %provider, universe = %universe_id, count = symbols.len()))]
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()> {
+        let store = self.store.clone();
+        let path = store.universe_file(provider, universe_id);
+        let row = UniverseRow {
+            as_of,
+            symbols: serde_json::to_string(symbols).map_err(|e| DataError::Parse(e.to_string()))?,
+        };
+        blocking(move || store.put_records(&path, &[row], |r| r.as_of)).await
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        let store = self.store.clone();
+        let path = store.negative_file(&key.provider);
+        let filter = col("symbol")
+            .eq(lit(key.symbol.as_str()))
+            .and(col("request").eq(lit(key.request.clone().unwrap_or_default())));
+        let rows: Vec<Cached<NegativeRow>> =
+            blocking(move || store.get_records(&path, Some(filter))).await?;
+        Ok(rows.into_iter().next().map(|row| row.map(|r| r.message)))
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        let store = self.store.clone();
+        let path = store.negative_file(&key.provider);
+        let row = NegativeRow {
+            symbol: key.symbol.to_string(),
+            request: key.request.clone().unwrap_or_default(),
+            message: message.to_string(),
+        };
+        blocking(move || {
+            store.put_records(&path, &[row], |r| (r.symbol.clone(), r.request.clone()))
+        })
+        .await?;
+        debug!("Cached negative entry");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        let store = self.store.clone();
+        blocking(move || store.keys()).await
+    }
+
+    #[instrument(skip(self))]
+    async fn invalidate_stale(&self, ttl: Duration) -> Result<usize> {
+        let cutoff = Utc::now()
+            - chrono::Duration::from_std(ttl)
+                .map_err(|e| DataError::Cache(format!("Invalid TTL duration: {}", e)))?;
+        let store = self.store.clone();
+        blocking(move || store.invalidate_stale(cutoff)).await
+    }
+
+    #[instrument(skip(self))]
+    async fn clear(&self) -> Result<()> {
+        let store = self.store.clone();
+        blocking(move || store.clear()).await
+    }
+}
+
+/// Run blocking file I/O on tokio's blocking pool.
+pub(crate) async fn blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| DataError::Cache(e.to_string()))?
+}
+
+/// Format a hive partition directory name, escaping characters that are not
+/// safe in paths or that would confuse `key=value` parsing.
+fn partition(key: &str, value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'^') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    format!("{key}={encoded}")
+}
+
+/// Decode the value of a partition directory written by [`partition`].
+fn partition_value(dir: &Path, key: &str) -> Result<String> {
+    let invalid = || DataError::Cache(format!("Invalid partition directory: {}", dir.display()));
+    let encoded = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_prefix(key))
+        .and_then(|n| n.strip_prefix('='))
+        .ok_or_else(invalid)?;
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let escaped = tail
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(invalid)?;
+            bytes.push(escaped);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Returns the Parquet files directly inside a directory, sorted by name.
+/// Merge overlapping and adjacent OHLCV coverage entries.
+///
+/// Where entries overlap the most recently stored one wins, so refreshing a
+/// range replaces its older coverage. Adjacent entries are joined and keep the
+/// older of their storage times, which may make recently refreshed dates look
+/// older than they are but never fresher.
+fn merge_coverage(mut coverage: Vec<CoverageEntry>) -> Vec<CoverageEntry> {
+    coverage.sort_by_key(|c| std::cmp::Reverse(c.cached_at));
+    let mut pieces: Vec<CoverageEntry> = Vec::with_capacity(coverage.len());
+    for entry in coverage {
+        let newer: Vec<DateRange> = pieces
+            .iter()
+            .map(|p| DateRange::new(p.start, p.end))
+            .collect();
+        for gap in missing_ranges(&newer, DateRange::new(entry.start, entry.end)) {
+            pieces.push(CoverageEntry {
+                start: gap.start,
+                end: gap.end,
+                cached_at: entry.cached_at,
+            });
+        }
+    }
+
+    pieces.sort_by_key(|p| p.start);
+    let mut merged: Vec<CoverageEntry> = Vec::with_capacity(pieces.len());
+    for piece in pieces {
+        match merged.last_mut() {
+            Some(last) if last.end.succ_opt() == Some(piece.start) => {
+                last.end = piece.end;
+                last.cached_at = last.cached_at.min(piece.cached_at);
+            }
+            _ => merged.push(piece),
+        }
+    }
+    merged
+}
+
+fn part_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| DataError::Cache(e.to_string()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "parquet"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the subdirectories of a directory.
+fn subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read_dir(dir)
+        .map_err(|e| DataError::Cache(e.to_string()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_dir())
+        .collect())
+}
+
+/// Returns the tick day partitions dated within `start..=end`, in date order.
+fn tick_days(series_dir: &Path, start: NaiveDate, end: NaiveDate) -> Result<Vec<PathBuf>> {
+    let mut days: Vec<PathBuf> = subdirs(series_dir)?
+        .into_iter()
+        .filter(|dir| {
+            dir.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("date="))
+                .and_then(|d| d.parse::<NaiveDate>().ok())
+                .is_some_and(|d| d >= start && d <= end)
+        })
+        .collect();
+    days.sort();
+    Ok(days)
+}
+
+fn timestamp_nanos(timestamp: DateTime<Utc>) -> Result<i64> {
+    timestamp
+        .timestamp_nanos_opt()
+        .ok_or_else(|| DataError::Cache(format!("Timestamp out of range: {timestamp}")))
+}
+
+fn ticks_to_frame(ticks: &[&Tick], cached_at: i64) -> Result<DataFrame> {
+    let timestamps = ticks
+        .iter()
+        .map(|t| timestamp_nanos(t.timestamp))
+        .collect::<Result<Vec<_>>>()?;
+    let conditions = ticks
+        .iter()
+        .map(|t| serde_json::to_string(&t.conditions).map_err(|e| DataError::Parse(e.to_string())))
+        .collect::<Result<Vec<_>>>()?;
+    DataFrame::new(vec![
+        Column::new("timestamp_ns".into(), timestamps),
+        Column::new(
+            "price".into(),
+            ticks.iter().map(|t| t.price).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "size".into(),
+            ticks.iter().map(|t| t.size).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "exchange".into(),
+            ticks.iter().map(|t| t.exchange.clone()).collect::<Vec<_>>(),
+        ),
+        Column::new("conditions".into(), conditions),
+        Column::new(CACHED_AT.into(), vec![cached_at; ticks.len()]),
+    ])
+    .map_err(|e| DataError::Cache(e.to_string()))
+}
+
+fn frame_to_ticks(symbol: &Symbol, df: &DataFrame) -> Result<Vec<Tick>> {
+    let column = |name: &str| df.column(name).map_err(|e| DataError::Cache(e.to_string()));
+    let timestamps = column("timestamp_ns")?
+        .i64()
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    let prices = column("price")?
+        .f64()
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    let sizes = column("size")?
+        .f64()
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    let exchanges = column("exchange")?
+        .cast(&DataType::String)
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    let exchanges = exchanges
+        .str()
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    let conditions = column("conditions")?
+        .str()
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+
+    let mut ticks = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        let (Some(timestamp), Some(price), Some(size)) =
+            (timestamps.get(i), prices.get(i), sizes.get(i))
+        else {
+            continue;
+        };
+        let mut tick = Tick::new(
+            symbol.clone(),
+            DateTime::from_timestamp_nanos(timestamp),
+            price,
+            size,
+        );
+        tick.exchange = exchanges.get(i).map(str::to_string);
+        if let Some(json) = conditions.get(i) {
+            tick.conditions =
+                serde_json::from_str(json).map_err(|e| DataError::Parse(e.to_string()))?;
+        }
+        ticks.push(tick);
+    }
+    Ok(ticks)
+}
+
+fn read_parquet(path: &Path) -> Result<DataFrame> {
+    let file = fs::File::open(path).map_err(|e| DataError::Cache(e.to_string()))?;
+    ParquetReader::new(file)
+        .finish()
+        .map_err(|e| DataError::Cache(e.to_string()))
+}
+
+pub(crate) fn write_parquet(path: &Path, df: &mut DataFrame) -> Result<()> {
+    write_atomic(path, |tmp| {
+        let file = fs::File::create(tmp).map_err(|e| DataError::Cache(e.to_string()))?;
+        ParquetWriter::new(file)
+            .finish(df)
+            .map_err(|e| DataError::Cache(e.to_string()))?;
+        Ok(())
+    })
+}
+
+/// Write to a temporary sibling and rename it into place.
+fn write_atomic(path: &Path, write: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    write(&tmp)?;
+    fs::rename(&tmp, path).map_err(|e| DataError::Cache(e.to_string()))
+}
+
+pub(crate) fn cached_at_column(df: &DataFrame) -> Result<Vec<i64>> {
+    df.column(CACHED_AT)
+        .and_then(|c| {
+            c.i64()
+                .map(|c| c.into_iter().map(Option::unwrap_or_default).collect())
+        })
+        .map_err(|e| DataError::Cache(e.to_string()))
+}
+
+/// Convert flat records into a table with one column per field.
+///
+/// Numeric fields become `Float64` columns and everything else `String`.
+pub(crate) fn records_to_frame<T: Serialize>(
+    records: &[&T],
+    cached_at: &[i64],
+) -> Result<DataFrame> {
+    let rows = records
+        .iter()
+        .map(|record| match serde_json::to_value(record) {
+            Ok(Value::Object(map)) => Ok(map),
+            Ok(_) => Err(DataError::Cache("Expected a record".to_string())),
+            Err(e) => Err(DataError::Parse(e.to_string())),
+        })
+        .collect::<Result<Vec<Map<String, Value>>>>()?;
+
+    let mut names: Vec<&String> = Vec::new();
+    for row in &rows {
+        for name in row.keys() {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    let mut columns = Vec::with_capacity(names.len() + 1);
+    for name in names {
+        let values: Vec<Option<&Value>> = rows
+            .iter()
+            .map(|row| row.get(name).filter(|v| !v.is_null()))
+            .collect();
+        let column = if values.iter().flatten().all(|v| v.is_number()) {
+            let values: Vec<Option<f64>> =
+                values.iter().map(|v| v.and_then(Value::as_f64)).collect();
+            Column::new(name.as_str().into(), values)
+        } else {
+            let values: Vec<Option<String>> = values
+                .iter()
+                .map(|v| {
+                    v.map(|v| match v {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                })
+                .collect();
+            Column::new(name.as_str().into(), values)
+        };
+        columns.push(column);
+    }
+    columns.push(Column::new(CACHED_AT.into(), cached_at.to_vec()));
+
+    DataFrame::new(columns).map_err(|e| DataError::Cache(e.to_string()))
+}
+
+/// Convert a table written by [`records_to_frame`] back into records.
+pub(crate) fn frame_to_records<T: DeserializeOwned>(df: &DataFrame) -> Result<Vec<T>> {
+    let mut rows = vec![Map::new(); df.height()];
+    for column in df.get_columns() {
+        if column.name() == CACHED_AT {
+            continue;
+        }
+        let name = column.name().to_string();
+        match column.dtype() {
+            DataType::String => {
+                let values = column.str().map_err(|e| DataError::Cache(e.to_string()))?;
+                for (row, value) in rows.iter_mut().zip(values) {
+                    let value = value.map_or(Value::Null, |v| Value::String(v.to_string()));
+                    row.insert(name.clone(), value);
+                }
+            }
+            _ => {
+                let values = column
+                    .cast(&DataType::Float64)
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                let values = values.f64().map_err(|e| DataError::Cache(e.to_string()))?;
+                for (row, value) in rows.iter_mut().zip(values) {
+                    row.insert(name.clone(), value.map_or(Value::Null, number));
+                }
+            }
+        }
+    }
+    rows.into_iter()
+        .map(|row| {
+            serde_json::from_value(Value::Object(row)).map_err(|e| DataError::Parse(e.to_string()))
+        })
+        .collect()
+}
+
+/// Convert a float to JSON, using an integer where exact so integer fields round-trip.
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 9.0e15 {
+        Value::from(value as i64)
+    } else {
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_core::{DataFrequency, PriceAdjustment};
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("data-cache-parquet-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn ohlcv_frame(dates: &[&str], closes: &[f64]) -> DataFrame {
+        DataFrame::new(vec![
+            Column::new("symbol".into(), vec!["AAPL"; dates.len()]),
+            Column::new("date".into(), dates.to_vec()),
+            Column::new("open".into(), closes.to_vec()),
+            Column::new("high".into(), closes.to_vec()),
+            Column::new("low".into(), closes.to_vec()),
+            Column::new("close".into(), closes.to_vec()),
+            Column::new("volume".into(), vec![1000000.0; dates.len()]),
+        ])
+        .unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ohlcv_partitions_and_gaps() {
+        let root = temp_root("ohlcv");
+        let cache = ParquetCache::new(&root).unwrap();
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("Yahoo Finance", &symbol, DataFrequency::Daily);
+
+        let df = ohlcv_frame(&["2023-12-29", "2024-01-02"], &[150.0, 151.0]);
+        cache
+            .put_ohlcv(&key, date(2023, 12, 28), date(2024, 1, 3), &df)
+            .await
+            .unwrap();
+
+        let series =
+            root.join("ohlcv/provider=Yahoo%20Finance/frequency=1d/adjustment=raw/symbol=AAPL");
+        assert_eq!(part_files(&series.join("year=2023")).unwrap().len(), 1);
+        assert_eq!(part_files(&series.join("year=2024")).unwrap().len(), 1);
+
+        let lookup = cache
+            .get_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 10))
+            .await
+            .unwrap();
+        let data = lookup.data.unwrap();
+        assert_eq!(data.height(), 1);
+        assert!(data.column(CACHED_AT).is_err());
+        assert_eq!(
+            lookup.missing,
+            [DateRange::new(date(2024, 1, 4), date(2024, 1, 10))]
+        );
+
+        let hourly = OhlcvCacheKey::new("Yahoo Finance", &symbol, DataFrequency::Hourly);
+        let lookup = cache
+            .get_ohlcv(&hourly, date(2024, 1, 1), date(2024, 1, 10))
+            .await
+            .unwrap();
+        assert!(lookup.is_miss());
+
+        // An adjusted series lives in its own partition and is never served
+        // for an unadjusted request
+        let adjusted = key.clone().with_adjustment(PriceAdjustment::Adjusted);
+        let df = ohlcv_frame(&["2024-01-04", "2024-01-05"], &[75.0, 76.0]);
+        cache
+            .put_ohlcv(&adjusted, date(2024, 1, 4), date(2024, 1, 10), &df)
+            .await
+            .unwrap();
+        assert!(
+            root.join(
+                "ohlcv/provider=Yahoo%20Finance/frequency=1d/adjustment=adjusted/symbol=AAPL"
+            )
+            .join("year=2024")
+            .exists()
+        );
+        let lookup = cache
+            .get_ohlcv(&key, date(2024, 1, 4), date(2024, 1, 10))
+            .await
+            .unwrap();
+        assert!(lookup.is_miss());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compaction_merges_parts() {
+        let root = temp_root("compact");
+        let cache = ParquetCache::new(&root)
+            .unwrap()
+            .with_compaction_threshold(100);
+        let key = OhlcvCacheKey::new("test", &Symbol::new("AAPL"), DataFrequency::Daily);
+
+        for (day, close) in [(2, 150.0), (3, 151.0), (3, 152.0)] {
+            let df = ohlcv_frame(&[&format!("2024-01-{day:02}")], &[close]);
+            cache
+                .put_ohlcv(&key, date(2024, 1, day), date(2024, 1, day), &df)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(cache.compact().await.unwrap(), 1);
+        let series_dir = cache.store.series_dir(&key);
+        assert_eq!(part_files(&series_dir.join("year=2024")).unwrap().len(), 1);
+
+        // The rewritten day replaces its older coverage and the adjacent days
+        // are joined, keeping the older storage time
+        let coverage = cache.store.read_coverage::<NaiveDate>(&series_dir).unwrap();
+        assert_eq!(coverage.len(), 1);
+        assert_eq!(
+            (coverage[0].start, coverage[0].end),
+            (date(2024, 1, 2), date(2024, 1, 3))
+        );
+
+        let lookup = cache
+            .get_ohlcv(&key, date(2024, 1, 2), date(2024, 1, 3))
+            .await
+            .unwrap();
+        assert!(lookup.is_complete());
+        let closes: Vec<Option<f64>> = lookup
+            .data
+            .unwrap()
+            .column("close")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .to_vec();
+        assert_eq!(closes, [Some(150.0), Some(152.0)]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_merge_coverage_prefers_newer_entries() {
+        let t0 = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let t1 = t0 + chrono::Duration::days(1);
+        let entry = |start, end, cached_at| CoverageEntry {
+            start,
+            end,
+            cached_at,
+        };
+        let merged = merge_coverage(vec![
+            entry(date(2024, 1, 5), date(2024, 1, 10), t0),
+            entry(date(2024, 1, 1), date(2024, 1, 31), t1),
+            entry(date(2024, 3, 1), date(2024, 3, 31), t0),
+            entry(date(2024, 3, 15), date(2024, 4, 15), t1),
+        ]);
+        let merged: Vec<_> = merged
+            .iter()
+            .map(|c| (c.start, c.end, c.cached_at))
+            .collect();
+        assert_eq!(
+            merged,
+            [
+                (date(2024, 1, 1), date(2024, 1, 31), t1),
+                (date(2024, 3, 1), date(2024, 4, 15), t0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fundamentals_round_trip() {
+        let root = temp_root("fundamentals");
+        let cache = ParquetCache::new(&root).unwrap();
+        let symbol = Symbol::new("AAPL");
+
+        let statement = FinancialStatement {
+            fiscal_year: Some(2024),
+            fiscal_quarter: Some(1),
+            revenue: Some(94_930_000_000.0),
+            eps_diluted: Some(1.53),
+            ..FinancialStatement::new(symbol.clone(), date(2024, 3, 31), PeriodType::Quarterly)
+        };
+        cache
+            .put_financials("test", &symbol, std::slice::from_ref(&statement))
+            .await
+            .unwrap();
+        let cached = cache
+            .get_financials("test", &symbol, PeriodType::Quarterly)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.value, [statement]);
+        assert!(
+            cache
+                .get_financials("test", &symbol, PeriodType::Annual)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let metrics = KeyMetrics {
+            pe_ratio: Some(28.5),
+            ..KeyMetrics::new(symbol.clone(), date(2024, 1, 15))
+        };
+        cache.put_metrics("test", &symbol, &metrics).await.unwrap();
+        let cached = cache
+            .get_metrics("test", &symbol, date(2024, 1, 15))
+            .await
+            .unwrap();
+        assert_eq!(cached.map(|c| c.value), Some(metrics));
+        assert!(
+            cache
+                .get_metrics("test", &symbol, date(2024, 1, 16))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cache.clear().await.unwrap();
+        assert!(
+            cache
+                .get_metrics("test", &symbol, date(2024, 1, 15))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_negative_entries_round_trip() {
+        let root = temp_root("negative");
+        let cache = ParquetCache::new(&root).unwrap();
+        let symbol = Symbol::new("GONE");
+        let symbol_key = NegativeKey::symbol("test", &symbol);
+        let request_key = NegativeKey::request("test", &symbol, "ohlcv:daily");
+        assert!(cache.get_negative(&symbol_key).await.unwrap().is_none());
+
+        cache
+            .put_negative(&symbol_key, "Symbol not found: GONE")
+            .await
+            .unwrap();
+        cache
+            .put_negative(&request_key, "No data for GONE")
+            .await
+            .unwrap();
+        cache
+            .put_negative(&symbol_key, "Unknown symbol GONE")
+            .await
+            .unwrap();
+        let cached = cache.get_negative(&symbol_key).await.unwrap().unwrap();
+        assert_eq!(cached.value, "Unknown symbol GONE");
+        let cached = cache.get_negative(&request_key).await.unwrap().unwrap();
+        assert_eq!(cached.value, "No data for GONE");
+
+        // Entries survive reopening the cache
+        let reopened = ParquetCache::new(&root).unwrap();
+        assert!(reopened.get_negative(&symbol_key).await.unwrap().is_some());
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert_eq!(cache.invalidate_stale(Duration::ZERO).await.unwrap(), 2);
+        assert!(cache.get_negative(&symbol_key).await.unwrap().is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ticks_round_trip() {
+        let root = temp_root("ticks");
+        let cache = ParquetCache::new(&root).unwrap();
+        let symbol = Symbol::new("AAPL");
+        let start = DateTime::parse_from_rfc3339("2024-01-02T23:30:00.123456789Z")
+            .unwrap()
+            .to_utc();
+        let end = start + chrono::Duration::hours(1);
+        let mut first = Tick::new(symbol.clone(), start, 150.0, 100.0).with_exchange("NASDAQ");
+        first.conditions = vec!["regular".to_string()];
+        let ticks = vec![first, Tick::new(symbol.clone(), end, 151.0, 200.0)];
+
+        cache
+            .put_ticks("test", &symbol, start, end, &ticks)
+            .await
+            .unwrap();
+        let series = cache.store.ticks_dir("test", &symbol);
+        assert_eq!(
+            tick_days(&series, date(2024, 1, 1), date(2024, 1, 31))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let cached = cache
+            .get_ticks("test", &symbol, start, end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.value, ticks);
+
+        // Re-storing the range replaces its ticks
+        cache
+            .put_ticks("test", &symbol, start, end, &ticks[1..])
+            .await
+            .unwrap();
+        let cached = cache
+            .get_ticks("test", &symbol, start, end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.value, ticks[1..]);
+
+        let wider = cache
+            .get_ticks("test", &symbol, start - chrono::Duration::seconds(1), end)
+            .await
+            .unwrap();
+        assert!(wider.is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reference_data_round_trip() {
+        let root = temp_root("reference");
+        let cache = ParquetCache::new(&root).unwrap();
+        let apple = Symbol::new("AAPL");
+        let mut info = CompanyInfo::new(
+            apple.clone(),
+            "Apple Inc.",
+            "NASDAQ",
+            "Technology",
+            "Consumer Electronics",
+            "US",
+            "USD",
+        );
+        info.cik = Some("0000320193".to_string());
+        let msft = CompanyInfo::new(
+            Symbol::new("MSFT"),
+            "Microsoft Corporation",
+            "NASDAQ",
+            "Technology",
+            "Software",
+            "US",
+            "USD",
+        );
+
+        cache.put_company_info("test", &apple, &info).await.unwrap();
+        cache
+            .put_company_info("test", &msft.symbol, &msft)
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .get_company_info("test", &apple)
+                .await
+                .unwrap()
+                .map(|c| c.value),
+            Some(info)
+        );
+        assert_eq!(
+            cache
+                .get_company_info("test", &msft.symbol)
+                .await
+                .unwrap()
+                .map(|c| c.value),
+            Some(msft)
+        );
+
+        let members = [apple.clone(), Symbol::new("MSFT")];
+        cache
+            .put_universe("test", "sp500", date(2024, 1, 1), &members[..1])
+            .await
+            .unwrap();
+        cache
+            .put_universe("test", "sp500", date(2024, 2, 1), &members)
+            .await
+            .unwrap();
+        let snapshot = cache
+            .get_universe("test", "sp500", date(2024, 1, 15))
+            .await
+            .unwrap();
+        assert_eq!(snapshot.map(|c| c.value).as_deref(), Some(&members[..1]));
+        let snapshot = cache
+            .get_universe("test", "sp500", date(2024, 3, 1))
+            .await
+            .unwrap();
+        assert_eq!(snapshot.map(|c| c.value).as_deref(), Some(&members[..]));
+        assert!(
+            cache
+                .get_universe("test", "sp500", date(2023, 12, 31))
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_stale() {
+        let root = temp_root("stale");
+        let cache = ParquetCache::new(&root).unwrap();
+        let key = OhlcvCacheKey::new("test", &Symbol::new("AAPL"), DataFrequency::Daily);
+        let df = ohlcv_frame(&["2024-01-02"], &[150.0]);
+        cache
+            .put_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5), &df)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cache
+                .invalidate_stale(Duration::from_secs(3600))
+                .await
+                .unwrap(),
+            0
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(cache.invalidate_stale(Duration::ZERO).await.unwrap(), 1);
+
+        let lookup = cache
+            .get_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5))
+            .await
+            .unwrap();
+        assert!(lookup.is_miss());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
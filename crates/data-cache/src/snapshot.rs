@@ -0,0 +1,610 @@
+//! Portable snapshots of a cache's contents.
+//!
+//! A snapshot is a directory of Parquet files described by a manifest:
+//!
+//! ```text
+//! <dir>/manifest.json
+//! <dir>/ohlcv/<n>.parquet
+//! <dir>/financials.parquet
+//! <dir>/metrics.parquet
+//! ```
+//!
+//! [`export_snapshot`] dumps every OHLCV series, financial statement and key
+//! metrics entry a cache lists through [`DataCache::keys`], and
+//! [`import_snapshot`] loads a snapshot into any [`DataCache`], so a frozen
+//! dataset can be shipped to colleagues or CI and runs reproduced offline.
+//!
+//! The manifest records each file's SHA-256 hash, which is checked before
+//! anything is imported. Provenance travels with the data: the manifest lists
+//! the ranges each OHLCV series covered and when each was stored, and
+//! financials and metrics rows carry their provider and storage time in
+//! `_provider` and `_cached_at` columns. Imported entries keep those storage
+//! times, so they age in the target cache as they did in the source.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheKey, Cached, CoveredRange, DataCache, DataError, FinancialStatement, KeyMetrics,
+    OhlcvCacheKey, Result, Symbol,
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, instrument};
+
+use crate::memory::filter_dates;
+use crate::parquet::{
+    blocking, cached_at_column, frame_to_records, records_to_frame, write_parquet,
+};
+
+/// Version of the snapshot layout written by [`export_snapshot`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest file in a snapshot directory.
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Column holding the provider of a financials or metrics row.
+const PROVIDER: &str = "_provider";
+
+/// Describes the contents of a snapshot directory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Layout version the snapshot was written with.
+    pub format_version: u32,
+    /// When the snapshot was exported.
+    pub created_at: DateTime<Utc>,
+    /// Every OHLCV series in the snapshot.
+    pub ohlcv: Vec<OhlcvSnapshot>,
+    /// Financial statements of every provider and symbol, if any were cached.
+    pub financials: Option<SnapshotFile>,
+    /// Key metrics of every provider and symbol, if any were cached.
+    pub metrics: Option<SnapshotFile>,
+}
+
+/// An OHLCV series in a snapshot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OhlcvSnapshot {
+    /// The series.
+    pub key: OhlcvCacheKey,
+    /// Ranges the series covered and when each was stored.
+    pub covered: Vec<CoveredRange>,
+    /// The series' rows, or `None` if the covered ranges hold no rows.
+    pub file: Option<SnapshotFile>,
+}
+
+/// A data file in a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path relative to the snapshot directory, with `/` separators.
+    pub path: String,
+    /// Hex-encoded SHA-256 hash of the file's contents.
+    pub sha256: String,
+    /// Number of rows in the file.
+    pub rows: usize,
+}
+
+/// Writes the OHLCV series, financials and metrics held by `cache` to a
+/// snapshot in `dir`, creating the directory if needed.
+///
+/// # Errors
+/// Returns an error if the cache cannot list its contents (see
+/// [`DataCache::keys`]), a read fails, or the files cannot be written.
+#[instrument(skip(cache, dir), fields(dir = %dir.as_ref().display()))]
+pub async fn export_snapshot(
+    cache: &dyn DataCache,
+    dir: impl AsRef<Path>,
+) -> Result<SnapshotManifest> {
+    let dir = dir.as_ref().to_path_buf();
+    let (earliest, latest) = full_range();
+
+    let mut ohlcv = Vec::new();
+    let mut financials: Vec<(String, FinancialStatement, i64)> = Vec::new();
+    let mut metrics: Vec<(String, KeyMetrics, i64)> = Vec::new();
+    for key in cache.keys().await? {
+        match key {
+            CacheKey::Ohlcv(key) => {
+                let lookup = cache.get_ohlcv(&key, earliest, latest).await?;
+                if lookup.covered.is_empty() {
+                    continue;
+                }
+                let file = match lookup.data {
+                    Some(data) if data.height() > 0 => {
+                        let path = format!("ohlcv/{:06}.parquet", ohlcv.len());
+                        Some(write_file(&dir, path, data).await?)
+                    }
+                    _ => None,
+                };
+                ohlcv.push(OhlcvSnapshot {
+                    key,
+                    covered: lookup.covered,
+                    file,
+                });
+            }
+            CacheKey::Financials {
+                provider,
+                symbol,
+                period_type,
+            } => {
+                if let Some(entries) = cache
+                    .get_financials_entries(&provider, &symbol, period_type)
+                    .await?
+                {
+                    financials.extend(entries.into_iter().map(|s| {
+                        let at = s.cached_at.timestamp_millis();
+                        (provider.clone(), s.value, at)
+                    }));
+                }
+            }
+            CacheKey::Metrics {
+                provider,
+                symbol,
+                date,
+            } => {
+                if let Some(cached) = cache.get_metrics(&provider, &symbol, date).await? {
+                    let at = cached.cached_at.timestamp_millis();
+                    metrics.push((provider, cached.value, at));
+                }
+            }
+        }
+    }
+
+    let manifest = SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        created_at: Utc::now(),
+        ohlcv,
+        financials: write_records(&dir, "financials.parquet", &financials).await?,
+        metrics: write_records(&dir, "metrics.parquet", &metrics).await?,
+    };
+    let json =
+        serde_json::to_string_pretty(&manifest).map_err(|e| DataError::Parse(e.to_string()))?;
+    let path = dir.join(MANIFEST_FILE);
+    blocking(move || {
+        fs::create_dir_all(&dir).map_err(|e| DataError::Cache(e.to_string()))?;
+        fs::write(&path, json).map_err(|e| DataError::Cache(e.to_string()))
+    })
+    .await?;
+
+    debug!(
+        series = manifest.ohlcv.len(),
+        financials = financials.len(),
+        metrics = metrics.len(),
+        "Exported cache snapshot"
+    );
+    Ok(manifest)
+}
+
+/// Loads a snapshot written by [`export_snapshot`] from `dir` into `cache`.
+///
+/// Every file is checked against the manifest's hashes before anything is
+/// written, so a corrupted or tampered snapshot leaves the cache untouched.
+/// Entries keep the storage times recorded in the snapshot.
+///
+/// # Errors
+/// Returns an error if the manifest is missing or was written by a newer
+/// version, a file path points outside `dir`, a file does not match its hash,
+/// or a write to the cache fails.
+#[instrument(skip(cache, dir), fields(dir = %dir.as_ref().display()))]
+pub async fn import_snapshot(
+    cache: &dyn DataCache,
+    dir: impl AsRef<Path>,
+) -> Result<SnapshotManifest> {
+    let dir = dir.as_ref().to_path_buf();
+    let path = dir.join(MANIFEST_FILE);
+    let json =
+        blocking(move || fs::read_to_string(&path).map_err(|e| DataError::Cache(e.to_string())))
+            .await?;
+    let manifest: SnapshotManifest =
+        serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
+    if manifest.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(DataError::Cache(format!(
+            "Snapshot format version {} is newer than the supported version {}",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+
+    let mut series = Vec::with_capacity(manifest.ohlcv.len());
+    for entry in &manifest.ohlcv {
+        let data = match &entry.file {
+            Some(file) => read_file(&dir, file).await?,
+            None => DataFrame::empty(),
+        };
+        series.push(data);
+    }
+    let financials: Vec<(String, FinancialStatement, i64)> = match &manifest.financials {
+        Some(file) => read_records(read_file(&dir, file).await?)?,
+        None => Vec::new(),
+    };
+    let metrics: Vec<(String, KeyMetrics, i64)> = match &manifest.metrics {
+        Some(file) => read_records(read_file(&dir, file).await?)?,
+        None => Vec::new(),
+    };
+
+    for (entry, data) in manifest.ohlcv.iter().zip(&series) {
+        for covered in &entry.covered {
+            let rows = filter_dates(data, covered.range.start, covered.range.end)?;
+            cache.put_ohlcv_at(&entry.key, *covered, &rows).await?;
+        }
+    }
+
+    let mut statements: HashMap<(String, Symbol), Vec<Cached<FinancialStatement>>> = HashMap::new();
+    for (provider, statement, at) in financials {
+        statements
+            .entry((provider, statement.symbol.clone()))
+            .or_default()
+            .push(Cached::new(statement, cached_at(at)?));
+    }
+    for ((provider, symbol), statements) in &statements {
+        cache
+            .put_financials_at(provider, symbol, statements)
+            .await?;
+    }
+
+    for (provider, metrics, at) in &metrics {
+        let metrics = Cached::new(metrics.clone(), cached_at(*at)?);
+        cache
+            .put_metrics_at(provider, &metrics.value.symbol, &metrics)
+            .await?;
+    }
+
+    debug!(
+        series = manifest.ohlcv.len(),
+        financials = statements.values().map(Vec::len).sum::<usize>(),
+        metrics = metrics.len(),
+        "Imported cache snapshot"
+    );
+    Ok(manifest)
+}
+
+/// The widest date range every backend can compare correctly.
+fn full_range() -> (NaiveDate, NaiveDate) {
+    (
+        NaiveDate::from_ymd_opt(1, 1, 1).unwrap_or(NaiveDate::MIN),
+        NaiveDate::from_ymd_opt(9999, 12, 31).unwrap_or(NaiveDate::MAX),
+    )
+}
+
+/// Convert a `_cached_at` value in milliseconds back into a timestamp.
+fn cached_at(millis: i64) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(millis)
+        .ok_or_else(|| DataError::Cache(format!("Invalid snapshot timestamp {millis}")))
+}
+
+/// Write a frame to `path` under the snapshot directory and hash it.
+async fn write_file(dir: &Path, path: String, mut df: DataFrame) -> Result<SnapshotFile> {
+    let full_path = dir.join(&path);
+    blocking(move || {
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+        write_parquet(&full_path, &mut df)?;
+        let bytes = fs::read(&full_path).map_err(|e| DataError::Cache(e.to_string()))?;
+        Ok(SnapshotFile {
+            path,
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+            rows: df.height(),
+        })
+    })
+    .await
+}
+
+/// Write records along with their provider and storage time, if there are any.
+async fn write_records<T: Serialize>(
+    dir: &Path,
+    path: &str,
+    records: &[(String, T, i64)],
+) -> Result<Option<SnapshotFile>> {
+    if records.is_empty() {
+        return Ok(None);
+    }
+    let rows: Vec<&T> = records.iter().map(|(_, record, _)| record).collect();
+    let cached_at: Vec<i64> = records.iter().map(|(_, _, at)| *at).collect();
+    let providers: Vec<&str> = records
+        .iter()
+        .map(|(provider, _, _)| provider.as_str())
+        .collect();
+    let mut df = records_to_frame(&rows, &cached_at)?;
+    df.with_column(Column::new(PROVIDER.into(), providers))
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+    write_file(dir, path.to_string(), df).await.map(Some)
+}
+
+/// Read a snapshot file, checking it against the manifest's hash.
+///
+/// Paths must be relative and stay within the snapshot directory, so a
+/// crafted manifest cannot read arbitrary files.
+async fn read_file(dir: &Path, file: &SnapshotFile) -> Result<DataFrame> {
+    let relative = Path::new(&file.path);
+    let contained = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !contained || file.path.is_empty() {
+        return Err(DataError::Cache(format!(
+            "Snapshot file {} is outside the snapshot directory",
+            file.path
+        )));
+    }
+    let full_path: PathBuf = dir.join(relative);
+    let file = file.clone();
+    blocking(move || {
+        let bytes = fs::read(&full_path).map_err(|e| DataError::Cache(e.to_string()))?;
+        if format!("{:x}", Sha256::digest(&bytes)) != file.sha256 {
+            return Err(DataError::Cache(format!(
+                "Snapshot file {} does not match its manifest hash",
+                file.path
+            )));
+        }
+        ParquetReader::new(Cursor::new(bytes))
+            .finish()
+            .map_err(|e| DataError::Cache(e.to_string()))
+    })
+    .await
+}
+
+/// Convert a frame written by [`write_records`] back into records.
+fn read_records<T: serde::de::DeserializeOwned>(df: DataFrame) -> Result<Vec<(String, T, i64)>> {
+    let providers: Vec<String> = df
+        .column(PROVIDER)
+        .and_then(|c| c.str().cloned())
+        .map_err(|e| DataError::Cache(e.to_string()))?
+        .into_iter()
+        .map(|p| p.unwrap_or_default().to_string())
+        .collect();
+    let cached_at = cached_at_column(&df)?;
+    let records = frame_to_records(&df.drop(PROVIDER).unwrap_or(df))?;
+    Ok(providers
+        .into_iter()
+        .zip(records)
+        .zip(cached_at)
+        .map(|((provider, record), at)| (provider, record, at))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InMemoryCache, ParquetCache, SqliteCache};
+    use data_core::{DataFrequency, PeriodType};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "data-cache-snapshot-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn ohlcv_frame() -> DataFrame {
+        df! {
+            "date" => [date(2024, 1, 2), date(2024, 1, 3), date(2024, 1, 4)],
+            "open" => [100.0, 101.0, 102.0],
+            "high" => [105.0, 106.0, 107.0],
+            "low" => [99.0, 100.0, 101.0],
+            "close" => [104.0, 105.0, 106.0],
+            "volume" => [1000.0, 1100.0, 1200.0],
+        }
+        .unwrap()
+    }
+
+    async fn populated_cache() -> InMemoryCache {
+        let cache = InMemoryCache::new();
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("yahoo", &symbol, DataFrequency::Daily);
+        cache
+            .put_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5), &ohlcv_frame())
+            .await
+            .unwrap();
+
+        let statement = FinancialStatement {
+            total_assets: Some(1.0e9),
+            ..FinancialStatement::new(symbol.clone(), date(2023, 12, 31), PeriodType::Annual)
+        };
+        cache
+            .put_financials("fmp", &symbol, &[statement])
+            .await
+            .unwrap();
+
+        let mut metrics = KeyMetrics::new(symbol.clone(), date(2024, 1, 5));
+        metrics.pe_ratio = Some(25.5);
+        cache.put_metrics("fmp", &symbol, &metrics).await.unwrap();
+        cache
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let dir = temp_dir("round-trip");
+        let source = populated_cache().await;
+        let exported = export_snapshot(&source, &dir).await.unwrap();
+        assert_eq!(exported.ohlcv.len(), 1);
+        assert_eq!(exported.financials.as_ref().unwrap().rows, 1);
+        assert_eq!(exported.metrics.as_ref().unwrap().rows, 1);
+
+        let root = temp_dir("round-trip-parquet");
+        let target = ParquetCache::new(&root).unwrap();
+        let imported = import_snapshot(&target, &dir).await.unwrap();
+        assert_eq!(imported, exported);
+
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("yahoo", &symbol, DataFrequency::Daily);
+        let lookup = target
+            .get_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5))
+            .await
+            .unwrap();
+        assert!(lookup.is_complete());
+        assert_eq!(lookup.data.unwrap().height(), 3);
+
+        let financials = target
+            .get_financials("fmp", &symbol, PeriodType::Annual)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(financials.value[0].total_assets, Some(1.0e9));
+
+        let metrics = target
+            .get_metrics("fmp", &symbol, date(2024, 1, 5))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metrics.value.pe_ratio, Some(25.5));
+
+        let mut keys = target.keys().await.unwrap();
+        keys.sort_by_key(|k| format!("{k:?}"));
+        let mut expected = source.keys().await.unwrap();
+        expected.sort_by_key(|k| format!("{k:?}"));
+        assert_eq!(keys, expected);
+
+        let _ = fs::remove_dir_all(&dir);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip_into_sqlite() {
+        let dir = temp_dir("round-trip-sqlite");
+        let source = populated_cache().await;
+        let exported = export_snapshot(&source, &dir).await.unwrap();
+
+        let target = SqliteCache::in_memory().unwrap();
+        import_snapshot(&target, &dir).await.unwrap();
+
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("yahoo", &symbol, DataFrequency::Daily);
+        let lookup = target
+            .get_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5))
+            .await
+            .unwrap();
+        assert!(lookup.is_complete());
+        let data = lookup.data.unwrap();
+        assert_eq!(data.height(), 3);
+        assert_eq!(
+            data.column("symbol").unwrap().str().unwrap().get(0),
+            Some("AAPL")
+        );
+        assert!(
+            target
+                .get_financials("fmp", &symbol, PeriodType::Annual)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            target
+                .get_metrics("fmp", &symbol, date(2024, 1, 5))
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(target.keys().await.unwrap().len(), exported.ohlcv.len() + 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_preserves_storage_times() {
+        let dir = temp_dir("provenance");
+        let source_root = temp_dir("provenance-source");
+        let target_root = temp_dir("provenance-target");
+        let source = ParquetCache::new(&source_root).unwrap();
+        let symbol = Symbol::new("AAPL");
+        let at = |millis| DateTime::from_timestamp_millis(millis).unwrap();
+
+        let key = OhlcvCacheKey::new("yahoo", &symbol, DataFrequency::Daily);
+        let covered = CoveredRange::new(
+            data_core::DateRange::new(date(2024, 1, 1), date(2024, 1, 5)),
+            at(1_700_000_000_000),
+        );
+        source
+            .put_ohlcv_at(&key, covered, &ohlcv_frame())
+            .await
+            .unwrap();
+        let statement =
+            |year| FinancialStatement::new(symbol.clone(), date(year, 12, 31), PeriodType::Annual);
+        let statements = [
+            Cached::new(statement(2023), at(1_700_000_100_000)),
+            Cached::new(statement(2022), at(1_700_000_200_000)),
+        ];
+        source
+            .put_financials_at("fmp", &symbol, &statements)
+            .await
+            .unwrap();
+        let metrics = Cached::new(
+            KeyMetrics::new(symbol.clone(), date(2024, 1, 5)),
+            at(1_700_000_300_000),
+        );
+        source
+            .put_metrics_at("fmp", &symbol, &metrics)
+            .await
+            .unwrap();
+
+        export_snapshot(&source, &dir).await.unwrap();
+        let target = ParquetCache::new(&target_root).unwrap();
+        import_snapshot(&target, &dir).await.unwrap();
+
+        let lookup = target
+            .get_ohlcv(&key, date(2024, 1, 1), date(2024, 1, 5))
+            .await
+            .unwrap();
+        assert_eq!(lookup.covered, [covered]);
+        let entries = target
+            .get_financials_entries("fmp", &symbol, PeriodType::Annual)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entries, statements);
+        let imported = target
+            .get_metrics("fmp", &symbol, date(2024, 1, 5))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported, metrics);
+
+        for path in [&dir, &source_root, &target_root] {
+            let _ = fs::remove_dir_all(path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_rejects_paths_outside_directory() {
+        let dir = temp_dir("escape");
+        let source = populated_cache().await;
+        let mut manifest = export_snapshot(&source, &dir).await.unwrap();
+
+        manifest.metrics.as_mut().unwrap().path = "../metrics.parquet".to_string();
+        let json = serde_json::to_string(&manifest).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), json).unwrap();
+
+        let target = InMemoryCache::new();
+        let err = import_snapshot(&target, &dir).await.unwrap_err();
+        assert!(err.to_string().contains("outside the snapshot directory"));
+        assert!(target.keys().await.unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_rejects_tampered_files() {
+        let dir = temp_dir("tampered");
+        let source = populated_cache().await;
+        let manifest = export_snapshot(&source, &dir).await.unwrap();
+
+        let metrics_file = dir.join(&manifest.metrics.unwrap().path);
+        let mut bytes = fs::read(&metrics_file).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        fs::write(&metrics_file, bytes).unwrap();
+
+        let target = InMemoryCache::new();
+        let err = import_snapshot(&target, &dir).await.unwrap_err();
+        assert!(err.to_string().contains("manifest hash"));
+        assert!(target.keys().await.unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,185 +1,234 @@
 //! SQLite-based cache implementation.
 
 use async_trait::async_trait;
-use chrono::{NaiveDate, Utc};
-use data_core::{DataCache, DataError, FinancialStatement, KeyMetrics, PeriodType, Result, Symbol};
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheDataKind, CacheEntryStats, CacheKey, CacheStats, Cached, CompanyInfo, CoveredRange,
+    DataCache, DataError, DateRange, FinancialStatement, KeyMetrics, NegativeKey, OhlcvCacheKey,
+    OhlcvLookup, PeriodType, Result, Symbol, Tick,
+};
 use polars::prelude::*;
-use rusqlite::{Connection, OptionalExtension, params};
-use std::path::Path;
-use std::sync::Mutex;
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
 use std::time::Duration;
-use tracing::{debug, instrument};
+use tracing::{Span, debug, instrument};
+
+use crate::memory::LookupCounter;
+
+/// Default number of pooled connections for file-backed caches.
+const DEFAULT_POOL_SIZE: usize = 4;
+
+/// How long a connection waits on a locked database before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prepared statements kept per connection; covers every query the cache issues.
+const STATEMENT_CACHE_CAPACITY: usize = 32;
 
 /// SQLite-based cache for market data.
 ///
 /// This cache stores data in a SQLite database file, providing persistence across
-/// application restarts. It uses `tokio::task::spawn_blocking` for async compatibility.
-#[derive(Debug)]
+/// application restarts. Queries run on tokio's blocking thread pool against a
+/// pool of connections, so concurrent fetches neither stall the executor nor
+/// queue behind a single connection. File-backed databases use WAL journaling,
+/// letting readers proceed while a write is in progress.
+/// Lookups are counted and reported with the contents by
+/// [`inspect`](DataCache::inspect); clones share the counters.
+#[derive(Debug, Clone)]
 pub struct SqliteCache {
-    conn: Mutex<Connection>,
+    pool: Arc<Pool>,
+    bitemporal: bool,
+    lookups: Arc<LookupCounter>,
 }
 
 impl SqliteCache {
+    /// Schema version this build reads and writes, stored in the database's
+    /// `user_version`.
+    pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
     /// Create a new SQLite cache at the given path.
     ///
     /// # Arguments
     /// * `path` - Path to the SQLite database file
     ///
     /// # Errors
-    /// Returns an error if the database cannot be opened or schema creation fails.
+    /// Returns an error if the database cannot be opened, was written by a newer
+    /// schema version, or cannot be migrated.
     pub fn new(path: impl AsRef<Path>) -> Result<Self> {
-        let conn = Connection::open(path).map_err(|e| DataError::Cache(e.to_string()))?;
-        let cache = Self {
-            conn: Mutex::new(conn),
-        };
-        cache.initialize_schema()?;
-        Ok(cache)
+        Self::with_pool_size(path, DEFAULT_POOL_SIZE)
+    }
+
+    /// Create a new SQLite cache at the given path with up to `max_connections`
+    /// pooled connections.
+    ///
+    /// # Errors
+    /// Returns an error if the database cannot be opened, was written by a newer
+    /// schema version, or cannot be migrated.
+    pub fn with_pool_size(path: impl AsRef<Path>, max_connections: usize) -> Result<Self> {
+        let pool = Pool::new(
+            Target::File(path.as_ref().to_path_buf()),
+            max_connections.max(1),
+        )?;
+        Ok(Self {
+            pool: Arc::new(pool),
+            bitemporal: false,
+            lookups: Arc::default(),
+        })
     }
 
     /// Create an in-memory SQLite cache.
     ///
-    /// Useful for testing; data is lost when the cache is dropped.
+    /// Useful for testing; data is lost when the cache is dropped. An in-memory
+    /// database lives in a single connection, so the pool holds exactly one.
     ///
     /// # Errors
     /// Returns an error if schema creation fails.
     pub fn in_memory() -> Result<Self> {
-        let conn = Connection::open_in_memory().map_err(|e| DataError::Cache(e.to_string()))?;
-        let cache = Self {
-            conn: Mutex::new(conn),
-        };
-        cache.initialize_schema()?;
-        Ok(cache)
+        let pool = Pool::new(Target::Memory, 1)?;
+        Ok(Self {
+            pool: Arc::new(pool),
+            bitemporal: false,
+            lookups: Arc::default(),
+        })
     }
 
-    /// Initialize the database schema.
-    fn initialize_schema(&self) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        // OHLCV cache table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS ohlcv_cache (
-                provider TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                date TEXT NOT NULL,
-                open REAL NOT NULL,
-                high REAL NOT NULL,
-                low REAL NOT NULL,
-                close REAL NOT NULL,
-                volume REAL NOT NULL,
-                adjusted_close REAL,
-                cached_at TEXT NOT NULL,
-                PRIMARY KEY (provider, symbol, date)
-            )",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_ohlcv_provider_symbol_date
-             ON ohlcv_cache(provider, symbol, date)",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        // Financials cache table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS financials_cache (
-                provider TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                period_end TEXT NOT NULL,
-                period_type TEXT NOT NULL,
-                fiscal_year INTEGER,
-                fiscal_quarter INTEGER,
-                data_json TEXT NOT NULL,
-                cached_at TEXT NOT NULL,
-                PRIMARY KEY (provider, symbol, period_end, period_type)
-            )",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_financials_provider_symbol
-             ON financials_cache(provider, symbol)",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
+    /// Keep every stored version of OHLCV rows, financial statements, metrics
+    /// and company info rather than only the latest.
+    ///
+    /// Versions are keyed by the time they were stored and read back with the
+    /// `*_as_of` methods of [`DataCache`], so a backtest can be rerun against
+    /// exactly the data an earlier run saw even after a provider restates it.
+    /// History survives [`invalidate_stale`](DataCache::invalidate_stale), which
+    /// only expires the current versions, and is removed by
+    /// [`clear`](DataCache::clear). Ticks and universe snapshots are not
+    /// versioned.
+    #[must_use]
+    pub const fn with_bitemporal(mut self, enabled: bool) -> Self {
+        self.bitemporal = enabled;
+        self
+    }
 
-        // Metrics cache table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS metrics_cache (
-                provider TEXT NOT NULL,
-                symbol TEXT NOT NULL,
-                date TEXT NOT NULL,
-                data_json TEXT NOT NULL,
-                cached_at TEXT NOT NULL,
-                PRIMARY KEY (provider, symbol, date)
-            )",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
+    /// Returns true if every stored version is kept.
+    #[must_use]
+    pub const fn is_bitemporal(&self) -> bool {
+        self.bitemporal
+    }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_metrics_provider_symbol
-             ON metrics_cache(provider, symbol)",
-            [],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
+    /// Returns the maximum number of pooled connections.
+    #[must_use]
+    pub fn pool_size(&self) -> usize {
+        self.pool.max_size
+    }
 
-        debug!("SQLite cache schema initialized");
-        Ok(())
+    /// Run `f` with a pooled connection on the blocking thread pool.
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let pool = self.pool.clone();
+        let span = Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            let mut conn = pool.get()?;
+            f(&mut conn)
+        })
+        .await
+        .map_err(|e| DataError::Cache(e.to_string()))?
     }
 
-    /// Convert period type to database string.
-    fn period_type_to_str(pt: PeriodType) -> &'static str {
-        match pt {
-            PeriodType::Annual => "A",
-            PeriodType::Quarterly => "Q",
+    /// Bring the database schema up to [`SqliteCache::SCHEMA_VERSION`].
+    ///
+    /// Each pending migration runs in its own transaction together with the
+    /// `user_version` bump, so an interrupted upgrade resumes where it stopped.
+    ///
+    /// # Errors
+    /// Returns an error if the file was written by a newer schema version or a
+    /// migration fails.
+    fn migrate(conn: &mut Connection) -> Result<()> {
+        loop {
+            // Re-read the version under the write lock in case another process
+            // is migrating the same file
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let version: u32 = tx
+                .pragma_query_value(None, "user_version", |row| row.get(0))
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            if version > Self::SCHEMA_VERSION {
+                return Err(DataError::Cache(format!(
+                    "SQLite cache schema version {} is newer than the supported version {}; \
+                     upgrade data-cache or point the cache at a different file",
+                    version,
+                    Self::SCHEMA_VERSION
+                )));
+            }
+            let Some(migration) = MIGRATIONS.get(version as usize) else {
+                debug!(version, "SQLite cache schema is up to date");
+                return Ok(());
+            };
+
+            (migration.apply)(&tx).map_err(|e| {
+                DataError::Cache(format!(
+                    "SQLite cache migration to version {} ({}) failed: {}",
+                    version + 1,
+                    migration.description,
+                    e
+                ))
+            })?;
+            tx.pragma_update(None, "user_version", version + 1)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+
+            debug!(
+                version = version + 1,
+                "Applied SQLite cache migration: {}", migration.description
+            );
         }
     }
 
-    /// Convert database string to period type.
-    #[allow(dead_code)]
-    fn str_to_period_type(s: &str) -> Result<PeriodType> {
-        match s {
-            "A" => Ok(PeriodType::Annual),
-            "Q" => Ok(PeriodType::Quarterly),
-            _ => Err(DataError::Parse(format!("Invalid period type: {}", s))),
-        }
+    /// Parse a stored `cached_at` timestamp.
+    fn parse_cached_at(s: &str) -> Result<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|t| t.to_utc())
+            .map_err(|e| DataError::Parse(e.to_string()))
     }
-}
 
-#[async_trait]
-impl DataCache for SqliteCache {
-    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
-    async fn get_ohlcv(
-        &self,
-        provider: &str,
-        symbol: &Symbol,
+    /// Load the cached OHLCV rows of a series within `start..=end`, or the
+    /// newest version of each row stored by `knowledge_time` when given.
+    fn ohlcv_rows(
+        conn: &Connection,
+        key: &OhlcvCacheKey,
         start: NaiveDate,
         end: NaiveDate,
+        knowledge_time: Option<&str>,
     ) -> Result<Option<DataFrame>> {
-        let provider = provider.to_string();
-        let symbol_str = symbol.to_string();
-        let start_str = start.to_string();
-        let end_str = end.to_string();
-
-        // Clone the connection for spawn_blocking
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-
         let mut stmt = conn
-            .prepare(
-                "SELECT symbol, date, open, high, low, close, volume, adjusted_close
-                 FROM ohlcv_cache
-                 WHERE provider = ?1 AND symbol = ?2 AND date >= ?3 AND date <= ?4
-                 ORDER BY date ASC",
-            )
+            .prepare_cached(match knowledge_time {
+                None => {
+                    "SELECT symbol, date, open, high, low, close, volume, adjusted_close
+                     FROM ohlcv_cache
+                     WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4
+                       AND date >= ?5 AND date <= ?6
+                     ORDER BY date ASC"
+                }
+                Some(_) => {
+                    "SELECT symbol, date, open, high, low, close, volume, adjusted_close
+                     FROM ohlcv_history h
+                     WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4
+                       AND date >= ?5 AND date <= ?6
+                       AND cached_at = (
+                           SELECT MAX(cached_at) FROM ohlcv_history v
+                           WHERE v.provider = h.provider AND v.symbol = h.symbol
+                             AND v.frequency = h.frequency AND v.adjustment = h.adjustment
+                             AND v.date = h.date AND v.cached_at <= ?7
+                       )
+                     ORDER BY date ASC"
+                }
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
 
         let mut symbols = Vec::new();
@@ -191,19 +240,32 @@ impl DataCache for SqliteCache {
         let mut volumes = Vec::new();
         let mut adj_closes: Vec<Option<f64>> = Vec::new();
 
+        let (start, end) = (start.to_string(), end.to_string());
+        let params = params![
+            key.provider,
+            key.symbol.as_str(),
+            key.frequency.as_str(),
+            key.adjustment.as_str(),
+            start,
+            end,
+            knowledge_time
+        ];
         let rows = stmt
-            .query_map(params![provider, symbol_str, start_str, end_str], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, f64>(2)?,
-                    row.get::<_, f64>(3)?,
-                    row.get::<_, f64>(4)?,
-                    row.get::<_, f64>(5)?,
-                    row.get::<_, f64>(6)?,
-                    row.get::<_, Option<f64>>(7)?,
-                ))
-            })
+            .query_map(
+                &params[..if knowledge_time.is_some() { 7 } else { 6 }],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, f64>(2)?,
+                        row.get::<_, f64>(3)?,
+                        row.get::<_, f64>(4)?,
+                        row.get::<_, f64>(5)?,
+                        row.get::<_, f64>(6)?,
+                        row.get::<_, Option<f64>>(7)?,
+                    ))
+                },
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
 
         for row in rows {
@@ -223,7 +285,6 @@ impl DataCache for SqliteCache {
             debug!("No cached OHLCV data found");
             return Ok(None);
         }
-
         debug!("Found {} cached OHLCV rows", dates.len());
 
         let df = DataFrame::new(vec![
@@ -239,109 +300,1120 @@ impl DataCache for SqliteCache {
         .map_err(|e| DataError::Cache(e.to_string()))?;
 
         // Convert date strings to Date type
-        let df = df
-            .lazy()
+        df.lazy()
             .with_column(col("date").cast(DataType::Date))
             .collect()
+            .map(Some)
+            .map_err(|e| DataError::Cache(e.to_string()))
+    }
+
+    /// Load the date ranges recorded as covered for a series, or those recorded
+    /// by `knowledge_time` when given.
+    fn ohlcv_coverage(
+        conn: &Connection,
+        key: &OhlcvCacheKey,
+        knowledge_time: Option<&str>,
+    ) -> Result<Vec<CoveredRange>> {
+        let mut stmt = conn
+            .prepare_cached(match knowledge_time {
+                None => {
+                    "SELECT start_date, end_date, cached_at FROM ohlcv_coverage
+                     WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4"
+                }
+                Some(_) => {
+                    "SELECT start_date, end_date, MAX(cached_at) FROM ohlcv_coverage_history
+                     WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4
+                       AND cached_at <= ?5
+                     GROUP BY start_date, end_date"
+                }
+            })
+            .map_err(|e| DataError::Cache(e.to_string()))?;
+        let provider = key.provider.as_str();
+        let (symbol, frequency, adjustment) = (
+            key.symbol.as_str(),
+            key.frequency.as_str(),
+            key.adjustment.as_str(),
+        );
+        let rows = stmt
+            .query_map(
+                &params![provider, symbol, frequency, adjustment, knowledge_time]
+                    [..if knowledge_time.is_some() { 5 } else { 4 }],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
 
-        Ok(Some(df))
+        let mut ranges = Vec::new();
+        for row in rows {
+            let (start, end, cached_at) = row.map_err(|e| DataError::Cache(e.to_string()))?;
+            let start = start
+                .parse::<NaiveDate>()
+                .map_err(|e| DataError::Parse(e.to_string()))?;
+            let end = end
+                .parse::<NaiveDate>()
+                .map_err(|e| DataError::Parse(e.to_string()))?;
+            ranges.push(CoveredRange::new(
+                DateRange::new(start, end),
+                Self::parse_cached_at(&cached_at)?,
+            ));
+        }
+        Ok(ranges)
     }
 
-    #[instrument(skip(self, data), fields(provider = %provider, symbol = %symbol))]
-    async fn put_ohlcv(&self, provider: &str, symbol: &Symbol, data: &DataFrame) -> Result<()> {
-        let cached_at = Utc::now().to_rfc3339();
+    /// Record a date range as covered for a series.
+    fn record_ohlcv_coverage(
+        conn: &Connection,
+        key: &OhlcvCacheKey,
+        range: DateRange,
+        cached_at: &str,
+    ) -> Result<()> {
+        conn.prepare_cached(
+            "INSERT OR REPLACE INTO ohlcv_coverage
+             (provider, symbol, frequency, adjustment, start_date, end_date, cached_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )
+        .and_then(|mut stmt| {
+            stmt.execute(params![
+                key.provider,
+                key.symbol.as_str(),
+                key.frequency.as_str(),
+                key.adjustment.as_str(),
+                range.start.to_string(),
+                range.end.to_string(),
+                cached_at
+            ])
+        })
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Copy the OHLCV rows and coverage written at `cached_at` into history.
+    fn record_ohlcv_history(conn: &Connection, key: &OhlcvCacheKey, cached_at: &str) -> Result<()> {
+        let params = params![
+            key.provider,
+            key.symbol.as_str(),
+            key.frequency.as_str(),
+            key.adjustment.as_str(),
+            cached_at
+        ];
+        conn.prepare_cached(
+            "INSERT OR REPLACE INTO ohlcv_history
+             SELECT provider, symbol, frequency, adjustment, date, open, high, low, close,
+                    volume, adjusted_close, cached_at
+             FROM ohlcv_cache
+             WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4
+               AND cached_at = ?5",
+        )
+        .and_then(|mut stmt| stmt.execute(params))
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+        conn.prepare_cached(
+            "INSERT OR REPLACE INTO ohlcv_coverage_history
+             SELECT provider, symbol, frequency, adjustment, start_date, end_date, cached_at
+             FROM ohlcv_coverage
+             WHERE provider = ?1 AND symbol = ?2 AND frequency = ?3 AND adjustment = ?4
+               AND cached_at = ?5",
+        )
+        .and_then(|mut stmt| stmt.execute(params))
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Read financial statements, timestamped with the oldest of their writes.
+    fn collect_financials(
+        rows: impl Iterator<Item = rusqlite::Result<(String, String)>>,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        Self::collect_financial_entries(rows).map(Self::oldest_financials)
+    }
+
+    /// Combine financial statements into one value timestamped with the oldest
+    /// of their writes.
+    fn oldest_financials(
+        entries: Vec<Cached<FinancialStatement>>,
+    ) -> Option<Cached<Vec<FinancialStatement>>> {
+        let Some(oldest) = entries.iter().map(|e| e.cached_at).min() else {
+            debug!("No cached financials found");
+            return None;
+        };
+
+        debug!("Found {} cached financial statements", entries.len());
+        let statements = entries.into_iter().map(|e| e.value).collect();
+        Some(Cached::new(statements, oldest))
+    }
+
+    /// Deserialize financial statements along with when each was stored.
+    fn collect_financial_entries(
+        rows: impl Iterator<Item = rusqlite::Result<(String, String)>>,
+    ) -> Result<Vec<Cached<FinancialStatement>>> {
+        rows.map(|row| {
+            let (json, cached_at) = row.map_err(|e| DataError::Cache(e.to_string()))?;
+            let statement =
+                serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
+            Ok(Cached::new(statement, Self::parse_cached_at(&cached_at)?))
+        })
+        .collect()
+    }
+
+    /// Load the current financial statements of a symbol, each with when it
+    /// was stored.
+    async fn financial_entries(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Vec<Cached<FinancialStatement>>> {
         let provider = provider.to_string();
         let symbol_str = symbol.to_string();
+        let period_type_str = Self::period_type_to_str(period_type);
 
-        // Extract columns
-        let symbols = data
-            .column("symbol")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .str()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-        let dates = data
-            .column("date")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .cast(&DataType::String)
+        self.with_conn(move |conn| {
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT data_json, cached_at FROM financials_cache
+                     WHERE provider = ?1 AND symbol = ?2 AND period_type = ?3
+                     ORDER BY period_end DESC",
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            let rows = stmt
+                .query_map(params![provider, symbol_str, period_type_str], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            Self::collect_financial_entries(rows)
+        })
+        .await
+    }
+
+    /// Deserialize a stored JSON value and its `cached_at` timestamp.
+    fn parse_cached<T: serde::de::DeserializeOwned>(
+        row: Option<(String, String)>,
+    ) -> Result<Option<Cached<T>>> {
+        let Some((json, cached_at)) = row else {
+            return Ok(None);
+        };
+        let value = serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
+        Ok(Some(Cached::new(value, Self::parse_cached_at(&cached_at)?)))
+    }
+
+    /// Convert period type to database string.
+    fn period_type_to_str(pt: PeriodType) -> &'static str {
+        match pt {
+            PeriodType::Annual => "A",
+            PeriodType::Quarterly => "Q",
+        }
+    }
+
+    /// Convert database string to period type.
+    fn str_to_period_type(s: &str) -> Result<PeriodType> {
+        match s {
+            "A" => Ok(PeriodType::Annual),
+            "Q" => Ok(PeriodType::Quarterly),
+            _ => Err(DataError::Parse(format!("Invalid period type: {}", s))),
+        }
+    }
+
+    /// Summarise a table per entry.
+    ///
+    /// `sql` selects the provider, id, frequency, adjustment and period type
+    /// (NULL where they do not apply), then the row count, estimated bytes and
+    /// the oldest and newest `cached_at`.
+    fn table_stats(
+        conn: &Connection,
+        kind: CacheDataKind,
+        sql: &str,
+    ) -> Result<Vec<CacheEntryStats>> {
+        let mut stmt = conn
+            .prepare_cached(sql)
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let dates = dates.str().map_err(|e| DataError::Cache(e.to_string()))?;
-        let opens = data
-            .column("open")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .f64()
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    (
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                    ),
+                    (
+                        row.get::<_, i64>(5)?,
+                        row.get::<_, i64>(6)?,
+                        row.get::<_, String>(7)?,
+                        row.get::<_, String>(8)?,
+                    ),
+                ))
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let highs = data
-            .column("high")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .f64()
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (
+                (provider, id, frequency, adjustment, period_type),
+                (count, bytes, oldest, newest),
+            ) = row.map_err(|e| DataError::Cache(e.to_string()))?;
+            let mut stats =
+                CacheEntryStats::new(kind, provider, id, Self::parse_cached_at(&oldest)?);
+            stats.observe(Self::parse_cached_at(&newest)?);
+            stats.frequency = frequency.map(|f| f.parse()).transpose()?;
+            stats.adjustment = adjustment.map(|a| a.parse()).transpose()?;
+            stats.period_type = period_type
+                .map(|p| Self::str_to_period_type(&p))
+                .transpose()?;
+            stats.rows = usize::try_from(count).unwrap_or_default();
+            stats.bytes = usize::try_from(bytes).unwrap_or_default();
+            entries.push(stats);
+        }
+        Ok(entries)
+    }
+
+    /// Add the recorded coverage of OHLCV series and ticks to their entries,
+    /// creating entries for those whose covered ranges hold no rows.
+    fn add_coverage(conn: &Connection, entries: &mut Vec<CacheEntryStats>) -> Result<()> {
+        let mut covered = Vec::new();
+
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT provider, symbol, frequency, adjustment, start_date, end_date, cached_at
+                 FROM ohlcv_coverage",
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let lows = data
-            .column("low")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .f64()
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    (
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ),
+                    (
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                        row.get::<_, String>(6)?,
+                    ),
+                ))
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let closes = data
-            .column("close")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .f64()
+        for row in rows {
+            let ((provider, symbol, frequency, adjustment), (start, end, cached_at)) =
+                row.map_err(|e| DataError::Cache(e.to_string()))?;
+            let key = OhlcvCacheKey::new(provider, &Symbol::new(symbol), frequency.parse()?)
+                .with_adjustment(adjustment.parse()?);
+            let range = DateRange::new(
+                start
+                    .parse::<NaiveDate>()
+                    .map_err(|e| DataError::Parse(e.to_string()))?,
+                end.parse::<NaiveDate>()
+                    .map_err(|e| DataError::Parse(e.to_string()))?,
+            );
+            let cached_at = Self::parse_cached_at(&cached_at)?;
+            covered.push((CacheEntryStats::ohlcv(&key, cached_at), range));
+        }
+
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT provider, symbol, start_timestamp, end_timestamp, cached_at
+                 FROM tick_coverage",
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let volumes = data
-            .column("volume")
-            .map_err(|e| DataError::Cache(e.to_string()))?
-            .f64()
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
+        for row in rows {
+            let (provider, symbol, start, end, cached_at) =
+                row.map_err(|e| DataError::Cache(e.to_string()))?;
+            let date = |micros: i64| {
+                DateTime::from_timestamp_micros(micros)
+                    .map(|t| t.date_naive())
+                    .ok_or_else(|| DataError::Parse(format!("Invalid tick timestamp: {micros}")))
+            };
+            let range = DateRange::new(date(start)?, date(end)?);
+            let cached_at = Self::parse_cached_at(&cached_at)?;
+            covered.push((
+                CacheEntryStats::new(CacheDataKind::Ticks, provider, symbol, cached_at),
+                range,
+            ));
+        }
+
+        let id = |e: &CacheEntryStats| {
+            (
+                e.kind,
+                e.provider.clone(),
+                e.id.clone(),
+                e.frequency,
+                e.adjustment,
+            )
+        };
+        let mut index: HashMap<_, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (id(e), i))
+            .collect();
+        for (stats, range) in covered {
+            let cached_at = stats.oldest_cached_at;
+            let i = *index.entry(id(&stats)).or_insert_with(|| {
+                entries.push(stats);
+                entries.len() - 1
+            });
+            entries[i].observe(cached_at);
+            entries[i].covered.push(range);
+        }
+        Ok(())
+    }
+}
 
-        // adjusted_close may be optional
-        let adj_closes = data
-            .column("adjusted_close")
-            .ok()
-            .and_then(|c| c.f64().ok());
+/// A one-way schema change.
+struct Migration {
+    /// What the migration does, for logs and error messages.
+    description: &'static str,
+    /// Apply the change; runs inside the migration's transaction.
+    apply: fn(&Connection) -> rusqlite::Result<()>,
+}
 
-        let conn = self
-            .conn
+/// Ordered schema migrations; entry `i` upgrades a database from version `i`
+/// to `i + 1`.
+///
+/// Only ever append to this list. Files written before versioning report
+/// version 0 but may already hold any of the early tables, so those steps use
+/// `IF NOT EXISTS` and column checks to replay safely.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "create OHLCV, financials and metrics tables",
+        apply: create_initial_tables,
+    },
+    Migration {
+        description: "key OHLCV rows by frequency and adjustment",
+        apply: key_ohlcv_by_series,
+    },
+    Migration {
+        description: "track covered OHLCV ranges",
+        apply: create_ohlcv_coverage,
+    },
+    Migration {
+        description: "add tick, company info and universe tables",
+        apply: create_tick_and_reference_tables,
+    },
+    Migration {
+        description: "add version history tables for bitemporal reads",
+        apply: create_history_tables,
+    },
+    Migration {
+        description: "add negative cache table",
+        apply: create_negative_cache,
+    },
+];
+
+fn create_initial_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ohlcv_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            date TEXT NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume REAL NOT NULL,
+            adjusted_close REAL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, date)
+        );
+
+        CREATE TABLE IF NOT EXISTS financials_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            period_end TEXT NOT NULL,
+            period_type TEXT NOT NULL,
+            fiscal_year INTEGER,
+            fiscal_quarter INTEGER,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, period_end, period_type)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_financials_provider_symbol
+        ON financials_cache(provider, symbol);
+
+        CREATE TABLE IF NOT EXISTS metrics_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            date TEXT NOT NULL,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, date)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_metrics_provider_symbol
+        ON metrics_cache(provider, symbol);",
+    )
+}
+
+/// Rebuild `ohlcv_cache` with frequency and adjustment in the key.
+///
+/// The old schema stored one row per date, so its rows can only hold daily
+/// bars; they are kept as raw daily data.
+fn key_ohlcv_by_series(conn: &Connection) -> rusqlite::Result<()> {
+    let has_frequency = conn
+        .prepare("SELECT name FROM pragma_table_info('ohlcv_cache')")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+        .iter()
+        .any(|c| c == "frequency");
+
+    if !has_frequency {
+        conn.execute_batch(
+            "ALTER TABLE ohlcv_cache RENAME TO ohlcv_cache_unkeyed;
+             CREATE TABLE ohlcv_cache (
+                provider TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                frequency TEXT NOT NULL,
+                adjustment TEXT NOT NULL,
+                date TEXT NOT NULL,
+                open REAL NOT NULL,
+                high REAL NOT NULL,
+                low REAL NOT NULL,
+                close REAL NOT NULL,
+                volume REAL NOT NULL,
+                adjusted_close REAL,
+                cached_at TEXT NOT NULL,
+                PRIMARY KEY (provider, symbol, frequency, adjustment, date)
+             );
+             INSERT INTO ohlcv_cache
+                (provider, symbol, frequency, adjustment, date, open, high, low, close,
+                 volume, adjusted_close, cached_at)
+             SELECT provider, symbol, '1d', 'raw', date, open, high, low, close,
+                    volume, adjusted_close, cached_at
+             FROM ohlcv_cache_unkeyed;
+             DROP TABLE ohlcv_cache_unkeyed;",
+        )?;
+    }
+
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_ohlcv_series_date
+         ON ohlcv_cache(provider, symbol, frequency, adjustment, date);",
+    )
+}
+
+/// Create `ohlcv_coverage`.
+///
+/// Rows cached before coverage was tracked may have holes, so no coverage is
+/// recorded for them: they are still returned, but their range is reported
+/// as missing until it is fetched again.
+fn create_ohlcv_coverage(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ohlcv_coverage (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            frequency TEXT NOT NULL,
+            adjustment TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, frequency, adjustment, start_date, end_date)
+        );",
+    )
+}
+
+fn create_tick_and_reference_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "-- Timestamps are microseconds since the epoch so ranges compare numerically
+        CREATE TABLE IF NOT EXISTS tick_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tick_provider_symbol_timestamp
+        ON tick_cache(provider, symbol, timestamp);
+
+        CREATE TABLE IF NOT EXISTS tick_coverage (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            start_timestamp INTEGER NOT NULL,
+            end_timestamp INTEGER NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, start_timestamp, end_timestamp)
+        );
+
+        CREATE TABLE IF NOT EXISTS company_info_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol)
+        );
+
+        CREATE TABLE IF NOT EXISTS universe_cache (
+            provider TEXT NOT NULL,
+            universe_id TEXT NOT NULL,
+            as_of TEXT NOT NULL,
+            symbols_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, universe_id, as_of)
+        );",
+    )
+}
+
+/// Tables holding every stored version, keyed by `cached_at` on top of the
+/// natural key of the table they shadow.
+fn create_history_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE ohlcv_history (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            frequency TEXT NOT NULL,
+            adjustment TEXT NOT NULL,
+            date TEXT NOT NULL,
+            open REAL NOT NULL,
+            high REAL NOT NULL,
+            low REAL NOT NULL,
+            close REAL NOT NULL,
+            volume REAL NOT NULL,
+            adjusted_close REAL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, frequency, adjustment, date, cached_at)
+        );
+
+        CREATE TABLE ohlcv_coverage_history (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            frequency TEXT NOT NULL,
+            adjustment TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, frequency, adjustment, start_date, end_date, cached_at)
+        );
+
+        CREATE TABLE financials_history (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            period_end TEXT NOT NULL,
+            period_type TEXT NOT NULL,
+            fiscal_year INTEGER,
+            fiscal_quarter INTEGER,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, period_end, period_type, cached_at)
+        );
+
+        CREATE TABLE metrics_history (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            date TEXT NOT NULL,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, date, cached_at)
+        );
+
+        CREATE TABLE company_info_history (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            data_json TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, cached_at)
+        );",
+    )
+}
+
+/// `request` is empty for symbol-level entries so it can be part of the key.
+fn create_negative_cache(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE negative_cache (
+            provider TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            request TEXT NOT NULL,
+            message TEXT NOT NULL,
+            cached_at TEXT NOT NULL,
+            PRIMARY KEY (provider, symbol, request)
+        );",
+    )
+}
+
+/// Where pooled connections are opened.
+#[derive(Debug)]
+enum Target {
+    File(PathBuf),
+    Memory,
+}
+
+/// A bounded pool of SQLite connections.
+///
+/// Connections are opened lazily up to `max_size`. Callers run on the blocking
+/// thread pool, so waiting for a free connection parks that thread rather than
+/// the async executor.
+#[derive(Debug)]
+struct Pool {
+    target: Target,
+    max_size: usize,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    idle: Vec<Connection>,
+    open: usize,
+}
+
+impl Pool {
+    /// Open the first connection and bring the schema up to date.
+    fn new(target: Target, max_size: usize) -> Result<Self> {
+        let mut conn = Self::open(&target)?;
+        SqliteCache::migrate(&mut conn)?;
+        Ok(Self {
+            target,
+            max_size,
+            state: Mutex::new(PoolState {
+                idle: vec![conn],
+                open: 1,
+            }),
+            returned: Condvar::new(),
+        })
+    }
+
+    /// Open and configure a new connection.
+    fn open(target: &Target) -> Result<Connection> {
+        let conn = match target {
+            Target::File(path) => Connection::open(path),
+            Target::Memory => Connection::open_in_memory(),
+        }
+        .map_err(|e| DataError::Cache(e.to_string()))?;
+
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| DataError::Cache(e.to_string()))?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+
+        if matches!(target, Target::File(_)) {
+            let mode: String = conn
+                .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            if !mode.eq_ignore_ascii_case("wal") {
+                debug!(mode = %mode, "SQLite cache could not enable WAL journaling");
+            }
+            // WAL keeps committed data durable across application crashes at
+            // this level; only an OS crash can lose the latest transactions
+            conn.pragma_update(None, "synchronous", "NORMAL")
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+        Ok(conn)
+    }
+
+    /// Take a connection, opening one if the pool has room or waiting for one
+    /// to be returned otherwise.
+    fn get(&self) -> Result<PooledConnection<'_>> {
+        let mut state = self
+            .state
             .lock()
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        let tx = conn
-            .unchecked_transaction()
+        loop {
+            if let Some(conn) = state.idle.pop() {
+                return Ok(PooledConnection {
+                    pool: self,
+                    conn: Some(conn),
+                });
+            }
+            if state.open < self.max_size {
+                state.open += 1;
+                drop(state);
+                return match Self::open(&self.target) {
+                    Ok(conn) => Ok(PooledConnection {
+                        pool: self,
+                        conn: Some(conn),
+                    }),
+                    Err(e) => {
+                        if let Ok(mut state) = self.state.lock() {
+                            state.open -= 1;
+                        }
+                        self.returned.notify_one();
+                        Err(e)
+                    }
+                };
+            }
+            state = self
+                .returned
+                .wait(state)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+        }
+    }
+}
+
+/// A connection checked out of the pool; returned when dropped.
+struct PooledConnection<'a> {
+    pool: &'a Pool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("connection is present until drop")
+    }
+}
+
+impl DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+            .as_mut()
+            .expect("connection is present until drop")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        if let Ok(mut state) = self.pool.state.lock() {
+            state.idle.push(conn);
+        }
+        self.pool.returned.notify_one();
+    }
+}
+
+#[async_trait]
+impl DataCache for SqliteCache {
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        let key = key.clone();
+
+        let lookup = self
+            .with_conn(move |conn| {
+                let data = Self::ohlcv_rows(conn, &key, start, end, None)?;
+                let coverage = Self::ohlcv_coverage(conn, &key, None)?;
+                Ok(OhlcvLookup::from_coverage(data, &coverage, start, end))
+            })
+            .await?;
+        self.lookups.record(lookup.is_complete());
+        Ok(lookup)
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let covered = CoveredRange::new(DateRange::new(start, end), Utc::now());
+        self.put_ohlcv_at(key, covered, data).await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let cached_at = covered.cached_at.to_rfc3339();
+        let key = key.clone();
+        let range = covered.range;
+        let data = data.clone();
+        let bitemporal = self.bitemporal;
+
+        self.with_conn(move |conn| {
+            if data.height() == 0 {
+                let tx = conn
+                    .transaction_with_behavior(TransactionBehavior::Immediate)
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                Self::record_ohlcv_coverage(&tx, &key, range, &cached_at)?;
+                if bitemporal {
+                    Self::record_ohlcv_history(&tx, &key, &cached_at)?;
+                }
+                tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+                debug!("Recorded empty OHLCV range");
+                return Ok(());
+            }
+
+            let symbol_str = key.symbol.to_string();
+            let frequency = key.frequency.as_str();
+            let adjustment = key.adjustment.as_str();
+
+            // Extract columns; rows without a symbol belong to the key's symbol
+            let symbols = data.column("symbol").ok().and_then(|c| c.str().ok());
+            let dates = data
+                .column("date")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .cast(&DataType::String)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let dates = dates.str().map_err(|e| DataError::Cache(e.to_string()))?;
+            let opens = data
+                .column("open")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .f64()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let highs = data
+                .column("high")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .f64()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let lows = data
+                .column("low")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .f64()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let closes = data
+                .column("close")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .f64()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let volumes = data
+                .column("volume")
+                .map_err(|e| DataError::Cache(e.to_string()))?
+                .f64()
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            // adjusted_close may be optional
+            let adj_closes = data
+                .column("adjusted_close")
+                .ok()
+                .and_then(|c| c.f64().ok());
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            {
+                // One prepared statement serves every row of the batch
+                let mut insert = tx
+                    .prepare_cached(
+                        "INSERT OR REPLACE INTO ohlcv_cache
+                         (provider, symbol, frequency, adjustment, date, open, high, low, close,
+                          volume, adjusted_close, cached_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                for i in 0..data.height() {
+                    let sym = symbols
+                        .and_then(|c| c.get(i))
+                        .unwrap_or(symbol_str.as_str());
+                    let date = dates
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing date".to_string()))?;
+                    let open = opens
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing open".to_string()))?;
+                    let high = highs
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing high".to_string()))?;
+                    let low = lows
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing low".to_string()))?;
+                    let close = closes
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing close".to_string()))?;
+                    let volume = volumes
+                        .get(i)
+                        .ok_or_else(|| DataError::Cache("Missing volume".to_string()))?;
+                    let adj_close = adj_closes.as_ref().and_then(|c| c.get(i));
+
+                    insert
+                        .execute(params![
+                            key.provider,
+                            sym,
+                            frequency,
+                            adjustment,
+                            date,
+                            open,
+                            high,
+                            low,
+                            close,
+                            volume,
+                            adj_close,
+                            cached_at
+                        ])
+                        .map_err(|e| DataError::Cache(e.to_string()))?;
+                }
+            }
+            Self::record_ohlcv_coverage(&tx, &key, range, &cached_at)?;
+            if bitemporal {
+                Self::record_ohlcv_history(&tx, &key, &cached_at)?;
+            }
+
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+            debug!("Cached {} OHLCV rows", data.height());
+            Ok(())
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
+
+        let cached = self
+            .with_conn(move |conn| {
+                let covered_at = conn
+                    .prepare_cached(
+                        "SELECT cached_at FROM tick_coverage
+                     WHERE provider = ?1 AND symbol = ?2
+                       AND start_timestamp <= ?3 AND end_timestamp >= ?4
+                     ORDER BY cached_at DESC
+                     LIMIT 1",
+                    )
+                    .and_then(|mut stmt| {
+                        stmt.query_row(
+                            params![
+                                provider,
+                                symbol_str,
+                                start.timestamp_micros(),
+                                end.timestamp_micros()
+                            ],
+                            |row| row.get::<_, String>(0),
+                        )
+                        .optional()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                let Some(covered_at) = covered_at else {
+                    debug!("No cached ticks found");
+                    return Ok(None);
+                };
+
+                let mut stmt = conn
+                    .prepare_cached(
+                        "SELECT data_json FROM tick_cache
+                     WHERE provider = ?1 AND symbol = ?2 AND timestamp >= ?3 AND timestamp <= ?4
+                     ORDER BY timestamp, rowid",
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                let rows = stmt
+                    .query_map(
+                        params![
+                            provider,
+                            symbol_str,
+                            start.timestamp_micros(),
+                            end.timestamp_micros()
+                        ],
+                        |row| row.get::<_, String>(0),
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                let mut ticks = Vec::new();
+                for row in rows {
+                    let json = row.map_err(|e| DataError::Cache(e.to_string()))?;
+                    let tick: Tick =
+                        serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
+                    ticks.push(tick);
+                }
+
+                debug!("Found {} cached ticks", ticks.len());
+                Ok(Some(Cached::new(
+                    ticks,
+                    Self::parse_cached_at(&covered_at)?,
+                )))
+            })
+            .await?;
+        self.lookups.record(cached.is_some());
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, ticks), fields(provider = %provider, symbol = %symbol, count = ticks.len()))]
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let cached_at = Utc::now().to_rfc3339();
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
+        let rows = ticks
+            .iter()
+            .map(|tick| {
+                serde_json::to_string(tick)
+                    .map(|json| (tick.timestamp.timestamp_micros(), json))
+                    .map_err(|e| DataError::Parse(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.with_conn(move |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            // Ticks have no natural key, so replace everything in the range
+            tx.execute(
+                "DELETE FROM tick_cache
+                 WHERE provider = ?1 AND symbol = ?2 AND timestamp >= ?3 AND timestamp <= ?4",
+                params![
+                    provider,
+                    symbol_str,
+                    start.timestamp_micros(),
+                    end.timestamp_micros()
+                ],
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
 
-        for i in 0..data.height() {
-            let sym = symbols.get(i).unwrap_or(&symbol_str);
-            let date = dates
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing date".to_string()))?;
-            let open = opens
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing open".to_string()))?;
-            let high = highs
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing high".to_string()))?;
-            let low = lows
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing low".to_string()))?;
-            let close = closes
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing close".to_string()))?;
-            let volume = volumes
-                .get(i)
-                .ok_or_else(|| DataError::Cache("Missing volume".to_string()))?;
-            let adj_close = adj_closes.as_ref().and_then(|c| c.get(i));
+            {
+                let mut insert = tx
+                    .prepare_cached(
+                        "INSERT INTO tick_cache (provider, symbol, timestamp, data_json, cached_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                for (timestamp, data_json) in &rows {
+                    insert
+                        .execute(params![
+                            provider, symbol_str, timestamp, data_json, cached_at
+                        ])
+                        .map_err(|e| DataError::Cache(e.to_string()))?;
+                }
+            }
 
             tx.execute(
-                "INSERT OR REPLACE INTO ohlcv_cache
-                 (provider, symbol, date, open, high, low, close, volume, adjusted_close, cached_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                "INSERT OR REPLACE INTO tick_coverage
+                 (provider, symbol, start_timestamp, end_timestamp, cached_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![
-                    provider, sym, date, open, high, low, close, volume, adj_close, cached_at
+                    provider,
+                    symbol_str,
+                    start.timestamp_micros(),
+                    end.timestamp_micros(),
+                    cached_at
                 ],
             )
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        }
 
-        tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
-        debug!("Cached {} OHLCV rows", data.height());
-        Ok(())
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+            debug!("Cached {} ticks", rows.len());
+            Ok(())
+        })
+        .await
     }
 
     #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
@@ -350,163 +1422,719 @@ impl DataCache for SqliteCache {
         provider: &str,
         symbol: &Symbol,
         period_type: PeriodType,
-    ) -> Result<Option<Vec<FinancialStatement>>> {
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        let entries = self
+            .financial_entries(provider, symbol, period_type)
+            .await?;
+        let cached = Self::oldest_financials(entries);
+        self.lookups.record(cached.is_some());
+        Ok(cached)
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials_entries(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Vec<Cached<FinancialStatement>>>> {
+        let entries = self
+            .financial_entries(provider, symbol, period_type)
+            .await?;
+        self.lookups.record(!entries.is_empty());
+        Ok(Some(entries).filter(|e| !e.is_empty()))
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[FinancialStatement],
+    ) -> Result<()> {
+        let now = Utc::now();
+        let statements: Vec<Cached<FinancialStatement>> = statements
+            .iter()
+            .map(|s| Cached::new(s.clone(), now))
+            .collect();
+        self.put_financials_at(provider, symbol, &statements).await
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
         let provider = provider.to_string();
         let symbol_str = symbol.to_string();
-        let period_type_str = Self::period_type_to_str(period_type);
+        let rows = statements
+            .iter()
+            .map(
+                |Cached {
+                     value: stmt,
+                     cached_at,
+                 }| {
+                    serde_json::to_string(stmt)
+                        .map(|json| {
+                            (
+                                stmt.period_end.to_string(),
+                                Self::period_type_to_str(stmt.period_type),
+                                stmt.fiscal_year,
+                                stmt.fiscal_quarter,
+                                json,
+                                cached_at.to_rfc3339(),
+                            )
+                        })
+                        .map_err(|e| DataError::Parse(e.to_string()))
+                },
+            )
+            .collect::<Result<Vec<_>>>()?;
+        let mut stamps: Vec<String> = rows.iter().map(|row| row.5.clone()).collect();
+        stamps.sort_unstable();
+        stamps.dedup();
+        let bitemporal = self.bitemporal;
+
+        self.with_conn(move |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            {
+                let mut insert = tx
+                    .prepare_cached(
+                        "INSERT OR REPLACE INTO financials_cache
+                         (provider, symbol, period_end, period_type, fiscal_year, fiscal_quarter, data_json, cached_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                for (period_end, period_type_str, fiscal_year, fiscal_quarter, data_json, cached_at) in
+                    &rows
+                {
+                    insert
+                        .execute(params![
+                            provider,
+                            symbol_str,
+                            period_end,
+                            period_type_str,
+                            fiscal_year,
+                            fiscal_quarter,
+                            data_json,
+                            cached_at
+                        ])
+                        .map_err(|e| DataError::Cache(e.to_string()))?;
+                }
+            }
 
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
+            if bitemporal {
+                for cached_at in &stamps {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO financials_history
+                         SELECT provider, symbol, period_end, period_type, fiscal_year, fiscal_quarter,
+                                data_json, cached_at
+                         FROM financials_cache
+                         WHERE provider = ?1 AND symbol = ?2 AND cached_at = ?3",
+                        params![provider, symbol_str, cached_at],
+                    )
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+                }
+            }
 
-        let mut stmt = conn
-            .prepare(
-                "SELECT data_json FROM financials_cache
-                 WHERE provider = ?1 AND symbol = ?2 AND period_type = ?3
-                 ORDER BY period_end DESC",
-            )
-            .map_err(|e| DataError::Cache(e.to_string()))?;
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+            debug!("Cached {} financial statements", rows.len());
+            Ok(())
+        })
+        .await
+    }
 
-        let rows = stmt
-            .query_map(params![provider, symbol_str, period_type_str], |row| {
-                row.get::<_, String>(0)
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
+        let date_str = date.to_string();
+
+        let cached = self
+            .with_conn(move |conn| {
+                let result = conn
+                    .prepare_cached(
+                        "SELECT data_json, cached_at FROM metrics_cache
+                     WHERE provider = ?1 AND symbol = ?2 AND date = ?3",
+                    )
+                    .and_then(|mut stmt| {
+                        stmt.query_row(params![provider, symbol_str, date_str], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })
+                        .optional()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                match result {
+                    Some((json, cached_at)) => {
+                        let metrics: KeyMetrics = serde_json::from_str(&json)
+                            .map_err(|e| DataError::Parse(e.to_string()))?;
+                        debug!("Found cached metrics");
+                        Ok(Some(Cached::new(
+                            metrics,
+                            Self::parse_cached_at(&cached_at)?,
+                        )))
+                    }
+                    None => {
+                        debug!("No cached metrics found");
+                        Ok(None)
+                    }
+                }
             })
+            .await?;
+        self.lookups.record(cached.is_some());
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &KeyMetrics,
+    ) -> Result<()> {
+        self.put_metrics_at(provider, symbol, &Cached::new(metrics.clone(), Utc::now()))
+            .await
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
+    ) -> Result<()> {
+        let cached_at = metrics.cached_at.to_rfc3339();
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
+        let date_str = metrics.value.date.to_string();
+        let data_json =
+            serde_json::to_string(&metrics.value).map_err(|e| DataError::Parse(e.to_string()))?;
+
+        let bitemporal = self.bitemporal;
+
+        self.with_conn(move |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let params = params![provider, symbol_str, date_str, data_json, cached_at];
+            tx.prepare_cached(
+                "INSERT OR REPLACE INTO metrics_cache
+                 (provider, symbol, date, data_json, cached_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .and_then(|mut stmt| stmt.execute(params))
             .map_err(|e| DataError::Cache(e.to_string()))?;
+            if bitemporal {
+                tx.prepare_cached(
+                    "INSERT OR REPLACE INTO metrics_history
+                     (provider, symbol, date, data_json, cached_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .and_then(|mut stmt| stmt.execute(params))
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            }
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
 
-        let mut statements = Vec::new();
-        for row in rows {
-            let json = row.map_err(|e| DataError::Cache(e.to_string()))?;
-            let stmt: FinancialStatement =
-                serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
-            statements.push(stmt);
-        }
+            debug!("Cached metrics");
+            Ok(())
+        })
+        .await
+    }
 
-        if statements.is_empty() {
-            debug!("No cached financials found");
-            return Ok(None);
-        }
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
 
-        debug!("Found {} cached financial statements", statements.len());
-        Ok(Some(statements))
+        let cached = self
+            .with_conn(move |conn| {
+                let result = conn
+                    .prepare_cached(
+                        "SELECT data_json, cached_at FROM company_info_cache
+                     WHERE provider = ?1 AND symbol = ?2",
+                    )
+                    .and_then(|mut stmt| {
+                        stmt.query_row(params![provider, symbol_str], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })
+                        .optional()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                match result {
+                    Some((json, cached_at)) => {
+                        let info: CompanyInfo = serde_json::from_str(&json)
+                            .map_err(|e| DataError::Parse(e.to_string()))?;
+                        debug!("Found cached company info");
+                        Ok(Some(Cached::new(info, Self::parse_cached_at(&cached_at)?)))
+                    }
+                    None => {
+                        debug!("No cached company info found");
+                        Ok(None)
+                    }
+                }
+            })
+            .await?;
+        self.lookups.record(cached.is_some());
+        Ok(cached)
     }
 
-    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
-    async fn put_financials(
+    #[instrument(skip(self, info), fields(provider = %provider, symbol = %symbol))]
+    async fn put_company_info(
         &self,
         provider: &str,
         symbol: &Symbol,
-        statements: &[FinancialStatement],
+        info: &CompanyInfo,
     ) -> Result<()> {
         let cached_at = Utc::now().to_rfc3339();
         let provider = provider.to_string();
         let symbol_str = symbol.to_string();
-
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-        let tx = conn
-            .unchecked_transaction()
+        let data_json = serde_json::to_string(info).map_err(|e| DataError::Parse(e.to_string()))?;
+
+        let bitemporal = self.bitemporal;
+
+        self.with_conn(move |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let params = params![provider, symbol_str, data_json, cached_at];
+            tx.prepare_cached(
+                "INSERT OR REPLACE INTO company_info_cache
+                 (provider, symbol, data_json, cached_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )
+            .and_then(|mut stmt| stmt.execute(params))
             .map_err(|e| DataError::Cache(e.to_string()))?;
+            if bitemporal {
+                tx.prepare_cached(
+                    "INSERT OR REPLACE INTO company_info_history
+                     (provider, symbol, data_json, cached_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .and_then(|mut stmt| stmt.execute(params))
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            }
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
 
-        for stmt in statements {
-            let period_type_str = Self::period_type_to_str(stmt.period_type);
-            let data_json =
-                serde_json::to_string(stmt).map_err(|e| DataError::Parse(e.to_string()))?;
+            debug!("Cached company info");
+            Ok(())
+        })
+        .await
+    }
 
-            tx.execute(
-                "INSERT OR REPLACE INTO financials_cache
-                 (provider, symbol, period_end, period_type, fiscal_year, fiscal_quarter, data_json, cached_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                params![
+    #[instrument(skip(self), fields(provider = %provider, universe = %universe_id))]
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        let provider = provider.to_string();
+        let universe_id = universe_id.to_string();
+        let as_of_str = as_of.to_string();
+
+        let cached = self
+            .with_conn(move |conn| {
+                let result = conn
+                    .prepare_cached(
+                        "SELECT symbols_json, cached_at FROM universe_cache
+                     WHERE provider = ?1 AND universe_id = ?2 AND as_of <= ?3
+                     ORDER BY as_of DESC
+                     LIMIT 1",
+                    )
+                    .and_then(|mut stmt| {
+                        stmt.query_row(params![provider, universe_id, as_of_str], |row| {
+                            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                        })
+                        .optional()
+                    })
+                    .map_err(|e| DataError::Cache(e.to_string()))?;
+
+                match result {
+                    Some((json, cached_at)) => {
+                        let symbols: Vec<Symbol> = serde_json::from_str(&json)
+                            .map_err(|e| DataError::Parse(e.to_string()))?;
+                        debug!("Found cached universe snapshot");
+                        Ok(Some(Cached::new(
+                            symbols,
+                            Self::parse_cached_at(&cached_at)?,
+                        )))
+                    }
+                    None => {
+                        debug!("No cached universe snapshot found");
+                        Ok(None)
+                    }
+                }
+            })
+            .await?;
+        self.lookups.record(cached.is_some());
+        Ok(cached)
+    }
+
+    #[instrument(skip(self, symbols), fields(provider = %provider, universe = %universe_id, count = symbols.len()))]
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()> {
+        let cached_at = Utc::now().to_rfc3339();
+        let provider = provider.to_string();
+        let universe_id = universe_id.to_string();
+        let as_of_str = as_of.to_string();
+        let symbols_json =
+            serde_json::to_string(symbols).map_err(|e| DataError::Parse(e.to_string()))?;
+
+        self.with_conn(move |conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO universe_cache
+                 (provider, universe_id, as_of, symbols_json, cached_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .and_then(|mut stmt| {
+                stmt.execute(params![
                     provider,
-                    symbol_str,
-                    stmt.period_end.to_string(),
-                    period_type_str,
-                    stmt.fiscal_year,
-                    stmt.fiscal_quarter,
-                    data_json,
+                    universe_id,
+                    as_of_str,
+                    symbols_json,
                     cached_at
-                ],
-            )
+                ])
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        }
 
-        tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
-        debug!("Cached {} financial statements", statements.len());
-        Ok(())
+            debug!("Cached universe snapshot");
+            Ok(())
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv_as_of(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<OhlcvLookup> {
+        let key = key.clone();
+        let knowledge_time = knowledge_time.to_rfc3339();
+
+        self.with_conn(move |conn| {
+            let data = Self::ohlcv_rows(conn, &key, start, end, Some(&knowledge_time))?;
+            let coverage = Self::ohlcv_coverage(conn, &key, Some(&knowledge_time))?;
+            Ok(OhlcvLookup::from_coverage(data, &coverage, start, end))
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        let provider = provider.to_string();
+        let symbol_str = symbol.to_string();
+        let period_type_str = Self::period_type_to_str(period_type);
+        let knowledge_time = knowledge_time.to_rfc3339();
+
+        self.with_conn(move |conn| {
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT data_json, cached_at FROM financials_history h
+                     WHERE provider = ?1 AND symbol = ?2 AND period_type = ?3
+                       AND cached_at = (
+                           SELECT MAX(cached_at) FROM financials_history v
+                           WHERE v.provider = h.provider AND v.symbol = h.symbol
+                             AND v.period_end = h.period_end AND v.period_type = h.period_type
+                             AND v.cached_at <= ?4
+                       )
+                     ORDER BY period_end DESC",
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            let rows = stmt
+                .query_map(
+                    params![provider, symbol_str, period_type_str, knowledge_time],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            Self::collect_financials(rows)
+        })
+        .await
     }
 
     #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
-    async fn get_metrics(
+    async fn get_metrics_as_of(
         &self,
         provider: &str,
         symbol: &Symbol,
         date: NaiveDate,
-    ) -> Result<Option<KeyMetrics>> {
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
         let provider = provider.to_string();
         let symbol_str = symbol.to_string();
         let date_str = date.to_string();
-
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        let result = conn
-            .query_row(
-                "SELECT data_json FROM metrics_cache
-                 WHERE provider = ?1 AND symbol = ?2 AND date = ?3",
-                params![provider, symbol_str, date_str],
-                |row| row.get::<_, String>(0),
-            )
-            .optional()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        match result {
-            Some(json) => {
-                let metrics: KeyMetrics =
-                    serde_json::from_str(&json).map_err(|e| DataError::Parse(e.to_string()))?;
-                debug!("Found cached metrics");
-                Ok(Some(metrics))
-            }
-            None => {
-                debug!("No cached metrics found");
-                Ok(None)
-            }
-        }
+        let knowledge_time = knowledge_time.to_rfc3339();
+
+        self.with_conn(move |conn| {
+            let row = conn
+                .prepare_cached(
+                    "SELECT data_json, cached_at FROM metrics_history
+                     WHERE provider = ?1 AND symbol = ?2 AND date = ?3 AND cached_at <= ?4
+                     ORDER BY cached_at DESC
+                     LIMIT 1",
+                )
+                .and_then(|mut stmt| {
+                    stmt.query_row(
+                        params![provider, symbol_str, date_str, knowledge_time],
+                        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                    )
+                    .optional()
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            Self::parse_cached(row)
+        })
+        .await
     }
 
-    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
-    async fn put_metrics(
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info_as_of(
         &self,
         provider: &str,
         symbol: &Symbol,
-        metrics: &KeyMetrics,
-    ) -> Result<()> {
-        let cached_at = Utc::now().to_rfc3339();
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
         let provider = provider.to_string();
         let symbol_str = symbol.to_string();
-        let date_str = metrics.date.to_string();
-        let data_json =
-            serde_json::to_string(metrics).map_err(|e| DataError::Parse(e.to_string()))?;
+        let knowledge_time = knowledge_time.to_rfc3339();
+
+        self.with_conn(move |conn| {
+            let row = conn
+                .prepare_cached(
+                    "SELECT data_json, cached_at FROM company_info_history
+                     WHERE provider = ?1 AND symbol = ?2 AND cached_at <= ?3
+                     ORDER BY cached_at DESC
+                     LIMIT 1",
+                )
+                .and_then(|mut stmt| {
+                    stmt.query_row(params![provider, symbol_str, knowledge_time], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .optional()
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            Self::parse_cached(row)
+        })
+        .await
+    }
 
-        let conn = self
-            .conn
-            .lock()
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        let provider = key.provider.clone();
+        let symbol_str = key.symbol.to_string();
+        let request = key.request.clone().unwrap_or_default();
+
+        self.with_conn(move |conn| {
+            let row = conn
+                .prepare_cached(
+                    "SELECT message, cached_at FROM negative_cache
+                     WHERE provider = ?1 AND symbol = ?2 AND request = ?3",
+                )
+                .and_then(|mut stmt| {
+                    stmt.query_row(params![provider, symbol_str, request], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })
+                    .optional()
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+
+            row.map(|(message, cached_at)| {
+                Ok(Cached::new(message, Self::parse_cached_at(&cached_at)?))
+            })
+            .transpose()
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        let cached_at = Utc::now().to_rfc3339();
+        let provider = key.provider.clone();
+        let symbol_str = key.symbol.to_string();
+        let request = key.request.clone().unwrap_or_default();
+        let message = message.to_string();
+
+        self.with_conn(move |conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO negative_cache
+                 (provider, symbol, request, message, cached_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .and_then(|mut stmt| {
+                stmt.execute(params![provider, symbol_str, request, message, cached_at])
+            })
             .map_err(|e| DataError::Cache(e.to_string()))?;
 
-        conn.execute(
-            "INSERT OR REPLACE INTO metrics_cache
-             (provider, symbol, date, data_json, cached_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![provider, symbol_str, date_str, data_json, cached_at],
-        )
-        .map_err(|e| DataError::Cache(e.to_string()))?;
+            debug!("Cached negative entry");
+            Ok(())
+        })
+        .await
+    }
 
-        debug!("Cached metrics");
-        Ok(())
+    #[instrument(skip(self))]
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        self.with_conn(|conn| {
+            let mut keys = Vec::new();
+
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT DISTINCT provider, symbol, frequency, adjustment FROM ohlcv_coverage",
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            for row in rows {
+                let (provider, symbol, frequency, adjustment) =
+                    row.map_err(|e| DataError::Cache(e.to_string()))?;
+                keys.push(CacheKey::Ohlcv(
+                    OhlcvCacheKey::new(provider, &Symbol::new(symbol), frequency.parse()?)
+                        .with_adjustment(adjustment.parse()?),
+                ));
+            }
+
+            let mut stmt = conn
+                .prepare_cached(
+                    "SELECT DISTINCT provider, symbol, period_type FROM financials_cache",
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            for row in rows {
+                let (provider, symbol, period_type) =
+                    row.map_err(|e| DataError::Cache(e.to_string()))?;
+                keys.push(CacheKey::Financials {
+                    provider,
+                    symbol: Symbol::new(symbol),
+                    period_type: Self::str_to_period_type(&period_type)?,
+                });
+            }
+
+            let mut stmt = conn
+                .prepare_cached("SELECT provider, symbol, date FROM metrics_cache")
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                })
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            for row in rows {
+                let (provider, symbol, date) = row.map_err(|e| DataError::Cache(e.to_string()))?;
+                keys.push(CacheKey::Metrics {
+                    provider,
+                    symbol: Symbol::new(symbol),
+                    date: date
+                        .parse::<NaiveDate>()
+                        .map_err(|e| DataError::Parse(e.to_string()))?,
+                });
+            }
+
+            Ok(keys)
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn inspect(&self) -> Result<CacheStats> {
+        let entries = self
+            .with_conn(|conn| {
+                let mut entries = Vec::new();
+                for (kind, sql) in [
+                    (
+                        CacheDataKind::Ohlcv,
+                        "SELECT provider, symbol, frequency, adjustment, NULL, COUNT(*),
+                                SUM(LENGTH(date)) + 8 * (5 * COUNT(*) + COUNT(adjusted_close)),
+                                MIN(cached_at), MAX(cached_at)
+                         FROM ohlcv_cache GROUP BY provider, symbol, frequency, adjustment",
+                    ),
+                    (
+                        CacheDataKind::Ticks,
+                        "SELECT provider, symbol, NULL, NULL, NULL, COUNT(*),
+                                SUM(8 + LENGTH(data_json)), MIN(cached_at), MAX(cached_at)
+                         FROM tick_cache GROUP BY provider, symbol",
+                    ),
+                    (
+                        CacheDataKind::Financials,
+                        "SELECT provider, symbol, NULL, NULL, period_type, COUNT(*),
+                                SUM(LENGTH(data_json)), MIN(cached_at), MAX(cached_at)
+                         FROM financials_cache GROUP BY provider, symbol, period_type",
+                    ),
+                    (
+                        CacheDataKind::Metrics,
+                        "SELECT provider, symbol, NULL, NULL, NULL, COUNT(*),
+                                SUM(LENGTH(data_json)), MIN(cached_at), MAX(cached_at)
+                         FROM metrics_cache GROUP BY provider, symbol",
+                    ),
+                    (
+                        CacheDataKind::CompanyInfo,
+                        "SELECT provider, symbol, NULL, NULL, NULL, COUNT(*),
+                                SUM(LENGTH(data_json)), MIN(cached_at), MAX(cached_at)
+                         FROM company_info_cache GROUP BY provider, symbol",
+                    ),
+                    (
+                        CacheDataKind::Universe,
+                        "SELECT provider, universe_id, NULL, NULL, NULL, COUNT(*),
+                                SUM(LENGTH(symbols_json)), MIN(cached_at), MAX(cached_at)
+                         FROM universe_cache GROUP BY provider, universe_id",
+                    ),
+                ] {
+                    entries.extend(Self::table_stats(conn, kind, sql)?);
+                }
+                Self::add_coverage(conn, &mut entries)?;
+                Ok(entries)
+            })
+            .await?;
+        Ok(CacheStats::new(
+            entries,
+            self.lookups.hits(),
+            self.lookups.misses(),
+        ))
     }
 
     #[instrument(skip(self))]
@@ -516,63 +2144,128 @@ impl DataCache for SqliteCache {
                 .map_err(|e| DataError::Cache(format!("Invalid TTL duration: {}", e)))?;
         let cutoff_str = cutoff.to_rfc3339();
 
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
+        self.with_conn(move |conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
 
-        let mut total_deleted = 0usize;
+            let mut total_deleted = 0usize;
 
-        // Delete stale OHLCV data
-        let deleted = conn
-            .execute(
-                "DELETE FROM ohlcv_cache WHERE cached_at < ?1",
-                params![cutoff_str],
-            )
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-        total_deleted += deleted;
+            // Delete stale OHLCV data
+            let deleted = tx
+                .execute(
+                    "DELETE FROM ohlcv_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
 
-        // Delete stale financials
-        let deleted = conn
-            .execute(
-                "DELETE FROM financials_cache WHERE cached_at < ?1",
+            tx.execute(
+                "DELETE FROM ohlcv_coverage WHERE cached_at < ?1",
                 params![cutoff_str],
             )
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        total_deleted += deleted;
 
-        // Delete stale metrics
-        let deleted = conn
-            .execute(
-                "DELETE FROM metrics_cache WHERE cached_at < ?1",
+            // Delete stale financials
+            let deleted = tx
+                .execute(
+                    "DELETE FROM financials_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            // Delete stale metrics
+            let deleted = tx
+                .execute(
+                    "DELETE FROM metrics_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            // Delete stale ticks
+            let deleted = tx
+                .execute(
+                    "DELETE FROM tick_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            tx.execute(
+                "DELETE FROM tick_coverage WHERE cached_at < ?1",
                 params![cutoff_str],
             )
             .map_err(|e| DataError::Cache(e.to_string()))?;
-        total_deleted += deleted;
 
-        if total_deleted > 0 {
-            debug!("Invalidated {} stale cache entries", total_deleted);
-        }
+            // Delete stale company info
+            let deleted = tx
+                .execute(
+                    "DELETE FROM company_info_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            // Delete stale universe snapshots
+            let deleted = tx
+                .execute(
+                    "DELETE FROM universe_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            // Delete stale negative entries
+            let deleted = tx
+                .execute(
+                    "DELETE FROM negative_cache WHERE cached_at < ?1",
+                    params![cutoff_str],
+                )
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            total_deleted += deleted;
+
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
+
+            if total_deleted > 0 {
+                debug!("Invalidated {} stale cache entries", total_deleted);
+            }
 
-        Ok(total_deleted)
+            Ok(total_deleted)
+        })
+        .await
     }
 
     #[instrument(skip(self))]
     async fn clear(&self) -> Result<()> {
-        let conn = self
-            .conn
-            .lock()
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-
-        conn.execute("DELETE FROM ohlcv_cache", [])
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-        conn.execute("DELETE FROM financials_cache", [])
-            .map_err(|e| DataError::Cache(e.to_string()))?;
-        conn.execute("DELETE FROM metrics_cache", [])
+        self.with_conn(|conn| {
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .map_err(|e| DataError::Cache(e.to_string()))?;
+            tx.execute_batch(
+                "DELETE FROM ohlcv_cache;
+                 DELETE FROM ohlcv_coverage;
+                 DELETE FROM financials_cache;
+                 DELETE FROM metrics_cache;
+                 DELETE FROM tick_cache;
+                 DELETE FROM tick_coverage;
+                 DELETE FROM company_info_cache;
+                 DELETE FROM universe_cache;
+                 DELETE FROM ohlcv_history;
+                 DELETE FROM ohlcv_coverage_history;
+                 DELETE FROM financials_history;
+                 DELETE FROM metrics_history;
+                 DELETE FROM company_info_history;
+                 DELETE FROM negative_cache;",
+            )
             .map_err(|e| DataError::Cache(e.to_string()))?;
+            tx.commit().map_err(|e| DataError::Cache(e.to_string()))?;
 
-        debug!("Cleared all cache entries");
-        Ok(())
+            debug!("Cleared all cache entries");
+            Ok(())
+        })
+        .await
     }
 }
 
@@ -580,6 +2273,7 @@ impl DataCache for SqliteCache {
 mod tests {
     use super::*;
     use chrono::NaiveDate;
+    use data_core::{DataFrequency, PriceAdjustment};
 
     #[tokio::test]
     async fn test_sqlite_cache_initialization() {
@@ -594,9 +2288,11 @@ mod tests {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
         let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
 
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+
         // Initially no data
-        let result = cache.get_ohlcv("test", &symbol, start, end).await.unwrap();
-        assert!(result.is_none());
+        let result = cache.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(result.is_miss());
 
         // Create test DataFrame
         let df = DataFrame::new(vec![
@@ -615,13 +2311,210 @@ mod tests {
         .unwrap();
 
         // Store data
-        cache.put_ohlcv("test", &symbol, &df).await.unwrap();
+        cache.put_ohlcv(&key, start, end, &df).await.unwrap();
 
         // Retrieve data
-        let result = cache.get_ohlcv("test", &symbol, start, end).await.unwrap();
-        assert!(result.is_some());
-        let retrieved = result.unwrap();
+        let result = cache.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(result.is_complete());
+        let retrieved = result.data.unwrap();
         assert_eq!(retrieved.height(), 2);
+
+        // A wider request reports the uncovered dates
+        let later = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let result = cache.get_ohlcv(&key, start, later).await.unwrap();
+        assert_eq!(result.data.unwrap().height(), 2);
+        assert_eq!(
+            result.missing,
+            vec![DateRange::new(
+                NaiveDate::from_ymd_opt(2024, 1, 6).unwrap(),
+                later
+            )]
+        );
+
+        // Recording an empty range closes the gap
+        let gap_start = NaiveDate::from_ymd_opt(2024, 1, 6).unwrap();
+        cache
+            .put_ohlcv(&key, gap_start, later, &DataFrame::empty())
+            .await
+            .unwrap();
+        let result = cache.get_ohlcv(&key, start, later).await.unwrap();
+        assert!(result.is_complete());
+
+        // Other frequencies and adjustment modes are separate series
+        let hourly = OhlcvCacheKey::new("test", &symbol, DataFrequency::Hourly);
+        assert!(
+            cache
+                .get_ohlcv(&hourly, start, end)
+                .await
+                .unwrap()
+                .is_miss()
+        );
+        let adjusted = key.clone().with_adjustment(PriceAdjustment::Adjusted);
+        assert!(
+            cache
+                .get_ohlcv(&adjusted, start, end)
+                .await
+                .unwrap()
+                .is_miss()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adjusted_series_is_not_served_unadjusted() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let raw = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let adjusted = raw.clone().with_adjustment(PriceAdjustment::Adjusted);
+
+        let df = DataFrame::new(vec![
+            Column::new("symbol".into(), vec!["AAPL"]),
+            Column::new("date".into(), vec!["2024-01-02"]),
+            Column::new("open".into(), vec![75.0]),
+            Column::new("high".into(), vec![76.0]),
+            Column::new("low".into(), vec![74.5]),
+            Column::new("close".into(), vec![75.5]),
+            Column::new("volume".into(), vec![2000000.0]),
+        ])
+        .unwrap();
+        cache.put_ohlcv(&adjusted, date, date, &df).await.unwrap();
+
+        assert!(cache.get_ohlcv(&raw, date, date).await.unwrap().is_miss());
+        let result = cache.get_ohlcv(&adjusted, date, date).await.unwrap();
+        assert!(result.is_complete());
+        assert_eq!(result.data.unwrap().height(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_migrates_unkeyed_ohlcv_table() {
+        let path =
+            std::env::temp_dir().join(format!("data-cache-migration-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE ohlcv_cache (
+                    provider TEXT NOT NULL,
+                    symbol TEXT NOT NULL,
+                    date TEXT NOT NULL,
+                    open REAL NOT NULL,
+                    high REAL NOT NULL,
+                    low REAL NOT NULL,
+                    close REAL NOT NULL,
+                    volume REAL NOT NULL,
+                    adjusted_close REAL,
+                    cached_at TEXT NOT NULL,
+                    PRIMARY KEY (provider, symbol, date)
+                );
+                INSERT INTO ohlcv_cache VALUES
+                    ('test', 'AAPL', '2024-01-02', 150, 152, 149, 151, 1000000, 151, '2024-01-02'),
+                    ('test', 'AAPL', '2024-01-05', 151, 153, 150, 152, 1000000, 152, '2024-01-05');",
+            )
+            .unwrap();
+        }
+
+        let cache = SqliteCache::new(&path).unwrap();
+        let symbol = Symbol::new("AAPL");
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+
+        let daily = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let result = cache.get_ohlcv(&daily, start, end).await.unwrap();
+        assert_eq!(result.data.unwrap().height(), 2);
+        // The series has a hole on 2024-01-03 and 2024-01-04, so none of it
+        // counts as covered until it is refetched
+        assert_eq!(result.missing, vec![DateRange::new(start, end)]);
+        let hourly = OhlcvCacheKey::new("test", &symbol, DataFrequency::Hourly);
+        assert!(
+            cache
+                .get_ohlcv(&hourly, start, end)
+                .await
+                .unwrap()
+                .is_miss()
+        );
+
+        drop(cache);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_access_with_wal() {
+        let path = std::env::temp_dir().join(format!("data-cache-pool-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let cache = SqliteCache::with_pool_size(&path, 4).unwrap();
+        assert_eq!(cache.pool_size(), 4);
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let tasks = (0..32)
+            .map(|i| {
+                let cache = cache.clone();
+                tokio::spawn(async move {
+                    let symbol = Symbol::new(format!("SYM{i}"));
+                    let metrics = KeyMetrics::new(symbol.clone(), date);
+                    cache.put_metrics("test", &symbol, &metrics).await.unwrap();
+                    cache.get_metrics("test", &symbol, date).await.unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            assert!(task.await.unwrap().is_some());
+        }
+
+        let conn = Connection::open(&path).unwrap();
+        let mode: String = conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+
+        drop(conn);
+        drop(cache);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_version_check() {
+        let path =
+            std::env::temp_dir().join(format!("data-cache-version-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let cache = SqliteCache::new(&path).unwrap();
+        let symbol = Symbol::new("AAPL");
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let metrics = KeyMetrics::new(symbol.clone(), date);
+        cache.put_metrics("test", &symbol, &metrics).await.unwrap();
+        drop(cache);
+
+        let version = |path: &std::path::Path| -> u32 {
+            Connection::open(path)
+                .unwrap()
+                .pragma_query_value(None, "user_version", |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(version(&path), SqliteCache::SCHEMA_VERSION);
+
+        // Reopening an up-to-date file keeps its data
+        let cache = SqliteCache::new(&path).unwrap();
+        assert!(
+            cache
+                .get_metrics("test", &symbol, date)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        drop(cache);
+
+        Connection::open(&path)
+            .unwrap()
+            .pragma_update(None, "user_version", SqliteCache::SCHEMA_VERSION + 1)
+            .unwrap();
+        let err = SqliteCache::new(&path).unwrap_err();
+        assert!(err.to_string().contains("newer than the supported version"));
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
     }
 
     #[tokio::test]
@@ -661,8 +2554,161 @@ mod tests {
             .unwrap();
         assert!(result.is_some());
         let retrieved = result.unwrap();
-        assert_eq!(retrieved.len(), 1);
-        assert_eq!(retrieved[0].fiscal_year, Some(2024));
+        assert_eq!(retrieved.value.len(), 1);
+        assert_eq!(retrieved.value[0].fiscal_year, Some(2024));
+    }
+
+    #[tokio::test]
+    async fn test_financials_keep_per_statement_times() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let statement = |month| {
+            FinancialStatement::new(
+                symbol.clone(),
+                NaiveDate::from_ymd_opt(2024, month, 30).unwrap(),
+                PeriodType::Quarterly,
+            )
+        };
+        let older = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let newer = DateTime::from_timestamp(1_700_100_000, 0).unwrap();
+        let statements = [
+            Cached::new(statement(6), newer),
+            Cached::new(statement(4), older),
+        ];
+
+        cache
+            .put_financials_at("test", &symbol, &statements)
+            .await
+            .unwrap();
+
+        let entries = cache
+            .get_financials_entries("test", &symbol, PeriodType::Quarterly)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entries, statements);
+        let cached = cache
+            .get_financials("test", &symbol, PeriodType::Quarterly)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.cached_at, older);
+    }
+
+    #[tokio::test]
+    async fn test_bitemporal_reads() {
+        let cache = SqliteCache::in_memory().unwrap().with_bitemporal(true);
+        let symbol = Symbol::new("AAPL");
+        let period_end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let statement = |revenue: f64| FinancialStatement {
+            symbol: symbol.clone(),
+            period_end,
+            period_type: PeriodType::Quarterly,
+            revenue: Some(revenue),
+            ..Default::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let bars = |close: f64| {
+            DataFrame::new(vec![
+                Column::new("symbol".into(), vec!["AAPL"]),
+                Column::new("date".into(), vec!["2024-01-02"]),
+                Column::new("open".into(), vec![close]),
+                Column::new("high".into(), vec![close]),
+                Column::new("low".into(), vec![close]),
+                Column::new("close".into(), vec![close]),
+                Column::new("volume".into(), vec![1000.0]),
+                Column::new("adjusted_close".into(), vec![Some(close)]),
+            ])
+            .unwrap()
+        };
+        let close_of = |lookup: OhlcvLookup| {
+            lookup
+                .data
+                .unwrap()
+                .column("close")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .get(0)
+        };
+
+        let before = Utc::now();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        cache
+            .put_financials("test", &symbol, &[statement(90.0)])
+            .await
+            .unwrap();
+        cache
+            .put_ohlcv(&key, start, end, &bars(150.0))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        let first_run = Utc::now();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+
+        // The provider restates revenue and revises the close
+        cache
+            .put_financials("test", &symbol, &[statement(95.0)])
+            .await
+            .unwrap();
+        cache
+            .put_ohlcv(&key, start, end, &bars(149.0))
+            .await
+            .unwrap();
+
+        let latest = cache
+            .get_financials("test", &symbol, PeriodType::Quarterly)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.value[0].revenue, Some(95.0));
+
+        let as_first_seen = cache
+            .get_financials_as_of("test", &symbol, PeriodType::Quarterly, first_run)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(as_first_seen.value.len(), 1);
+        assert_eq!(as_first_seen.value[0].revenue, Some(90.0));
+        assert!(as_first_seen.cached_at <= first_run);
+        assert!(
+            cache
+                .get_financials_as_of("test", &symbol, PeriodType::Quarterly, before)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let lookup = cache
+            .get_ohlcv_as_of(&key, start, end, first_run)
+            .await
+            .unwrap();
+        assert!(lookup.is_complete());
+        assert_eq!(close_of(lookup), Some(150.0));
+        let lookup = cache
+            .get_ohlcv_as_of(&key, start, end, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(close_of(lookup), Some(149.0));
+        assert!(
+            cache
+                .get_ohlcv_as_of(&key, start, end, before)
+                .await
+                .unwrap()
+                .is_miss()
+        );
+
+        // History outlives stale-entry invalidation
+        cache.invalidate_stale(Duration::ZERO).await.unwrap();
+        assert!(
+            cache
+                .get_financials_as_of("test", &symbol, PeriodType::Quarterly, first_run)
+                .await
+                .unwrap()
+                .is_some()
+        );
     }
 
     #[tokio::test]
@@ -691,7 +2737,225 @@ mod tests {
         let result = cache.get_metrics("test", &symbol, date).await.unwrap();
         assert!(result.is_some());
         let retrieved = result.unwrap();
-        assert_eq!(retrieved.market_cap, Some(3_000_000_000_000.0));
+        assert_eq!(retrieved.value.market_cap, Some(3_000_000_000_000.0));
+    }
+
+    #[tokio::test]
+    async fn test_keys_lists_cached_entries() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Hourly)
+            .with_adjustment(PriceAdjustment::SplitAdjusted);
+        cache
+            .put_ohlcv(&key, date, date, &DataFrame::empty())
+            .await
+            .unwrap();
+        let metrics = KeyMetrics {
+            symbol: symbol.clone(),
+            date,
+            ..Default::default()
+        };
+        cache.put_metrics("test", &symbol, &metrics).await.unwrap();
+
+        let keys = cache.keys().await.unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&CacheKey::Ohlcv(key)));
+        assert!(keys.contains(&CacheKey::Metrics {
+            provider: "test".to_string(),
+            symbol,
+            date,
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_negative_entries_round_trip() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("GONE");
+        let symbol_key = NegativeKey::symbol("test", &symbol);
+        let request_key = NegativeKey::request("test", &symbol, "ohlcv:daily");
+        assert!(cache.get_negative(&symbol_key).await.unwrap().is_none());
+
+        cache
+            .put_negative(&symbol_key, "Symbol not found: GONE")
+            .await
+            .unwrap();
+        let cached = cache.get_negative(&symbol_key).await.unwrap().unwrap();
+        assert_eq!(cached.value, "Symbol not found: GONE");
+        // Symbol-level and request-level entries are kept apart
+        assert!(cache.get_negative(&request_key).await.unwrap().is_none());
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert_eq!(cache.invalidate_stale(Duration::ZERO).await.unwrap(), 1);
+        assert!(cache.get_negative(&symbol_key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inspect_reports_contents_and_lookups() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let df = DataFrame::new(vec![
+            Column::new("symbol".into(), vec!["AAPL"]),
+            Column::new("date".into(), vec!["2024-01-02"]),
+            Column::new("open".into(), vec![150.0]),
+            Column::new("high".into(), vec![152.0]),
+            Column::new("low".into(), vec![149.0]),
+            Column::new("close".into(), vec![151.0]),
+            Column::new("volume".into(), vec![1000000.0]),
+        ])
+        .unwrap();
+        cache.put_ohlcv(&key, start, end, &df).await.unwrap();
+        cache
+            .put_ohlcv(
+                &key,
+                end.succ_opt().unwrap(),
+                end + chrono::Days::new(3),
+                &DataFrame::empty(),
+            )
+            .await
+            .unwrap();
+        let metrics = KeyMetrics {
+            symbol: symbol.clone(),
+            date: start,
+            ..Default::default()
+        };
+        cache.put_metrics("test", &symbol, &metrics).await.unwrap();
+
+        cache.get_ohlcv(&key, start, end).await.unwrap();
+        cache
+            .get_metrics("test", &Symbol::new("MSFT"), start)
+            .await
+            .unwrap();
+
+        let stats = cache.inspect().await.unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.entries.len(), 2);
+
+        let ohlcv = &stats.entries[0];
+        assert_eq!(ohlcv.kind, CacheDataKind::Ohlcv);
+        assert_eq!(ohlcv.frequency, Some(DataFrequency::Daily));
+        assert_eq!(ohlcv.rows, 1);
+        assert_eq!(
+            ohlcv.covered,
+            vec![DateRange::new(start, end + chrono::Days::new(3))]
+        );
+
+        let metrics = &stats.entries[1];
+        assert_eq!(metrics.kind, CacheDataKind::Metrics);
+        assert_eq!(metrics.id, "AAPL");
+        assert!(metrics.bytes > 0);
+        assert!(stats.oldest_cached_at() <= stats.newest_cached_at());
+    }
+
+    #[tokio::test]
+    async fn test_ticks_cache() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let start = DateTime::parse_from_rfc3339("2024-01-02T14:30:00Z")
+            .unwrap()
+            .to_utc();
+        let end = start + chrono::Duration::hours(1);
+        let ticks = vec![
+            Tick::new(symbol.clone(), start, 150.0, 100.0).with_exchange("NASDAQ"),
+            Tick::new(symbol.clone(), start, 150.5, 50.0),
+            Tick::new(symbol.clone(), end, 151.0, 200.0),
+        ];
+
+        assert!(
+            cache
+                .get_ticks("test", &symbol, start, end)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cache
+            .put_ticks("test", &symbol, start, end, &ticks)
+            .await
+            .unwrap();
+        let cached = cache
+            .get_ticks("test", &symbol, start, end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.value, ticks);
+
+        // Re-storing the range replaces rather than duplicates its ticks
+        cache
+            .put_ticks("test", &symbol, start, end, &ticks[..1])
+            .await
+            .unwrap();
+        let cached = cache
+            .get_ticks("test", &symbol, start, end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.value, ticks[..1]);
+
+        let later = cache
+            .get_ticks("test", &symbol, start, end + chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        assert!(later.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reference_data_cache() {
+        let cache = SqliteCache::in_memory().unwrap();
+        let symbol = Symbol::new("AAPL");
+        let mut info = CompanyInfo::new(
+            symbol.clone(),
+            "Apple Inc.",
+            "NASDAQ",
+            "Technology",
+            "Consumer Electronics",
+            "US",
+            "USD",
+        );
+        info.cik = Some("0000320193".to_string());
+
+        cache
+            .put_company_info("test", &symbol, &info)
+            .await
+            .unwrap();
+        let cached = cache.get_company_info("test", &symbol).await.unwrap();
+        assert_eq!(cached.map(|c| c.value), Some(info));
+        assert!(
+            cache
+                .get_company_info("other", &symbol)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let jan = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let feb = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let members = [Symbol::new("AAPL"), Symbol::new("MSFT")];
+        cache
+            .put_universe("test", "sp500", jan, &members[..1])
+            .await
+            .unwrap();
+        cache
+            .put_universe("test", "sp500", feb, &members)
+            .await
+            .unwrap();
+
+        let mid_jan = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let snapshot = cache.get_universe("test", "sp500", mid_jan).await.unwrap();
+        assert_eq!(snapshot.map(|c| c.value).as_deref(), Some(&members[..1]));
+        let snapshot = cache.get_universe("test", "sp500", feb).await.unwrap();
+        assert_eq!(snapshot.map(|c| c.value).as_deref(), Some(&members[..]));
+        let before = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        assert!(
+            cache
+                .get_universe("test", "sp500", before)
+                .await
+                .unwrap()
+                .is_none()
+        );
     }
 
     #[tokio::test]
@@ -0,0 +1,890 @@
+//! Tiered cache implementation.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{
+    CacheKey, Cached, CompanyInfo, CoveredRange, DataCache, DataError, FinancialStatement,
+    KeyMetrics, NegativeKey, OhlcvCacheKey, OhlcvLookup, PeriodType, Result, Symbol, Tick,
+};
+use polars::prelude::DataFrame;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, instrument, warn};
+
+use crate::memory::filter_dates;
+
+/// How a [`TieredCache`] propagates writes to its lower tiers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Write to every tier before returning.
+    #[default]
+    WriteThrough,
+    /// Write to the top tier before returning and to lower tiers in the
+    /// background, in the order the writes were made. Use
+    /// [`TieredCache::flush`] to wait for pending writes.
+    WriteBack,
+}
+
+/// A cache that layers several [`DataCache`] implementations.
+///
+/// Tiers are ordered fastest first, e.g. a
+/// [`BoundedMemoryCache`](crate::BoundedMemoryCache) in front of a
+/// [`SqliteCache`](crate::SqliteCache) or [`ParquetCache`](crate::ParquetCache).
+/// Reads try each tier in order, and a hit in a lower tier is copied into the
+/// tiers above it. Promoted OHLCV series, financial statements and key metrics
+/// keep the time they were first stored, so they do not look fresher in the
+/// upper tiers than they are; other data is timestamped when it is promoted.
+/// Writes go to every tier
+/// according to the [`WriteMode`], and [`clear`](DataCache::clear) and
+/// [`invalidate_stale`](DataCache::invalidate_stale) apply to every tier.
+///
+/// A tier that fails to read is treated as a miss. Bitemporal reads such as
+/// [`get_financials_as_of`](DataCache::get_financials_as_of) are answered by
+/// the first tier that keeps history, and [`keys`](DataCache::keys) lists
+/// the entries of every tier that can list its contents.
+///
+/// ```rust,ignore
+/// use std::sync::Arc;
+/// use data_cache::{BoundedMemoryCache, SqliteCache, TieredCache};
+///
+/// let cache = TieredCache::new()
+///     .with_tier(Arc::new(BoundedMemoryCache::new(256 * 1024 * 1024)))
+///     .with_tier(Arc::new(SqliteCache::new("cache.db")?));
+/// ```
+#[derive(Default)]
+pub struct TieredCache {
+    tiers: Vec<Arc<dyn DataCache>>,
+    write_mode: WriteMode,
+    /// The most recent background write, which waits for the one before it.
+    pending: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl std::fmt::Debug for TieredCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TieredCache")
+            .field("tiers", &self.tiers.len())
+            .field("write_mode", &self.write_mode)
+            .finish()
+    }
+}
+
+impl TieredCache {
+    /// Create a tiered cache with no tiers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tier below the existing ones.
+    #[must_use]
+    pub fn with_tier(mut self, tier: Arc<dyn DataCache>) -> Self {
+        self.tiers.push(tier);
+        self
+    }
+
+    /// Set how writes reach the lower tiers.
+    #[must_use]
+    pub const fn with_write_mode(mut self, write_mode: WriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+
+    /// Returns the number of tiers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tiers.len()
+    }
+
+    /// Returns true if the cache has no tiers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+
+    /// Wait for background writes to the lower tiers to finish.
+    pub async fn flush(&self) {
+        let pending = self.pending.lock().await.take();
+        if let Some(handle) = pending {
+            if let Err(e) = handle.await {
+                warn!(error = %e, "Background cache write panicked");
+            }
+        }
+    }
+
+    /// Apply a write to every tier according to the write mode.
+    ///
+    /// In write-through mode every tier is attempted and the first error is
+    /// returned. In write-back mode only the top tier's error is returned;
+    /// lower-tier failures are logged.
+    async fn write<F, Fut>(&self, op: F) -> Result<()>
+    where
+        F: Fn(Arc<dyn DataCache>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let Some((top, lower)) = self.tiers.split_first() else {
+            return Ok(());
+        };
+
+        let runtime = tokio::runtime::Handle::try_current().ok();
+        match (self.write_mode, runtime) {
+            (WriteMode::WriteBack, Some(runtime)) if !lower.is_empty() => {
+                // Holding the lock across the top-tier write queues background
+                // writes in the same order the top tier saw them
+                let mut pending = self.pending.lock().await;
+                op(Arc::clone(top)).await?;
+                let previous = pending.take();
+                let lower = lower.to_vec();
+                *pending = Some(runtime.spawn(async move {
+                    // Each write waits for the previous one, so a lower tier
+                    // never ends up holding an older value than the top tier
+                    if let Some(previous) = previous {
+                        if let Err(e) = previous.await {
+                            warn!(error = %e, "Background cache write panicked");
+                        }
+                    }
+                    for (i, tier) in lower.into_iter().enumerate() {
+                        if let Err(e) = op(tier).await {
+                            warn!(tier = i + 1, error = %e, "Background cache write failed");
+                        }
+                    }
+                }));
+                Ok(())
+            }
+            _ => {
+                let mut first_error = None;
+                for (i, tier) in self.tiers.iter().enumerate() {
+                    if let Err(e) = op(Arc::clone(tier)).await {
+                        warn!(tier = i, error = %e, "Cache write failed");
+                        first_error.get_or_insert(e);
+                    }
+                }
+                first_error.map_or(Ok(()), Err)
+            }
+        }
+    }
+
+    /// Answer a bitemporal read from the first tier that keeps history.
+    ///
+    /// History is never promoted, since upper tiers only hold current values.
+    async fn read_history<'a, T, F, Fut>(&'a self, read: F) -> Result<T>
+    where
+        F: Fn(&'a Arc<dyn DataCache>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut first_error = None;
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match read(tier).await {
+                Err(DataError::NotSupported(_)) => continue,
+                Err(e) => {
+                    read_failed(i, &e);
+                    first_error.get_or_insert(e);
+                }
+                result => return result,
+            }
+        }
+        Err(first_error
+            .unwrap_or_else(|| DataError::NotSupported("no cache tier keeps history".to_string())))
+    }
+}
+
+/// Log a failed read from a tier, which is then treated as a miss.
+fn read_failed(tier: usize, error: &data_core::DataError) {
+    warn!(tier, error = %error, "Cache tier read failed");
+}
+
+/// Log a failed promotion into an upper tier.
+fn promote_failed(tier: usize, error: &data_core::DataError) {
+    warn!(tier, error = %error, "Failed to promote cache entry");
+}
+
+/// Number of days in a lookup's missing ranges.
+fn missing_days(lookup: &OhlcvLookup) -> i64 {
+    lookup
+        .missing
+        .iter()
+        .map(|r| (r.end - r.start).num_days() + 1)
+        .sum()
+}
+
+#[async_trait]
+impl DataCache for TieredCache {
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<OhlcvLookup> {
+        // Use the first complete lookup, or else the one missing the fewest days
+        let mut best: Option<(usize, OhlcvLookup)> = None;
+        for (i, tier) in self.tiers.iter().enumerate() {
+            let lookup = match tier.get_ohlcv(key, start, end).await {
+                Ok(lookup) => lookup,
+                Err(e) => {
+                    read_failed(i, &e);
+                    continue;
+                }
+            };
+            let complete = lookup.is_complete();
+            if best
+                .as_ref()
+                .is_none_or(|(_, b)| missing_days(&lookup) < missing_days(b))
+            {
+                best = Some((i, lookup));
+            }
+            if complete {
+                break;
+            }
+        }
+
+        let Some((hit_tier, lookup)) = best else {
+            return Ok(OhlcvLookup::miss(start, end));
+        };
+        if hit_tier > 0 && !lookup.covered.is_empty() {
+            debug!(tier = hit_tier, "Promoting OHLCV data");
+            let data = lookup.data.clone().unwrap_or_default();
+            for covered in &lookup.covered {
+                let rows = filter_dates(&data, covered.range.start, covered.range.end)?;
+                for (i, upper) in self.tiers[..hit_tier].iter().enumerate() {
+                    if let Err(e) = upper.put_ohlcv_at(key, *covered, &rows).await {
+                        promote_failed(i, &e);
+                    }
+                }
+            }
+        }
+        Ok(lookup)
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let (key, data) = (key.clone(), data.clone());
+        self.write(move |tier| {
+            let (key, data) = (key.clone(), data.clone());
+            async move { tier.put_ohlcv(&key, start, end, &data).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self, data), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        let (key, data) = (key.clone(), data.clone());
+        self.write(move |tier| {
+            let (key, data) = (key.clone(), data.clone());
+            async move { tier.put_ohlcv_at(&key, covered, &data).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_ticks(provider, symbol, start, end).await {
+                Ok(Some(cached)) => {
+                    for (j, upper) in self.tiers[..i].iter().enumerate() {
+                        if let Err(e) = upper
+                            .put_ticks(provider, symbol, start, end, &cached.value)
+                            .await
+                        {
+                            promote_failed(j, &e);
+                        }
+                    }
+                    return Ok(Some(cached));
+                }
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, ticks), fields(provider = %provider, symbol = %symbol, count = ticks.len()))]
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        let (provider, symbol, ticks) = (provider.to_string(), symbol.clone(), ticks.to_vec());
+        self.write(move |tier| {
+            let (provider, symbol, ticks) = (provider.clone(), symbol.clone(), ticks.clone());
+            async move { tier.put_ticks(&provider, &symbol, start, end, &ticks).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_financials(provider, symbol, period_type).await {
+                Ok(Some(cached)) => {
+                    if i > 0 {
+                        let entries = match tier
+                            .get_financials_entries(provider, symbol, period_type)
+                            .await
+                        {
+                            Ok(Some(entries)) => entries,
+                            _ => cached
+                                .value
+                                .iter()
+                                .map(|s| Cached::new(s.clone(), cached.cached_at))
+                                .collect(),
+                        };
+                        for (j, upper) in self.tiers[..i].iter().enumerate() {
+                            if let Err(e) =
+                                upper.put_financials_at(provider, symbol, &entries).await
+                            {
+                                promote_failed(j, &e);
+                            }
+                        }
+                    }
+                    return Ok(Some(cached));
+                }
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[FinancialStatement],
+    ) -> Result<()> {
+        let (provider, symbol, statements) =
+            (provider.to_string(), symbol.clone(), statements.to_vec());
+        self.write(move |tier| {
+            let (provider, symbol, statements) =
+                (provider.clone(), symbol.clone(), statements.clone());
+            async move { tier.put_financials(&provider, &symbol, &statements).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials_entries(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Vec<Cached<FinancialStatement>>>> {
+        // Answered by the first tier holding the statements, without promotion
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier
+                .get_financials_entries(provider, symbol, period_type)
+                .await
+            {
+                Ok(Some(entries)) => return Ok(Some(entries)),
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, statements), fields(provider = %provider, symbol = %symbol, count = statements.len()))]
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
+        let (provider, symbol, statements) =
+            (provider.to_string(), symbol.clone(), statements.to_vec());
+        self.write(move |tier| {
+            let (provider, symbol, statements) =
+                (provider.clone(), symbol.clone(), statements.clone());
+            async move {
+                tier.put_financials_at(&provider, &symbol, &statements)
+                    .await
+            }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_metrics(provider, symbol, date).await {
+                Ok(Some(cached)) => {
+                    for (j, upper) in self.tiers[..i].iter().enumerate() {
+                        if let Err(e) = upper.put_metrics_at(provider, symbol, &cached).await {
+                            promote_failed(j, &e);
+                        }
+                    }
+                    return Ok(Some(cached));
+                }
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &KeyMetrics,
+    ) -> Result<()> {
+        let (provider, symbol, metrics) = (provider.to_string(), symbol.clone(), metrics.clone());
+        self.write(move |tier| {
+            let (provider, symbol, metrics) = (provider.clone(), symbol.clone(), metrics.clone());
+            async move { tier.put_metrics(&provider, &symbol, &metrics).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self, metrics), fields(provider = %provider, symbol = %symbol))]
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
+    ) -> Result<()> {
+        let (provider, symbol, metrics) = (provider.to_string(), symbol.clone(), metrics.clone());
+        self.write(move |tier| {
+            let (provider, symbol, metrics) = (provider.clone(), symbol.clone(), metrics.clone());
+            async move { tier.put_metrics_at(&provider, &symbol, &metrics).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_company_info(provider, symbol).await {
+                Ok(Some(cached)) => {
+                    for (j, upper) in self.tiers[..i].iter().enumerate() {
+                        if let Err(e) = upper
+                            .put_company_info(provider, symbol, &cached.value)
+                            .await
+                        {
+                            promote_failed(j, &e);
+                        }
+                    }
+                    return Ok(Some(cached));
+                }
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, info), fields(provider = %provider, symbol = %symbol))]
+    async fn put_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+    ) -> Result<()> {
+        let (provider, symbol, info) = (provider.to_string(), symbol.clone(), info.clone());
+        self.write(move |tier| {
+            let (provider, symbol, info) = (provider.clone(), symbol.clone(), info.clone());
+            async move { tier.put_company_info(&provider, &symbol, &info).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, universe = %universe_id))]
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_universe(provider, universe_id, as_of).await {
+                Ok(Some(cached)) => {
+                    for (j, upper) in self.tiers[..i].iter().enumerate() {
+                        if let Err(e) = upper
+                            .put_universe(provider, universe_id, as_of, &cached.value)
+                            .await
+                        {
+                            promote_failed(j, &e);
+                        }
+                    }
+                    return Ok(Some(cached));
+                }
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, symbols), fields(provider = %provider, universe = %universe_id, count = symbols.len()))]
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()> {
+        let (provider, universe_id, symbols) = (
+            provider.to_string(),
+            universe_id.to_string(),
+            symbols.to_vec(),
+        );
+        self.write(move |tier| {
+            let (provider, universe_id, symbols) =
+                (provider.clone(), universe_id.clone(), symbols.clone());
+            async move {
+                tier.put_universe(&provider, &universe_id, as_of, &symbols)
+                    .await
+            }
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol, frequency = ?key.frequency))]
+    async fn get_ohlcv_as_of(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<OhlcvLookup> {
+        self.read_history(|tier| tier.get_ohlcv_as_of(key, start, end, knowledge_time))
+            .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_financials_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        self.read_history(|tier| {
+            tier.get_financials_as_of(provider, symbol, period_type, knowledge_time)
+        })
+        .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_metrics_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        self.read_history(|tier| tier.get_metrics_as_of(provider, symbol, date, knowledge_time))
+            .await
+    }
+
+    #[instrument(skip(self), fields(provider = %provider, symbol = %symbol))]
+    async fn get_company_info_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        self.read_history(|tier| tier.get_company_info_as_of(provider, symbol, knowledge_time))
+            .await
+    }
+
+    /// Negative entries are not promoted, since rewriting them would restart
+    /// their TTL in the upper tiers.
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        for (i, tier) in self.tiers.iter().enumerate() {
+            match tier.get_negative(key).await {
+                Ok(Some(cached)) => return Ok(Some(cached)),
+                Ok(None) => {}
+                Err(e) => read_failed(i, &e),
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self), fields(provider = %key.provider, symbol = %key.symbol))]
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        let (key, message) = (key.clone(), message.to_string());
+        self.write(move |tier| {
+            let (key, message) = (key.clone(), message.clone());
+            async move { tier.put_negative(&key, &message).await }
+        })
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        self.flush().await;
+        let mut listed = false;
+        let mut seen = HashSet::new();
+        let mut keys = Vec::new();
+        for tier in &self.tiers {
+            match tier.keys().await {
+                Err(DataError::NotSupported(_)) => continue,
+                result => {
+                    listed = true;
+                    keys.extend(result?.into_iter().filter(|key| seen.insert(key.clone())));
+                }
+            }
+        }
+        if !listed {
+            return Err(DataError::NotSupported(
+                "no cache tier can list its contents".to_string(),
+            ));
+        }
+        Ok(keys)
+    }
+
+    #[instrument(skip(self))]
+    async fn invalidate_stale(&self, ttl: Duration) -> Result<usize> {
+        self.flush().await;
+        let mut total_removed = 0;
+        for tier in &self.tiers {
+            total_removed += tier.invalidate_stale(ttl).await?;
+        }
+        Ok(total_removed)
+    }
+
+    #[instrument(skip(self))]
+    async fn clear(&self) -> Result<()> {
+        self.flush().await;
+        for tier in &self.tiers {
+            tier.clear().await?;
+        }
+        debug!("Cleared all cache tiers");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoundedMemoryCache, InMemoryCache};
+    use data_core::DataFrequency;
+    use polars::prelude::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn ohlcv_frame() -> DataFrame {
+        DataFrame::new(vec![
+            Column::new("date".into(), vec![date(2024, 1, 2), date(2024, 1, 3)]),
+            Column::new("close".into(), vec![100.0, 101.0]),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reads_promote_from_lower_tiers() {
+        let memory = Arc::new(BoundedMemoryCache::new(1024 * 1024));
+        let disk = Arc::new(InMemoryCache::new());
+        let cache = TieredCache::new()
+            .with_tier(memory.clone())
+            .with_tier(disk.clone());
+        let key = OhlcvCacheKey::new("test", &Symbol::new("AAPL"), DataFrequency::Daily);
+        let (start, end) = (date(2024, 1, 1), date(2024, 1, 5));
+
+        // Data written only to the lower tier
+        disk.put_ohlcv(&key, start, end, &ohlcv_frame())
+            .await
+            .unwrap();
+        let info = CompanyInfo::new(
+            Symbol::new("AAPL"),
+            "Apple Inc.",
+            "NASDAQ",
+            "Technology",
+            "Consumer Electronics",
+            "US",
+            "USD",
+        );
+        disk.put_company_info("test", &Symbol::new("AAPL"), &info)
+            .await
+            .unwrap();
+        assert!(memory.get_ohlcv(&key, start, end).await.unwrap().is_miss());
+
+        let lookup = cache.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(lookup.is_complete());
+        assert_eq!(lookup.data.unwrap().height(), 2);
+        let cached = cache
+            .get_company_info("test", &Symbol::new("AAPL"))
+            .await
+            .unwrap();
+        assert_eq!(cached.unwrap().value, info);
+
+        // Both entries now live in the top tier
+        let lookup = memory.get_ohlcv(&key, start, end).await.unwrap();
+        assert!(lookup.is_complete());
+        assert_eq!(lookup.data.unwrap().height(), 2);
+        assert!(
+            memory
+                .get_company_info("test", &Symbol::new("AAPL"))
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_promotion_keeps_storage_times() {
+        let memory = Arc::new(InMemoryCache::new());
+        let disk = Arc::new(InMemoryCache::new());
+        let cache = TieredCache::new()
+            .with_tier(memory.clone())
+            .with_tier(disk.clone());
+        let symbol = Symbol::new("AAPL");
+        let key = OhlcvCacheKey::new("test", &symbol, DataFrequency::Daily);
+        let (start, end) = (date(2024, 1, 1), date(2024, 1, 5));
+        let stored_at = Utc::now() - chrono::Duration::days(30);
+
+        let covered = CoveredRange::new(data_core::DateRange::new(start, end), stored_at);
+        disk.put_ohlcv_at(&key, covered, &ohlcv_frame())
+            .await
+            .unwrap();
+        let statement = FinancialStatement::new(symbol.clone(), end, PeriodType::Annual);
+        disk.put_financials_at("test", &symbol, &[Cached::new(statement, stored_at)])
+            .await
+            .unwrap();
+        let metrics = KeyMetrics::new(symbol.clone(), end);
+        disk.put_metrics_at("test", &symbol, &Cached::new(metrics, stored_at))
+            .await
+            .unwrap();
+
+        assert!(
+            cache
+                .get_ohlcv(&key, start, end)
+                .await
+                .unwrap()
+                .is_complete()
+        );
+        cache
+            .get_financials("test", &symbol, PeriodType::Annual)
+            .await
+            .unwrap()
+            .unwrap();
+        cache
+            .get_metrics("test", &symbol, end)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The top tier holds the promoted entries with their original times
+        let lookup = memory.get_ohlcv(&key, start, end).await.unwrap();
+        assert_eq!(lookup.covered, [covered]);
+        let financials = memory
+            .get_financials("test", &symbol, PeriodType::Annual)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(financials.cached_at, stored_at);
+        let metrics = memory
+            .get_metrics("test", &symbol, end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metrics.cached_at, stored_at);
+    }
+
+    #[tokio::test]
+    async fn test_write_back_reaches_lower_tiers_after_flush() {
+        let memory = Arc::new(InMemoryCache::new());
+        let disk = Arc::new(InMemoryCache::new());
+        let cache = TieredCache::new()
+            .with_tier(memory.clone())
+            .with_tier(disk.clone())
+            .with_write_mode(WriteMode::WriteBack);
+        let key = OhlcvCacheKey::new("test", &Symbol::new("AAPL"), DataFrequency::Daily);
+        let (start, end) = (date(2024, 1, 1), date(2024, 1, 5));
+
+        cache
+            .put_ohlcv(&key, start, end, &ohlcv_frame())
+            .await
+            .unwrap();
+        assert!(
+            memory
+                .get_ohlcv(&key, start, end)
+                .await
+                .unwrap()
+                .is_complete()
+        );
+
+        cache.flush().await;
+        assert!(
+            disk.get_ohlcv(&key, start, end)
+                .await
+                .unwrap()
+                .is_complete()
+        );
+
+        cache.clear().await.unwrap();
+        assert!(memory.get_ohlcv(&key, start, end).await.unwrap().is_miss());
+        assert!(disk.get_ohlcv(&key, start, end).await.unwrap().is_miss());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_write_back_preserves_write_order() {
+        let disk = Arc::new(crate::SqliteCache::in_memory().unwrap());
+        let cache = TieredCache::new()
+            .with_tier(Arc::new(InMemoryCache::new()))
+            .with_tier(disk.clone())
+            .with_write_mode(WriteMode::WriteBack);
+        let symbol = Symbol::new("AAPL");
+
+        for i in 0..50 {
+            let name = format!("Apple {i}");
+            let info = CompanyInfo::new(symbol.clone(), name, "NASDAQ", "", "", "US", "USD");
+            cache
+                .put_company_info("test", &symbol, &info)
+                .await
+                .unwrap();
+        }
+
+        cache.flush().await;
+        let info = disk
+            .get_company_info("test", &symbol)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.value.name, "Apple 49");
+    }
+}
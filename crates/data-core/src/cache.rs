@@ -1,48 +1,667 @@
 //! Cache trait for storing fetched financial data.
 //!
 //! This module defines the [`DataCache`] trait that provides a unified interface
-//! for caching OHLCV data, financial statements, and key metrics.
+//! for caching OHLCV data, ticks, financial statements, key metrics, company
+//! information and universe snapshots, along with
+//! [`OhlcvCacheKey`] and [`PriceAdjustment`] which identify a cached price series,
+//! [`OhlcvLookup`] which reports which parts of a requested range are cached, and
+//! [`Cached`] which carries the time a cached value was stored.
+//! [`CacheKey`] names an entry when listing a cache's contents, and
+//! [`CacheStats`] summarises what a cache holds. [`NegativeKey`] identifies a
+//! remembered "not found" answer from a provider.
+//!
+//! Caches that keep every stored version also answer bitemporal reads such as
+//! [`DataCache::get_financials_as_of`], which return data as it was known at an
+//! earlier point in time.
 
 use async_trait::async_trait;
-use chrono::NaiveDate;
-use polars::prelude::DataFrame;
+use chrono::{DateTime, NaiveDate, Utc};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::{
-    error::Result,
-    frequency::PeriodType,
-    types::{FinancialStatement, KeyMetrics, Symbol},
+    error::{DataError, Result},
+    frequency::{DataFrequency, PeriodType},
+    types::{CompanyInfo, FinancialStatement, KeyMetrics, Symbol, Tick},
 };
 
+/// How prices in an OHLCV series are adjusted for corporate actions.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum PriceAdjustment {
+    /// Prices as delivered by the provider, unadjusted apart from any
+    /// `adjusted_close` column the provider includes.
+    #[default]
+    Raw,
+    /// Prices adjusted for splits only.
+    SplitAdjusted,
+    /// Prices adjusted for splits and dividends.
+    Adjusted,
+}
+
+impl PriceAdjustment {
+    /// Returns a stable identifier suitable for storage keys.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::SplitAdjusted => "split",
+            Self::Adjusted => "adjusted",
+        }
+    }
+}
+
+impl std::str::FromStr for PriceAdjustment {
+    type Err = DataError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        [Self::Raw, Self::SplitAdjusted, Self::Adjusted]
+            .into_iter()
+            .find(|adjustment| adjustment.as_str() == s)
+            .ok_or_else(|| DataError::Parse(format!("unknown price adjustment: {s}")))
+    }
+}
+
+/// Identifies a cached OHLCV series.
+///
+/// Series for the same symbol at different frequencies or adjustment modes are
+/// cached independently, so a daily series is never served for an hourly request.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OhlcvCacheKey {
+    /// Name of the provider that served the data.
+    pub provider: String,
+    /// Instrument symbol.
+    pub symbol: Symbol,
+    /// Bar frequency.
+    pub frequency: DataFrequency,
+    /// Price adjustment mode.
+    pub adjustment: PriceAdjustment,
+}
+
+impl OhlcvCacheKey {
+    /// Creates a key for raw prices at the given frequency.
+    #[must_use]
+    pub fn new(provider: impl Into<String>, symbol: &Symbol, frequency: DataFrequency) -> Self {
+        Self {
+            provider: provider.into(),
+            symbol: symbol.clone(),
+            frequency,
+            adjustment: PriceAdjustment::default(),
+        }
+    }
+
+    /// Sets the price adjustment mode.
+    #[must_use]
+    pub const fn with_adjustment(mut self, adjustment: PriceAdjustment) -> Self {
+        self.adjustment = adjustment;
+        self
+    }
+}
+
+/// An inclusive range of dates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DateRange {
+    /// First date in the range.
+    pub start: NaiveDate,
+    /// Last date in the range.
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// Creates a range from `start` to `end`, inclusive.
+    #[must_use]
+    pub const fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns true if the range contains no dates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+/// The kinds of data a [`DataCache`] stores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CacheDataKind {
+    /// OHLCV bars.
+    Ohlcv,
+    /// Trades and quotes.
+    Ticks,
+    /// Financial statements.
+    Financials,
+    /// Key metrics.
+    Metrics,
+    /// Company reference information.
+    CompanyInfo,
+    /// Universe membership snapshots.
+    Universe,
+}
+
+impl CacheDataKind {
+    /// Every kind of cached data.
+    pub const ALL: [Self; 6] = [
+        Self::Ohlcv,
+        Self::Ticks,
+        Self::Financials,
+        Self::Metrics,
+        Self::CompanyInfo,
+        Self::Universe,
+    ];
+
+    /// Returns a stable identifier suitable for storage keys.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ohlcv => "ohlcv",
+            Self::Ticks => "ticks",
+            Self::Financials => "financials",
+            Self::Metrics => "metrics",
+            Self::CompanyInfo => "company_info",
+            Self::Universe => "universe",
+        }
+    }
+}
+
+/// Identifies a cached OHLCV series, set of financial statements or key
+/// metrics snapshot, as listed by [`DataCache::keys`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CacheKey {
+    /// An OHLCV series.
+    Ohlcv(OhlcvCacheKey),
+    /// Financial statements for one symbol and period type.
+    Financials {
+        /// Name of the provider that served the data.
+        provider: String,
+        /// Instrument symbol.
+        symbol: Symbol,
+        /// Reporting period of the statements.
+        period_type: PeriodType,
+    },
+    /// Key metrics for one symbol on one date.
+    Metrics {
+        /// Name of the provider that served the data.
+        provider: String,
+        /// Instrument symbol.
+        symbol: Symbol,
+        /// Date the metrics apply to.
+        date: NaiveDate,
+    },
+}
+
+/// Identifies a request a provider answered with "not found", as remembered
+/// by [`DataCache::put_negative`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NegativeKey {
+    /// Name of the provider that gave the answer.
+    pub provider: String,
+    /// Instrument symbol.
+    pub symbol: Symbol,
+    /// Description of the request that had no data, or `None` if the provider
+    /// does not recognise the symbol at all.
+    pub request: Option<String>,
+}
+
+impl NegativeKey {
+    /// Creates a key recording that a provider does not recognise a symbol.
+    #[must_use]
+    pub fn symbol(provider: impl Into<String>, symbol: &Symbol) -> Self {
+        Self {
+            provider: provider.into(),
+            symbol: symbol.clone(),
+            request: None,
+        }
+    }
+
+    /// Creates a key recording that a provider has no data for one request
+    /// about a symbol, e.g. a date range of daily bars.
+    #[must_use]
+    pub fn request(
+        provider: impl Into<String>,
+        symbol: &Symbol,
+        request: impl Into<String>,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            symbol: symbol.clone(),
+            request: Some(request.into()),
+        }
+    }
+}
+
+/// What a cache holds for one series, symbol or universe, as reported by
+/// [`DataCache::inspect`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntryStats {
+    /// Kind of data.
+    pub kind: CacheDataKind,
+    /// Name of the provider that served the data.
+    pub provider: String,
+    /// Instrument symbol, or the universe identifier for universe snapshots.
+    pub id: String,
+    /// Bar frequency, for OHLCV series.
+    pub frequency: Option<DataFrequency>,
+    /// Price adjustment mode, for OHLCV series.
+    pub adjustment: Option<PriceAdjustment>,
+    /// Reporting period, for financial statements.
+    pub period_type: Option<PeriodType>,
+    /// Dates recorded as covered, for OHLCV series and ticks. Ranges are
+    /// sorted and overlapping or adjacent ranges merged.
+    pub covered: Vec<DateRange>,
+    /// Number of bars, ticks, statements, metrics dates or universe snapshots
+    /// stored. Company information counts as one row.
+    pub rows: usize,
+    /// Estimated size of the stored data in bytes.
+    pub bytes: usize,
+    /// When the oldest part of the entry was stored.
+    pub oldest_cached_at: DateTime<Utc>,
+    /// When the newest part of the entry was stored.
+    pub newest_cached_at: DateTime<Utc>,
+}
+
+impl CacheEntryStats {
+    /// Creates stats for an entry with no rows, stored at `cached_at`.
+    #[must_use]
+    pub fn new(
+        kind: CacheDataKind,
+        provider: impl Into<String>,
+        id: impl Into<String>,
+        cached_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            kind,
+            provider: provider.into(),
+            id: id.into(),
+            frequency: None,
+            adjustment: None,
+            period_type: None,
+            covered: Vec::new(),
+            rows: 0,
+            bytes: 0,
+            oldest_cached_at: cached_at,
+            newest_cached_at: cached_at,
+        }
+    }
+
+    /// Creates stats for an OHLCV series with no rows, stored at `cached_at`.
+    #[must_use]
+    pub fn ohlcv(key: &OhlcvCacheKey, cached_at: DateTime<Utc>) -> Self {
+        Self {
+            frequency: Some(key.frequency),
+            adjustment: Some(key.adjustment),
+            ..Self::new(
+                CacheDataKind::Ohlcv,
+                &key.provider,
+                key.symbol.as_str(),
+                cached_at,
+            )
+        }
+    }
+
+    /// Sets the reporting period.
+    #[must_use]
+    pub const fn with_period_type(mut self, period_type: PeriodType) -> Self {
+        self.period_type = Some(period_type);
+        self
+    }
+
+    /// Widens the oldest and newest storage times to include `cached_at`.
+    pub fn observe(&mut self, cached_at: DateTime<Utc>) {
+        self.oldest_cached_at = self.oldest_cached_at.min(cached_at);
+        self.newest_cached_at = self.newest_cached_at.max(cached_at);
+    }
+}
+
+/// A summary of a cache's contents and lookup counters, as reported by
+/// [`DataCache::inspect`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// One entry per series, symbol or universe, ordered by kind, provider,
+    /// id, frequency, adjustment and period.
+    pub entries: Vec<CacheEntryStats>,
+    /// Lookups answered from the cache since it was opened.
+    pub hits: u64,
+    /// Lookups the cache could not answer since it was opened.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Builds a summary, sorting the entries and merging their covered ranges.
+    #[must_use]
+    pub fn new(mut entries: Vec<CacheEntryStats>, hits: u64, misses: u64) -> Self {
+        for entry in &mut entries {
+            entry.covered = merge_ranges(std::mem::take(&mut entry.covered));
+        }
+        entries.sort_by(|a, b| {
+            (
+                a.kind,
+                &a.provider,
+                &a.id,
+                a.frequency,
+                a.adjustment,
+                a.period_type,
+            )
+                .cmp(&(
+                    b.kind,
+                    &b.provider,
+                    &b.id,
+                    b.frequency,
+                    b.adjustment,
+                    b.period_type,
+                ))
+        });
+        Self {
+            entries,
+            hits,
+            misses,
+        }
+    }
+
+    /// Total rows across all entries.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.entries.iter().map(|e| e.rows).sum()
+    }
+
+    /// Total estimated bytes across all entries.
+    #[must_use]
+    pub fn bytes(&self) -> usize {
+        self.entries.iter().map(|e| e.bytes).sum()
+    }
+
+    /// When the oldest data in the cache was stored, if it holds anything.
+    #[must_use]
+    pub fn oldest_cached_at(&self) -> Option<DateTime<Utc>> {
+        self.entries.iter().map(|e| e.oldest_cached_at).min()
+    }
+
+    /// When the newest data in the cache was stored, if it holds anything.
+    #[must_use]
+    pub fn newest_cached_at(&self) -> Option<DateTime<Utc>> {
+        self.entries.iter().map(|e| e.newest_cached_at).max()
+    }
+
+    /// Fraction of lookups answered from the cache, if any were made.
+    #[must_use]
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+
+    /// Returns the entries of one kind.
+    pub fn entries_of(&self, kind: CacheDataKind) -> impl Iterator<Item = &CacheEntryStats> {
+        self.entries.iter().filter(move |e| e.kind == kind)
+    }
+}
+
+/// Sorts ranges and merges those that overlap or touch.
+fn merge_ranges(mut ranges: Vec<DateRange>) -> Vec<DateRange> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<DateRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end.succ_opt().is_none_or(|next| range.start <= next) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// A cached value along with the time it was stored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cached<T> {
+    /// The cached value.
+    pub value: T,
+    /// When the value was stored. For values assembled from several writes
+    /// this is the oldest write.
+    pub cached_at: DateTime<Utc>,
+}
+
+impl<T> Cached<T> {
+    /// Wraps a value stored at `cached_at`.
+    #[must_use]
+    pub const fn new(value: T, cached_at: DateTime<Utc>) -> Self {
+        Self { value, cached_at }
+    }
+
+    /// Returns how long ago the value was stored.
+    #[must_use]
+    pub fn age(&self) -> chrono::TimeDelta {
+        Utc::now().signed_duration_since(self.cached_at)
+    }
+
+    /// Transforms the cached value, keeping its timestamp.
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Cached<U> {
+        Cached {
+            value: f(self.value),
+            cached_at: self.cached_at,
+        }
+    }
+}
+
+/// A date range recorded as covered in a cache and when it was stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoveredRange {
+    /// The covered dates.
+    pub range: DateRange,
+    /// When the range was stored.
+    pub cached_at: DateTime<Utc>,
+}
+
+impl CoveredRange {
+    /// Creates a covered range stored at `cached_at`.
+    #[must_use]
+    pub const fn new(range: DateRange, cached_at: DateTime<Utc>) -> Self {
+        Self { range, cached_at }
+    }
+
+    /// Returns the part of this range within `bounds`, if any.
+    #[must_use]
+    pub fn clip(&self, bounds: DateRange) -> Option<Self> {
+        let range = DateRange::new(
+            self.range.start.max(bounds.start),
+            self.range.end.min(bounds.end),
+        );
+        (!range.is_empty()).then_some(Self::new(range, self.cached_at))
+    }
+}
+
+/// Returns the parts of `requested` not covered by any of `covered`.
+///
+/// Gaps are returned in ascending order. Overlapping and adjacent covered
+/// ranges are treated as one.
+#[must_use]
+pub fn missing_ranges(covered: &[DateRange], requested: DateRange) -> Vec<DateRange> {
+    let mut covered: Vec<DateRange> = covered.iter().filter(|r| !r.is_empty()).copied().collect();
+    covered.sort_by_key(|r| r.start);
+
+    let mut gaps = Vec::new();
+    let mut cursor = requested.start;
+    for range in covered {
+        if cursor > requested.end {
+            break;
+        }
+        if range.end < cursor {
+            continue;
+        }
+        if range.start > cursor {
+            let gap_end = range
+                .start
+                .pred_opt()
+                .unwrap_or(range.start)
+                .min(requested.end);
+            gaps.push(DateRange::new(cursor, gap_end));
+        }
+        match range.end.succ_opt() {
+            Some(next) => cursor = cursor.max(next),
+            None => return gaps,
+        }
+    }
+    if cursor <= requested.end {
+        gaps.push(DateRange::new(cursor, requested.end));
+    }
+    gaps
+}
+
+/// Result of looking up an OHLCV range in a cache.
+#[derive(Clone, Debug, Default)]
+pub struct OhlcvLookup {
+    /// Cached rows within the requested range, if any.
+    pub data: Option<DataFrame>,
+    /// Parts of the requested range the cache has no record of.
+    pub missing: Vec<DateRange>,
+    /// Stored ranges overlapping the request, clipped to it, with the time
+    /// each was stored. Ranges may overlap when a span was stored more than once.
+    pub covered: Vec<CoveredRange>,
+}
+
+impl OhlcvLookup {
+    /// A lookup where nothing in the range is cached.
+    #[must_use]
+    pub fn miss(start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            data: None,
+            missing: vec![DateRange::new(start, end)],
+            covered: Vec::new(),
+        }
+    }
+
+    /// Builds a lookup for `start..=end` from cached rows and the ranges a
+    /// series has stored.
+    #[must_use]
+    pub fn from_coverage(
+        data: Option<DataFrame>,
+        coverage: &[CoveredRange],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        let requested = DateRange::new(start, end);
+        let ranges: Vec<DateRange> = coverage.iter().map(|c| c.range).collect();
+        Self {
+            data,
+            missing: missing_ranges(&ranges, requested),
+            covered: coverage.iter().filter_map(|c| c.clip(requested)).collect(),
+        }
+    }
+
+    /// Returns true if the whole requested range is covered by the cache.
+    ///
+    /// A complete lookup may still have no rows, e.g. for a range that
+    /// contains no trading days.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Returns true if nothing in the requested range is covered.
+    #[must_use]
+    pub fn is_miss(&self) -> bool {
+        self.data.is_none() && !self.missing.is_empty()
+    }
+}
+
+/// Combines OHLCV frames into one series sorted by date.
+///
+/// Rows for the same date are deduplicated, keeping the one from the latest
+/// frame. Empty frames are ignored.
+///
+/// # Errors
+/// Returns an error if the frames have incompatible schemas.
+pub fn stitch_ohlcv(frames: Vec<DataFrame>) -> Result<DataFrame> {
+    let frames: Vec<LazyFrame> = frames
+        .into_iter()
+        .filter(|df| df.height() > 0)
+        .map(IntoLazy::lazy)
+        .collect();
+    if frames.is_empty() {
+        return Ok(DataFrame::empty());
+    }
+    concat(
+        frames,
+        UnionArgs {
+            to_supertypes: true,
+            ..Default::default()
+        },
+    )
+    .and_then(|lf| {
+        lf.unique_stable(Some(vec!["date".into()]), UniqueKeepStrategy::Last)
+            .sort(["date"], SortMultipleOptions::default())
+            .collect()
+    })
+    .map_err(|e| DataError::Cache(e.to_string()))
+}
+
 /// Trait for caching fetched financial data.
 ///
 /// Implementations can store data in various backends (SQLite, in-memory, etc.)
 /// to avoid repeated API calls and improve performance.
 #[async_trait]
 pub trait DataCache: Send + Sync {
-    /// Retrieves cached OHLCV data for a symbol within a date range.
+    /// Retrieves cached OHLCV data for a series within a date range.
     ///
-    /// Returns `Ok(Some(df))` if cached data exists, `Ok(None)` if not cached.
+    /// Returns the cached rows in the range along with the sub-ranges that have
+    /// never been stored, so callers can fetch only the gaps.
     async fn get_ohlcv(
         &self,
-        provider: &str,
-        symbol: &Symbol,
+        key: &OhlcvCacheKey,
         start: NaiveDate,
         end: NaiveDate,
-    ) -> Result<Option<DataFrame>>;
+    ) -> Result<OhlcvLookup>;
 
-    /// Stores OHLCV data in the cache.
-    async fn put_ohlcv(&self, provider: &str, symbol: &Symbol, data: &DataFrame) -> Result<()>;
+    /// Stores OHLCV data for a series, recording `start..=end` as covered.
+    ///
+    /// The covered range is the range that was requested from the provider, so
+    /// dates without rows (weekends, holidays) are not reported as missing later.
+    /// `data` may be empty to record a range that has no rows.
+    async fn put_ohlcv(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        data: &DataFrame,
+    ) -> Result<()>;
+
+    /// Retrieves cached ticks for a symbol within a time range.
+    ///
+    /// Returns `Ok(Some(ticks))` only if a single previously stored range
+    /// contains `start..=end`, so a partially cached range is treated as a miss.
+    /// The timestamp is that of the newest such range.
+    async fn get_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<Tick>>>>;
+
+    /// Stores ticks for a symbol, recording `start..=end` as covered.
+    ///
+    /// `ticks` may be empty to record a range with no activity.
+    async fn put_ticks(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        ticks: &[Tick],
+    ) -> Result<()>;
 
     /// Retrieves cached financial statements for a symbol.
     ///
     /// Returns `Ok(Some(statements))` if cached, `Ok(None)` if not cached.
+    /// The timestamp is that of the oldest statement.
     async fn get_financials(
         &self,
         provider: &str,
         symbol: &Symbol,
         period_type: PeriodType,
-    ) -> Result<Option<Vec<FinancialStatement>>>;
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>>;
 
     /// Stores financial statements in the cache.
     async fn put_financials(
@@ -60,7 +679,7 @@ pub trait DataCache: Send + Sync {
         provider: &str,
         symbol: &Symbol,
         date: NaiveDate,
-    ) -> Result<Option<KeyMetrics>>;
+    ) -> Result<Option<Cached<KeyMetrics>>>;
 
     /// Stores key metrics in the cache.
     async fn put_metrics(
@@ -70,6 +689,233 @@ pub trait DataCache: Send + Sync {
         metrics: &KeyMetrics,
     ) -> Result<()>;
 
+    /// Retrieves cached company information for a symbol.
+    ///
+    /// Returns `Ok(Some(info))` if cached, `Ok(None)` if not cached.
+    async fn get_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+    ) -> Result<Option<Cached<CompanyInfo>>>;
+
+    /// Stores company information in the cache.
+    async fn put_company_info(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+    ) -> Result<()>;
+
+    /// Retrieves the latest cached snapshot of a universe taken on or before `as_of`.
+    ///
+    /// Returns `Ok(Some(symbols))` if such a snapshot exists, `Ok(None)` otherwise.
+    async fn get_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<Option<Cached<Vec<Symbol>>>>;
+
+    /// Stores a snapshot of a universe's members as of a date.
+    ///
+    /// A later snapshot for the same date replaces the earlier one.
+    async fn put_universe(
+        &self,
+        provider: &str,
+        universe_id: &str,
+        as_of: NaiveDate,
+        symbols: &[Symbol],
+    ) -> Result<()>;
+
+    /// Retrieves OHLCV data for a series as the cache knew it at `knowledge_time`.
+    ///
+    /// Each date is served from the newest version stored at or before
+    /// `knowledge_time`, and coverage is limited to ranges stored by then, so a
+    /// backtest can be rerun against exactly the prices an earlier run saw.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`]; only
+    /// caches that keep every stored version can answer.
+    async fn get_ohlcv_as_of(
+        &self,
+        key: &OhlcvCacheKey,
+        start: NaiveDate,
+        end: NaiveDate,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<OhlcvLookup> {
+        let _ = (key, start, end, knowledge_time);
+        Err(DataError::NotSupported(
+            "this cache does not keep OHLCV history".to_string(),
+        ))
+    }
+
+    /// Retrieves financial statements for a symbol as the cache knew them at
+    /// `knowledge_time`, so restated figures are reported as first seen.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`].
+    async fn get_financials_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<Vec<FinancialStatement>>>> {
+        let _ = (provider, symbol, period_type, knowledge_time);
+        Err(DataError::NotSupported(
+            "this cache does not keep financials history".to_string(),
+        ))
+    }
+
+    /// Retrieves key metrics for a symbol on a date as the cache knew them at
+    /// `knowledge_time`.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`].
+    async fn get_metrics_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        date: NaiveDate,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<KeyMetrics>>> {
+        let _ = (provider, symbol, date, knowledge_time);
+        Err(DataError::NotSupported(
+            "this cache does not keep metrics history".to_string(),
+        ))
+    }
+
+    /// Retrieves company information for a symbol as the cache knew it at
+    /// `knowledge_time`.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`].
+    async fn get_company_info_as_of(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        knowledge_time: DateTime<Utc>,
+    ) -> Result<Option<Cached<CompanyInfo>>> {
+        let _ = (provider, symbol, knowledge_time);
+        Err(DataError::NotSupported(
+            "this cache does not keep company info history".to_string(),
+        ))
+    }
+
+    /// Retrieves a remembered "not found" answer along with the provider's
+    /// error message.
+    ///
+    /// The default implementation remembers nothing and returns `Ok(None)`.
+    async fn get_negative(&self, key: &NegativeKey) -> Result<Option<Cached<String>>> {
+        let _ = key;
+        Ok(None)
+    }
+
+    /// Remembers that a provider answered a request with "not found", so
+    /// repeated requests can skip it. A later answer for the same key replaces
+    /// the earlier one.
+    ///
+    /// Negative entries are dropped by [`invalidate_stale`](Self::invalidate_stale)
+    /// and [`clear`](Self::clear) like any other entry. The default
+    /// implementation discards the answer.
+    async fn put_negative(&self, key: &NegativeKey, message: &str) -> Result<()> {
+        let _ = (key, message);
+        Ok(())
+    }
+
+    /// Retrieves cached financial statements for a symbol, each with the time
+    /// it was stored.
+    ///
+    /// The default implementation stamps every statement with the timestamp
+    /// [`get_financials`](Self::get_financials) reports.
+    async fn get_financials_entries(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        period_type: PeriodType,
+    ) -> Result<Option<Vec<Cached<FinancialStatement>>>> {
+        let cached = self.get_financials(provider, symbol, period_type).await?;
+        Ok(cached.map(|cached| {
+            cached
+                .value
+                .into_iter()
+                .map(|statement| Cached::new(statement, cached.cached_at))
+                .collect()
+        }))
+    }
+
+    /// Stores OHLCV data as if it had been stored at `covered.cached_at`,
+    /// recording `covered.range` as covered.
+    ///
+    /// Used to restore data without losing when it was first cached. The
+    /// default implementation calls [`put_ohlcv`](Self::put_ohlcv), which
+    /// stamps the data with the current time.
+    async fn put_ohlcv_at(
+        &self,
+        key: &OhlcvCacheKey,
+        covered: CoveredRange,
+        data: &DataFrame,
+    ) -> Result<()> {
+        self.put_ohlcv(key, covered.range.start, covered.range.end, data)
+            .await
+    }
+
+    /// Stores financial statements as if each had been stored at its
+    /// `cached_at`.
+    ///
+    /// The default implementation calls [`put_financials`](Self::put_financials),
+    /// which stamps the statements with the current time.
+    async fn put_financials_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        statements: &[Cached<FinancialStatement>],
+    ) -> Result<()> {
+        let statements: Vec<FinancialStatement> =
+            statements.iter().map(|s| s.value.clone()).collect();
+        self.put_financials(provider, symbol, &statements).await
+    }
+
+    /// Stores key metrics as if they had been stored at `metrics.cached_at`.
+    ///
+    /// The default implementation calls [`put_metrics`](Self::put_metrics),
+    /// which stamps the metrics with the current time.
+    async fn put_metrics_at(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        metrics: &Cached<KeyMetrics>,
+    ) -> Result<()> {
+        self.put_metrics(provider, symbol, &metrics.value).await
+    }
+
+    /// Lists the OHLCV series, financial statements and key metrics held by
+    /// the cache, including stale entries.
+    ///
+    /// Ticks, company information and universe snapshots are not listed.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`].
+    async fn keys(&self) -> Result<Vec<CacheKey>> {
+        Err(DataError::NotSupported(
+            "this cache cannot list its contents".to_string(),
+        ))
+    }
+
+    /// Reports what the cache holds and how often lookups were answered from
+    /// it, so coverage can be audited before kicking off a backtest.
+    ///
+    /// Lookups through the `get_*` methods are counted; an OHLCV lookup is a
+    /// hit only if the whole requested range is covered.
+    ///
+    /// # Errors
+    /// The default implementation returns [`DataError::NotSupported`].
+    async fn inspect(&self) -> Result<CacheStats> {
+        Err(DataError::NotSupported(
+            "this cache does not report statistics".to_string(),
+        ))
+    }
+
     /// Removes cache entries older than the specified TTL.
     ///
     /// Returns the number of entries invalidated.
@@ -0,0 +1,160 @@
+//! Provider capability declarations.
+//!
+//! This module defines [`ProviderCapabilities`], which describes what a provider
+//! can serve (frequencies, history depth, asset classes, exchanges, universes),
+//! and [`AssetClass`] for classifying instruments.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{frequency::DataFrequency, types::Symbol};
+
+/// Broad classification of a tradable instrument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetClass {
+    /// Common and preferred stock.
+    Equity,
+    /// Exchange-traded funds.
+    Etf,
+    /// Mutual funds.
+    Fund,
+    /// Market indices.
+    Index,
+    /// Foreign exchange pairs.
+    Fx,
+    /// Cryptocurrencies.
+    Crypto,
+    /// Futures contracts.
+    Future,
+    /// Options contracts.
+    Option,
+    /// Fixed income instruments.
+    Bond,
+}
+
+impl AssetClass {
+    /// Infers the asset class from Yahoo-style symbol conventions.
+    ///
+    /// Recognizes `^` indices, `=X` currency pairs, `=F` futures and
+    /// `-USD` style crypto pairs. Returns `None` when the symbol carries no
+    /// marker, since plain tickers may be equities, ETFs or funds.
+    #[must_use]
+    pub fn infer(symbol: &Symbol) -> Option<Self> {
+        let s = symbol.as_str();
+        if s.starts_with('^') {
+            Some(Self::Index)
+        } else if s.ends_with("=X") {
+            Some(Self::Fx)
+        } else if s.ends_with("=F") {
+            Some(Self::Future)
+        } else if s.ends_with("-USD") || s.ends_with("-USDT") || s.ends_with("-EUR") {
+            Some(Self::Crypto)
+        } else {
+            None
+        }
+    }
+}
+
+/// Declared capabilities of a data provider.
+///
+/// Empty asset class and exchange lists mean "unrestricted": a provider with no
+/// declared asset classes is assumed to serve any asset class. Universes, by
+/// contrast, must be listed explicitly to be supported.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    /// Supported data frequencies.
+    pub frequencies: Vec<DataFrequency>,
+    /// Maximum history available per frequency, in days back from today.
+    pub max_history_days: HashMap<DataFrequency, u32>,
+    /// Supported asset classes.
+    pub asset_classes: Vec<AssetClass>,
+    /// Supported exchange suffixes (e.g. `"L"` for London, `""` for US listings).
+    pub exchanges: Vec<String>,
+    /// Named universes the provider can resolve (e.g. `"sp500"`).
+    pub universes: Vec<String>,
+}
+
+impl ProviderCapabilities {
+    /// Creates capabilities supporting the given frequencies and nothing else restricted.
+    #[must_use]
+    pub fn new(frequencies: &[DataFrequency]) -> Self {
+        Self {
+            frequencies: frequencies.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Limits the history available at a frequency.
+    #[must_use]
+    pub fn with_max_history(mut self, frequency: DataFrequency, days: u32) -> Self {
+        self.max_history_days.insert(frequency, days);
+        self
+    }
+
+    /// Sets the supported asset classes.
+    #[must_use]
+    pub fn with_asset_classes(mut self, asset_classes: &[AssetClass]) -> Self {
+        self.asset_classes = asset_classes.to_vec();
+        self
+    }
+
+    /// Sets the supported exchange suffixes.
+    #[must_use]
+    pub fn with_exchanges(mut self, exchanges: &[&str]) -> Self {
+        self.exchanges = exchanges.iter().map(|e| (*e).to_string()).collect();
+        self
+    }
+
+    /// Sets the supported universes.
+    #[must_use]
+    pub fn with_universes(mut self, universes: &[&str]) -> Self {
+        self.universes = universes.iter().map(|u| (*u).to_string()).collect();
+        self
+    }
+
+    /// Returns true if the frequency is supported.
+    #[must_use]
+    pub fn supports_frequency(&self, frequency: DataFrequency) -> bool {
+        self.frequencies.contains(&frequency)
+    }
+
+    /// Returns true if data starting at `start` is within the history limit
+    /// for the frequency, measured back from `today`.
+    #[must_use]
+    pub fn covers_history(
+        &self,
+        frequency: DataFrequency,
+        start: NaiveDate,
+        today: NaiveDate,
+    ) -> bool {
+        self.max_history_days
+            .get(&frequency)
+            .is_none_or(|&days| (today - start).num_days() <= i64::from(days))
+    }
+
+    /// Returns true if the asset class is supported.
+    #[must_use]
+    pub fn supports_asset_class(&self, asset_class: AssetClass) -> bool {
+        self.asset_classes.is_empty() || self.asset_classes.contains(&asset_class)
+    }
+
+    /// Returns true if the symbol's exchange suffix is supported.
+    #[must_use]
+    pub fn supports_exchange(&self, symbol: &Symbol) -> bool {
+        self.exchanges.is_empty()
+            || self
+                .exchanges
+                .iter()
+                .any(|e| e == symbol.exchange_suffix().unwrap_or_default())
+    }
+
+    /// Returns true if the universe is supported.
+    #[must_use]
+    pub fn supports_universe(&self, universe_id: &str) -> bool {
+        self.universes
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(universe_id))
+    }
+}
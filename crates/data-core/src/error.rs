@@ -6,7 +6,7 @@
 use thiserror::Error;
 
 /// Errors that can occur during data operations.
-#[derive(Error, Debug)]
+#[derive(Error, Clone, Debug)]
 pub enum DataError {
     /// Network-related errors (connection failures, timeouts, etc.).
     #[error("Network error: {0}")]
@@ -40,6 +40,10 @@ pub enum DataError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// Data returned by a provider failed a quality check.
+    #[error("Validation failed: {0}")]
+    Validation(String),
+
     /// Error interacting with the cache.
     #[error("Cache error: {0}")]
     Cache(String),
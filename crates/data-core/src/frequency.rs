@@ -4,9 +4,12 @@
 //! and [`PeriodType`] for fundamental data periods.
 
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::DataError;
 
 /// Frequency/granularity of time series data.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum DataFrequency {
     /// Individual trades/quotes.
     Tick,
@@ -35,6 +38,22 @@ pub enum DataFrequency {
 }
 
 impl DataFrequency {
+    /// Every frequency, from finest to coarsest.
+    pub const ALL: [Self; 12] = [
+        Self::Tick,
+        Self::Second,
+        Self::Minute,
+        Self::FiveMinute,
+        Self::FifteenMinute,
+        Self::ThirtyMinute,
+        Self::Hourly,
+        Self::Daily,
+        Self::Weekly,
+        Self::Monthly,
+        Self::Quarterly,
+        Self::Annual,
+    ];
+
     /// Returns true if this is an intraday frequency (tick through hourly).
     #[must_use]
     pub const fn is_intraday(&self) -> bool {
@@ -50,6 +69,25 @@ impl DataFrequency {
         )
     }
 
+    /// Returns a stable identifier suitable for storage keys.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tick => "tick",
+            Self::Second => "1s",
+            Self::Minute => "1m",
+            Self::FiveMinute => "5m",
+            Self::FifteenMinute => "15m",
+            Self::ThirtyMinute => "30m",
+            Self::Hourly => "1h",
+            Self::Daily => "1d",
+            Self::Weekly => "1wk",
+            Self::Monthly => "1mo",
+            Self::Quarterly => "3mo",
+            Self::Annual => "1y",
+        }
+    }
+
     /// Returns true if this is a fundamental data frequency (quarterly or annual).
     #[must_use]
     pub const fn is_fundamental(&self) -> bool {
@@ -57,8 +95,21 @@ impl DataFrequency {
     }
 }
 
+impl FromStr for DataFrequency {
+    type Err = DataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|frequency| frequency.as_str() == s)
+            .ok_or_else(|| DataError::Parse(format!("unknown data frequency: {s}")))
+    }
+}
+
 /// Period type for fundamental financial data.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub enum PeriodType {
     /// Annual reporting period.
     #[default]
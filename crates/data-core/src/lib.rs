@@ -17,6 +17,8 @@
 
 /// Cache trait and types for storing fetched data.
 pub mod cache;
+/// Provider capability declarations.
+pub mod capabilities;
 /// Error types for data operations.
 pub mod error;
 /// Data frequency and period type definitions.
@@ -27,7 +29,12 @@ pub mod provider;
 pub mod types;
 
 // Re-export commonly used items at crate root
-pub use cache::DataCache;
+pub use cache::{
+    CacheDataKind, CacheEntryStats, CacheKey, CacheStats, Cached, CoveredRange, DataCache,
+    DateRange, NegativeKey, OhlcvCacheKey, OhlcvLookup, PriceAdjustment, missing_ranges,
+    stitch_ohlcv,
+};
+pub use capabilities::{AssetClass, ProviderCapabilities};
 pub use error::{DataError, Result};
 pub use frequency::{DataFrequency, PeriodType};
 pub use provider::{
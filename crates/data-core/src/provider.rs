@@ -16,6 +16,7 @@ use std::fmt::Debug;
 use std::pin::Pin;
 
 use crate::{
+    capabilities::ProviderCapabilities,
     error::Result,
     frequency::{DataFrequency, PeriodType},
     types::{CompanyInfo, FinancialStatement, KeyMetrics, Symbol, Tick},
@@ -34,6 +35,15 @@ pub trait DataProvider: Send + Sync + Debug {
 
     /// Returns the data frequencies supported by this provider.
     fn supported_frequencies(&self) -> &[DataFrequency];
+
+    /// Returns the full set of capabilities declared by this provider.
+    ///
+    /// The default implementation declares the supported frequencies with no
+    /// other restrictions. Providers should override this to describe history
+    /// limits, asset classes, exchanges and universes they serve.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::new(self.supported_frequencies())
+    }
 }
 
 /// Provider for OHLCV price data.
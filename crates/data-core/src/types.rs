@@ -35,6 +35,17 @@ impl Symbol {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Returns the exchange suffix of a Yahoo-style symbol (e.g. `"L"` for `VOD.L`).
+    ///
+    /// Returns `None` for symbols without a suffix, which are typically US listings.
+    #[must_use]
+    pub fn exchange_suffix(&self) -> Option<&str> {
+        self.0
+            .rsplit_once('.')
+            .map(|(_, suffix)| suffix)
+            .filter(|suffix| !suffix.is_empty())
+    }
 }
 
 impl fmt::Display for Symbol {
@@ -363,6 +374,70 @@ impl FinancialStatement {
     }
 }
 
+/// Generates name-based accessors for the numeric line items of a statement.
+macro_rules! line_item_fields {
+    ($($field:ident),* $(,)?) => {
+        impl FinancialStatement {
+            /// Names of all numeric line-item fields, in declaration order.
+            pub const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            /// Returns the value of a line item by field name.
+            ///
+            /// Returns `None` if the field is unknown or has no value.
+            #[must_use]
+            pub fn field(&self, name: &str) -> Option<f64> {
+                match name {
+                    $(stringify!($field) => self.$field,)*
+                    _ => None,
+                }
+            }
+
+            /// Returns a mutable reference to a line item by field name.
+            ///
+            /// Returns `None` if the field is unknown.
+            pub fn field_mut(&mut self, name: &str) -> Option<&mut Option<f64>> {
+                match name {
+                    $(stringify!($field) => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+line_item_fields!(
+    total_assets,
+    current_assets,
+    cash_and_equivalents,
+    inventory,
+    accounts_receivable,
+    total_liabilities,
+    current_liabilities,
+    long_term_debt,
+    short_term_debt,
+    total_debt,
+    accounts_payable,
+    stockholders_equity,
+    revenue,
+    cost_of_revenue,
+    gross_profit,
+    operating_expenses,
+    operating_income,
+    net_income,
+    ebitda,
+    eps_basic,
+    eps_diluted,
+    interest_expense,
+    operating_cash_flow,
+    investing_cash_flow,
+    financing_cash_flow,
+    capital_expenditures,
+    free_cash_flow,
+    dividends_paid,
+    shares_outstanding,
+    shares_outstanding_diluted,
+);
+
 /// Key financial metrics and ratios.
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct KeyMetrics {
@@ -41,8 +41,9 @@
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use data_core::{
-    CompanyInfo, DataError, DataFrequency, DataProvider, FinancialStatement,
-    FundamentalDataProvider, KeyMetrics, PeriodType, ReferenceDataProvider, Result, Symbol,
+    AssetClass, CompanyInfo, DataError, DataFrequency, DataProvider, FinancialStatement,
+    FundamentalDataProvider, KeyMetrics, PeriodType, ProviderCapabilities, ReferenceDataProvider,
+    Result, Symbol,
 };
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -153,6 +154,25 @@ impl EdgarProvider {
         }
     }
 
+    /// Set the minimum interval between requests.
+    ///
+    /// The SEC allows at most 10 requests per second, so intervals below the
+    /// default of 100ms are not recommended.
+    ///
+    /// # Example
+    /// ```
+    /// use data_edgar::EdgarProvider;
+    /// use std::time::Duration;
+    ///
+    /// let provider = EdgarProvider::new("MyApp/1.0 (contact@example.com)")
+    ///     .with_rate_limit(Duration::from_millis(250));
+    /// ```
+    #[must_use]
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.rate_limiter = Arc::new(Mutex::new(RateLimiter::new(min_interval)));
+        self
+    }
+
     /// Look up a company's CIK number from its ticker symbol.
     ///
     /// # Arguments
@@ -620,6 +640,13 @@ impl DataProvider for EdgarProvider {
     fn supported_frequencies(&self) -> &[DataFrequency] {
         &[DataFrequency::Quarterly, DataFrequency::Annual]
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        // EDGAR only covers SEC registrants, which trade without a suffix
+        ProviderCapabilities::new(self.supported_frequencies())
+            .with_asset_classes(&[AssetClass::Equity])
+            .with_exchanges(&[""])
+    }
 }
 
 #[async_trait]
@@ -697,6 +724,7 @@ impl FundamentalDataProvider for EdgarProvider {
         }
 
         // Sort by period end date (most recent first)
+        #[allow(clippy::unnecessary_sort_by)]
         statements.sort_by(|a, b| b.period_end.cmp(&a.period_end));
 
         // Apply limit if specified
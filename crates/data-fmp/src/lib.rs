@@ -35,11 +35,11 @@
 //! ```
 
 use async_trait::async_trait;
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use data_core::{
-    CompanyInfo, DataError, DataFrequency, DataProvider, FinancialStatement,
-    FundamentalDataProvider, KeyMetrics, PeriodType, PriceDataProvider, ReferenceDataProvider,
-    Result, Symbol,
+    AssetClass, CompanyInfo, DataError, DataFrequency, DataProvider, FinancialStatement,
+    FundamentalDataProvider, KeyMetrics, PeriodType, PriceDataProvider, ProviderCapabilities,
+    ReferenceDataProvider, Result, Symbol,
 };
 use polars::prelude::*;
 use reqwest::Client;
@@ -257,6 +257,12 @@ impl DataProvider for FmpProvider {
     fn supported_frequencies(&self) -> &[DataFrequency] {
         SUPPORTED_FREQUENCIES
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::new(SUPPORTED_FREQUENCIES)
+            .with_asset_classes(&[AssetClass::Equity, AssetClass::Etf])
+            .with_universes(&["sp500", "nasdaq100", "dowjones"])
+    }
 }
 
 #[async_trait]
@@ -288,42 +294,7 @@ impl PriceDataProvider for FmpProvider {
             });
         }
 
-        // Convert to DataFrame
-        let dates: Vec<String> = prices.iter().map(|p| p.date.clone()).collect();
-        let opens: Vec<f64> = prices.iter().map(|p| p.open).collect();
-        let highs: Vec<f64> = prices.iter().map(|p| p.high).collect();
-        let lows: Vec<f64> = prices.iter().map(|p| p.low).collect();
-        let closes: Vec<f64> = prices.iter().map(|p| p.close).collect();
-        let adj_closes: Vec<f64> = prices.iter().map(|p| p.adj_close).collect();
-        let volumes: Vec<f64> = prices.iter().map(|p| p.volume).collect();
-
-        // Parse dates to NaiveDate
-        let parsed_dates: Vec<i32> = dates
-            .iter()
-            .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
-            .map(|d| d.num_days_from_ce())
-            .collect();
-
-        let df = DataFrame::new(vec![
-            Column::new("date".into(), parsed_dates),
-            Column::new("open".into(), opens),
-            Column::new("high".into(), highs),
-            Column::new("low".into(), lows),
-            Column::new("close".into(), closes),
-            Column::new("adj_close".into(), adj_closes),
-            Column::new("volume".into(), volumes),
-        ])
-        .map_err(|e| DataError::Parse(e.to_string()))?;
-
-        // Cast date column to proper date type and sort
-        let df = df
-            .lazy()
-            .with_column(col("date").cast(DataType::Date))
-            .sort(["date"], Default::default())
-            .collect()
-            .map_err(|e| DataError::Parse(e.to_string()))?;
-
-        Ok(df)
+        prices_to_frame(prices)
     }
 }
 
@@ -622,6 +593,54 @@ struct FmpHistoricalPrice {
     volume: f64,
 }
 
+/// Converts FMP historical prices into an OHLCV frame sorted by date.
+fn prices_to_frame(prices: Vec<FmpHistoricalPrice>) -> Result<DataFrame> {
+    // Polars dates count days since the Unix epoch
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+    // Parse dates, skipping whole rows whose date is invalid so every
+    // column stays aligned
+    let (parsed_dates, prices): (Vec<i32>, Vec<_>) = prices
+        .into_iter()
+        .filter_map(|p| match NaiveDate::parse_from_str(&p.date, "%Y-%m-%d") {
+            Ok(date) => Some(((date - epoch).num_days() as i32, p)),
+            Err(e) => {
+                tracing::warn!("Skipping FMP price with invalid date {}: {}", p.date, e);
+                None
+            }
+        })
+        .unzip();
+
+    // Convert to DataFrame
+    let opens: Vec<f64> = prices.iter().map(|p| p.open).collect();
+    let highs: Vec<f64> = prices.iter().map(|p| p.high).collect();
+    let lows: Vec<f64> = prices.iter().map(|p| p.low).collect();
+    let closes: Vec<f64> = prices.iter().map(|p| p.close).collect();
+    let adj_closes: Vec<f64> = prices.iter().map(|p| p.adj_close).collect();
+    let volumes: Vec<f64> = prices.iter().map(|p| p.volume).collect();
+
+    let df = DataFrame::new(vec![
+        Column::new("date".into(), parsed_dates),
+        Column::new("open".into(), opens),
+        Column::new("high".into(), highs),
+        Column::new("low".into(), lows),
+        Column::new("close".into(), closes),
+        Column::new("adj_close".into(), adj_closes),
+        Column::new("volume".into(), volumes),
+    ])
+    .map_err(|e| DataError::Parse(e.to_string()))?;
+
+    // Cast date column to proper date type and sort
+    let df = df
+        .lazy()
+        .with_column(col("date").cast(DataType::Date))
+        .sort(["date"], Default::default())
+        .collect()
+        .map_err(|e| DataError::Parse(e.to_string()))?;
+
+    Ok(df)
+}
+
 /// FMP Index Constituent response.
 #[derive(Debug, Clone, Deserialize)]
 struct FmpConstituent {
@@ -660,4 +679,29 @@ mod tests {
         assert!(!debug_str.contains("secret_key_12345"));
         assert!(debug_str.contains("[REDACTED]"));
     }
+
+    #[test]
+    fn test_prices_to_frame_parses_dates() {
+        let price = |date: &str| FmpHistoricalPrice {
+            date: date.to_string(),
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            adj_close: 1.0,
+            volume: 100.0,
+        };
+        let df =
+            prices_to_frame(vec![price("2024-01-03"), price("bad"), price("2024-01-02")]).unwrap();
+
+        assert_eq!(df.height(), 2);
+        let dates = df.column("date").unwrap().date().unwrap();
+        assert_eq!(
+            dates.as_date_iter().collect::<Vec<_>>(),
+            [
+                NaiveDate::from_ymd_opt(2024, 1, 2),
+                NaiveDate::from_ymd_opt(2024, 1, 3)
+            ]
+        );
+    }
 }
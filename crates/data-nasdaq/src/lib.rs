@@ -32,8 +32,8 @@ use std::pin::Pin;
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
 use data_core::{
-    DataError, DataFrequency, DataProvider, PriceDataProvider, Result, Symbol, Tick,
-    TickDataProvider,
+    AssetClass, DataError, DataFrequency, DataProvider, PriceDataProvider, ProviderCapabilities,
+    Result, Symbol, Tick, TickDataProvider,
 };
 use futures::Stream;
 use polars::prelude::DataFrame;
@@ -100,6 +100,13 @@ impl DataProvider for NasdaqProvider {
             DataFrequency::Daily,
         ]
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        // NASDAQ feeds only cover US-listed equities and ETFs
+        ProviderCapabilities::new(self.supported_frequencies())
+            .with_asset_classes(&[AssetClass::Equity, AssetClass::Etf])
+            .with_exchanges(&[""])
+    }
 }
 
 #[async_trait]
@@ -43,8 +43,8 @@ use std::time::Duration;
 use async_trait::async_trait;
 use chrono::{NaiveDate, TimeZone, Utc};
 use data_core::{
-    CompanyInfo, DataError, DataFrequency, DataProvider, PriceDataProvider, ReferenceDataProvider,
-    Result, Symbol,
+    AssetClass, CompanyInfo, DataError, DataFrequency, DataProvider, PriceDataProvider,
+    ProviderCapabilities, ReferenceDataProvider, Result, Symbol,
 };
 use polars::prelude::*;
 use serde::Deserialize;
@@ -326,6 +326,25 @@ impl DataProvider for YahooProvider {
             DataFrequency::Monthly,
         ]
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        // Yahoo only serves recent intraday history
+        ProviderCapabilities::new(self.supported_frequencies())
+            .with_max_history(DataFrequency::Minute, 30)
+            .with_max_history(DataFrequency::FiveMinute, 60)
+            .with_max_history(DataFrequency::FifteenMinute, 60)
+            .with_max_history(DataFrequency::ThirtyMinute, 60)
+            .with_max_history(DataFrequency::Hourly, 730)
+            .with_asset_classes(&[
+                AssetClass::Equity,
+                AssetClass::Etf,
+                AssetClass::Fund,
+                AssetClass::Index,
+                AssetClass::Fx,
+                AssetClass::Crypto,
+                AssetClass::Future,
+            ])
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,130 @@
+//! Result types for multi-symbol batch fetches.
+
+use std::collections::HashMap;
+
+use polars::prelude::DataFrame;
+
+use data_core::{DataError, Symbol};
+
+/// Outcome of fetching a single symbol as part of a batch.
+#[derive(Debug)]
+pub struct SymbolOutcome {
+    /// Name of the provider that served the data, if any succeeded.
+    pub provider: Option<String>,
+    /// Whether the data was served from the cache.
+    pub cache_hit: bool,
+    /// Number of rows contributed to the combined frame.
+    pub rows: usize,
+    /// The last error encountered, if every provider failed.
+    pub error: Option<DataError>,
+}
+
+impl SymbolOutcome {
+    /// Creates a successful outcome.
+    #[must_use]
+    pub fn success(provider: impl Into<String>, cache_hit: bool, rows: usize) -> Self {
+        Self {
+            provider: Some(provider.into()),
+            cache_hit,
+            rows,
+            error: None,
+        }
+    }
+
+    /// Creates a failed outcome.
+    #[must_use]
+    pub const fn failure(error: DataError) -> Self {
+        Self {
+            provider: None,
+            cache_hit: false,
+            rows: 0,
+            error: Some(error),
+        }
+    }
+
+    /// Returns true if data was fetched for this symbol.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Result of a batch OHLCV fetch.
+///
+/// Contains the combined frame for every symbol that succeeded, plus a
+/// per-symbol outcome describing where the data came from or why it failed.
+#[derive(Debug)]
+pub struct BatchResult {
+    /// Combined OHLCV data for all successful symbols, with a `symbol` column.
+    pub data: DataFrame,
+    /// Per-symbol fetch outcomes.
+    pub outcomes: HashMap<Symbol, SymbolOutcome>,
+}
+
+impl BatchResult {
+    /// Returns the outcome for a symbol, if it was part of the batch.
+    #[must_use]
+    pub fn outcome(&self, symbol: &Symbol) -> Option<&SymbolOutcome> {
+        self.outcomes.get(symbol)
+    }
+
+    /// Returns an iterator over symbols that were fetched successfully.
+    pub fn succeeded(&self) -> impl Iterator<Item = &Symbol> {
+        self.outcomes
+            .iter()
+            .filter(|(_, o)| o.is_success())
+            .map(|(s, _)| s)
+    }
+
+    /// Returns an iterator over symbols that failed, with their errors.
+    pub fn failed(&self) -> impl Iterator<Item = (&Symbol, &DataError)> {
+        self.outcomes
+            .iter()
+            .filter_map(|(s, o)| o.error.as_ref().map(|e| (s, e)))
+    }
+
+    /// Returns true if every symbol in the batch was fetched.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.outcomes.values().all(SymbolOutcome::is_success)
+    }
+
+    /// Consumes the result and returns the combined frame.
+    #[must_use]
+    pub fn into_data(self) -> DataFrame {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_result_partitions_outcomes() {
+        let mut outcomes = HashMap::new();
+        outcomes.insert(
+            Symbol::new("AAPL"),
+            SymbolOutcome::success("Yahoo Finance", false, 10),
+        );
+        outcomes.insert(
+            Symbol::new("ZZZZ"),
+            SymbolOutcome::failure(DataError::SymbolNotFound("ZZZZ".to_string())),
+        );
+
+        let result = BatchResult {
+            data: DataFrame::empty(),
+            outcomes,
+        };
+
+        assert!(!result.is_complete());
+        assert_eq!(
+            result.succeeded().collect::<Vec<_>>(),
+            [&Symbol::new("AAPL")]
+        );
+        let failed: Vec<_> = result.failed().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, &Symbol::new("ZZZZ"));
+        assert!(matches!(failed[0].1, DataError::SymbolNotFound(_)));
+    }
+}
@@ -0,0 +1,113 @@
+//! Single-flight coalescing of concurrent identical requests.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio::sync::watch;
+use tracing::debug;
+
+/// Shares one in-flight call among concurrent callers with the same key.
+///
+/// The first caller for a key runs the call; callers arriving while it is in
+/// flight wait for its result instead of starting their own. If the running
+/// call is cancelled, one of the waiters takes over.
+pub(crate) struct SingleFlight<T> {
+    calls: Mutex<HashMap<String, watch::Receiver<Option<T>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// Run `call`, or wait for the result of an identical call already in
+    /// flight.
+    pub(crate) async fn run(&self, key: String, call: impl Future<Output = T>) -> T {
+        loop {
+            let waiting = {
+                let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+                match calls.get(&key) {
+                    Some(receiver) => Err(receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        calls.insert(key.clone(), receiver);
+                        Ok(sender)
+                    }
+                }
+            };
+
+            match waiting {
+                Ok(sender) => {
+                    let _in_flight = InFlight {
+                        calls: &self.calls,
+                        key: &key,
+                    };
+                    let value = call.await;
+                    sender.send_replace(Some(value.clone()));
+                    return value;
+                }
+                Err(mut receiver) => {
+                    debug!(key = %key, "Joining in-flight request");
+                    if let Ok(value) = receiver.wait_for(Option::is_some).await {
+                        if let Some(value) = value.as_ref() {
+                            return value.clone();
+                        }
+                    }
+                    // The running call was cancelled; try again
+                }
+            }
+        }
+    }
+}
+
+/// Forgets an in-flight call when it finishes or is cancelled.
+struct InFlight<'a, T> {
+    calls: &'a Mutex<HashMap<String, watch::Receiver<Option<T>>>>,
+    key: &'a str,
+}
+
+impl<T> Drop for InFlight<'_, T> {
+    fn drop(&mut self) {
+        self.calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancelled_call_is_taken_over() {
+        let flights = Arc::new(SingleFlight::<usize>::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let call = |calls: Arc<AtomicUsize>| async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            calls.fetch_add(1, Ordering::SeqCst) + 1
+        };
+
+        let leader = tokio::spawn({
+            let (flights, calls) = (Arc::clone(&flights), Arc::clone(&calls));
+            async move { flights.run("key".to_string(), call(calls)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let follower = tokio::spawn({
+            let (flights, calls) = (Arc::clone(&flights), Arc::clone(&calls));
+            async move { flights.run("key".to_string(), call(calls)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        leader.abort();
+
+        assert_eq!(follower.await.unwrap(), 1);
+        assert!(flights.calls.lock().unwrap().is_empty());
+    }
+}
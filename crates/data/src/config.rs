@@ -0,0 +1,1251 @@
+//! Declarative registry configuration.
+//!
+//! A [`RegistryConfig`] describes a complete [`DataProviderRegistry`] setup —
+//! providers and their order, credentials, cache backend, TTLs, rate limits,
+//! circuit breaker settings, fallback mode, OHLCV validation and routing rules
+//! — and can be loaded from TOML or YAML so provider setups can change without
+//! recompiling.
+//!
+//! ```toml
+//! [[providers]]
+//! kind = "yahoo"
+//! rate_limit_ms = 500
+//!
+//! [[providers]]
+//! kind = "fmp"
+//! api_key = { env = "FMP_API_KEY" }
+//! roles = ["fundamental", "reference"]
+//!
+//! [[providers]]
+//! kind = "edgar"
+//! user_agent = "MyApp/1.0 (contact@example.com)"
+//!
+//! [cache]
+//! backend = "sqlite"
+//! path = "/var/cache/data/cache.db"
+//! ttl_secs = 86400
+//! max_bytes = 268435456
+//!
+//! [cache.freshness]
+//! recent_ohlcv_ttl_secs = 900
+//! financials_ttl_secs = 43200
+//! stale_while_revalidate_secs = 3600
+//! negative_ttl_secs = 600
+//!
+//! [health]
+//! failure_threshold = 3
+//! open_duration_secs = 60
+//!
+//! [fallback]
+//! mode = "hedged"
+//! hedge_delay_ms = 250
+//!
+//! [validation]
+//! action = "warn"
+//! max_jump = 0.3
+//!
+//! [validation.checks]
+//! missing_prices = "fill"
+//! ohlc_consistency = "drop"
+//! calendar_gaps = "off"
+//!
+//! [[routing]]
+//! pattern = "*.L"
+//! providers = ["FMP"]
+//! mode = "only"
+//! ```
+//!
+//! Credentials may be given inline, as `{ env = "VAR" }` to read an
+//! environment variable, or as `{ file = "/run/secrets/key" }` to read a secret
+//! file. Validation errors name the offending key, e.g.
+//! `providers[1].api_key: environment variable FMP_API_KEY is not set`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+#[cfg(any(
+    feature = "yahoo",
+    feature = "edgar",
+    feature = "fmp",
+    feature = "nasdaq",
+    feature = "ibkr"
+))]
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use data_cache::EvictionPolicy;
+use data_core::{CacheDataKind, DataCache, DataError, Result};
+
+use crate::freshness::{CachePolicy, FreshnessPolicy};
+use crate::health::HealthConfig;
+use crate::registry::DataProviderRegistry;
+use crate::routing::{FallbackMode, RoutingMode, RoutingRule};
+use crate::validation::{QualityCheck, ValidationAction, ValidationPolicy};
+
+/// Complete registry configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// Providers, in the order they should be tried.
+    #[serde(default)]
+    pub providers: Vec<ProviderConfig>,
+    /// Cache backend settings.
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+    /// Circuit breaker settings.
+    #[serde(default)]
+    pub health: Option<HealthSettings>,
+    /// How providers are moved through when one is slow or fails.
+    #[serde(default)]
+    pub fallback: Option<FallbackSettings>,
+    /// OHLCV data-quality checks.
+    #[serde(default)]
+    pub validation: Option<ValidationSettings>,
+    /// Routing rules, evaluated in order.
+    #[serde(default)]
+    pub routing: Vec<RoutingRuleConfig>,
+}
+
+/// Kind of data provider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// Yahoo Finance (`yahoo` feature).
+    Yahoo,
+    /// SEC EDGAR (`edgar` feature).
+    Edgar,
+    /// Financial Modeling Prep (`fmp` feature).
+    Fmp,
+    /// NASDAQ (`nasdaq` feature).
+    Nasdaq,
+    /// Interactive Brokers (`ibkr` feature).
+    Ibkr,
+}
+
+impl ProviderKind {
+    /// Configuration name of the provider kind.
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Yahoo => "yahoo",
+            Self::Edgar => "edgar",
+            Self::Fmp => "fmp",
+            Self::Nasdaq => "nasdaq",
+            Self::Ibkr => "ibkr",
+        }
+    }
+
+    /// Roles the provider can fill.
+    const fn roles(self) -> &'static [ProviderRole] {
+        match self {
+            Self::Yahoo => &[ProviderRole::Price, ProviderRole::Reference],
+            Self::Edgar => &[ProviderRole::Fundamental, ProviderRole::Reference],
+            Self::Fmp => &[
+                ProviderRole::Price,
+                ProviderRole::Fundamental,
+                ProviderRole::Reference,
+            ],
+            Self::Nasdaq => &[ProviderRole::Tick],
+            Self::Ibkr => &[
+                ProviderRole::Price,
+                ProviderRole::Tick,
+                ProviderRole::Fundamental,
+            ],
+        }
+    }
+}
+
+/// Kind of data a provider is registered for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderRole {
+    /// OHLCV price data.
+    Price,
+    /// Financial statements and metrics.
+    Fundamental,
+    /// Tick data.
+    Tick,
+    /// Company information and universes.
+    Reference,
+}
+
+/// Configuration for a single provider.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderConfig {
+    /// Provider kind.
+    pub kind: ProviderKind,
+    /// Roles to register the provider for; defaults to every role it supports.
+    #[serde(default)]
+    pub roles: Option<Vec<ProviderRole>>,
+    /// API key (FMP, NASDAQ).
+    #[serde(default)]
+    pub api_key: Option<Secret>,
+    /// User agent identifying the application (EDGAR).
+    #[serde(default)]
+    pub user_agent: Option<Secret>,
+    /// Gateway host (IBKR).
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Gateway port (IBKR).
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Minimum interval between requests in milliseconds (Yahoo, EDGAR).
+    #[serde(default)]
+    pub rate_limit_ms: Option<u64>,
+}
+
+/// A configuration value that may be inline or read from the environment or a file.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Secret {
+    /// The value itself.
+    Value(String),
+    /// Read from an environment variable.
+    Env {
+        /// Environment variable name.
+        env: String,
+    },
+    /// Read from a file, with surrounding whitespace trimmed.
+    File {
+        /// Path to the secret file.
+        file: PathBuf,
+    },
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Value(_) => f.write_str("Value([REDACTED])"),
+            Self::Env { env } => f.debug_struct("Env").field("env", env).finish(),
+            Self::File { file } => f.debug_struct("File").field("file", file).finish(),
+        }
+    }
+}
+
+impl Secret {
+    /// Resolve the secret, reporting failures against `key`.
+    pub fn resolve(&self, key: &str) -> Result<String> {
+        let value = match self {
+            Self::Value(value) => value.clone(),
+            Self::Env { env } => std::env::var(env)
+                .map_err(|_| invalid(key, format!("environment variable {env} is not set")))?,
+            Self::File { file } => std::fs::read_to_string(file)
+                .map_err(|e| invalid(key, format!("cannot read {}: {e}", file.display())))?
+                .trim()
+                .to_string(),
+        };
+        if value.is_empty() {
+            return Err(invalid(key, "value is empty"));
+        }
+        Ok(value)
+    }
+}
+
+/// Cache backend kind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// Persistent SQLite cache (`cache-sqlite` feature).
+    Sqlite,
+    /// Partitioned Parquet files under a directory.
+    Parquet,
+    /// In-memory cache.
+    Memory,
+    /// No caching.
+    #[default]
+    None,
+}
+
+/// Cache configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Cache backend.
+    #[serde(default)]
+    pub backend: CacheBackend,
+    /// Database path (SQLite) or root directory (Parquet).
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Entries older than this are purged when the registry is built.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+    /// Byte budget for the memory backend. For the sqlite and parquet
+    /// backends, adds a memory tier with this budget in front of the backend.
+    /// Unbounded (memory) or no memory tier (sqlite, parquet) if unset.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+    /// Eviction policy for the bounded memory cache.
+    #[serde(default)]
+    pub eviction: Option<EvictionPolicyConfig>,
+    /// How long each kind of cached data is served.
+    #[serde(default)]
+    pub freshness: Option<FreshnessSettings>,
+    /// Keep every stored version for point-in-time reads (sqlite backend).
+    #[serde(default)]
+    pub bitemporal: bool,
+    /// Whether requests read from and write to the cache.
+    #[serde(default)]
+    pub policy: CachePolicyConfig,
+}
+
+/// Cache freshness configuration; unset fields use [`FreshnessPolicy`]
+/// defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FreshnessSettings {
+    /// TTL for OHLCV bars older than the recent window.
+    #[serde(default)]
+    pub ohlcv_ttl_secs: Option<u64>,
+    /// TTL for OHLCV bars within the recent window.
+    #[serde(default)]
+    pub recent_ohlcv_ttl_secs: Option<u64>,
+    /// Number of days before today treated as recent.
+    #[serde(default)]
+    pub recent_window_days: Option<u32>,
+    /// TTL for ticks.
+    #[serde(default)]
+    pub ticks_ttl_secs: Option<u64>,
+    /// TTL for financial statements.
+    #[serde(default)]
+    pub financials_ttl_secs: Option<u64>,
+    /// TTL for key metrics.
+    #[serde(default)]
+    pub metrics_ttl_secs: Option<u64>,
+    /// TTL for company information.
+    #[serde(default)]
+    pub company_info_ttl_secs: Option<u64>,
+    /// TTL for universe snapshots.
+    #[serde(default)]
+    pub universe_ttl_secs: Option<u64>,
+    /// Seconds past its TTL an entry is still served while it is refreshed.
+    #[serde(default)]
+    pub stale_while_revalidate_secs: Option<u64>,
+    /// How long a provider's "not found" answer is remembered; 0 disables it.
+    #[serde(default)]
+    pub negative_ttl_secs: Option<u64>,
+}
+
+/// Cache policy names used in configuration files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CachePolicyConfig {
+    /// See [`CachePolicy::Default`].
+    #[default]
+    Default,
+    /// See [`CachePolicy::CacheOnly`].
+    CacheOnly,
+    /// See [`CachePolicy::NetworkOnly`].
+    NetworkOnly,
+    /// See [`CachePolicy::Refresh`].
+    Refresh,
+}
+
+/// Eviction policy names used in configuration files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicyConfig {
+    /// See [`EvictionPolicy::Lru`].
+    #[default]
+    Lru,
+    /// See [`EvictionPolicy::Lfu`].
+    Lfu,
+}
+
+/// Circuit breaker configuration; unset fields use [`HealthConfig`] defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HealthSettings {
+    /// Number of recent calls used for rolling statistics.
+    #[serde(default)]
+    pub window: Option<usize>,
+    /// Consecutive failures that open the circuit.
+    #[serde(default)]
+    pub failure_threshold: Option<u32>,
+    /// Rolling error rate (0.0 to 1.0) that opens the circuit.
+    #[serde(default)]
+    pub error_rate_threshold: Option<f64>,
+    /// Minimum calls before the error rate is considered.
+    #[serde(default)]
+    pub min_samples: Option<usize>,
+    /// Seconds an open circuit waits before probing.
+    #[serde(default)]
+    pub open_duration_secs: Option<u64>,
+}
+
+/// Provider fallback configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FallbackSettings {
+    /// Whether providers are tried in turn, hedged or raced.
+    #[serde(default)]
+    pub mode: FallbackModeConfig,
+    /// Milliseconds to wait before hedging; required for `hedged`.
+    #[serde(default)]
+    pub hedge_delay_ms: Option<u64>,
+}
+
+/// Fallback mode names used in configuration files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FallbackModeConfig {
+    /// See [`FallbackMode::Sequential`].
+    #[default]
+    Sequential,
+    /// See [`FallbackMode::Hedged`].
+    Hedged,
+    /// See [`FallbackMode::Race`].
+    Race,
+}
+
+/// OHLCV validation configuration; unset fields use [`ValidationPolicy`]
+/// defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidationSettings {
+    /// Action for checks not listed in `checks`.
+    #[serde(default)]
+    pub action: ValidationActionConfig,
+    /// Per-check actions.
+    #[serde(default)]
+    pub checks: BTreeMap<QualityCheckConfig, ValidationActionConfig>,
+    /// Consecutive missing weekdays allowed between daily bars.
+    #[serde(default)]
+    pub max_gap_weekdays: Option<usize>,
+    /// Consecutive bars with the same close that count as stale.
+    #[serde(default)]
+    pub stale_bars: Option<usize>,
+    /// Largest one-bar move, as a fraction of the neighbouring closes.
+    #[serde(default)]
+    pub max_jump: Option<f64>,
+}
+
+/// Quality check names used in configuration files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityCheckConfig {
+    /// See [`QualityCheck::UnsortedDates`].
+    UnsortedDates,
+    /// See [`QualityCheck::DuplicateDates`].
+    DuplicateDates,
+    /// See [`QualityCheck::MissingPrices`].
+    MissingPrices,
+    /// See [`QualityCheck::OhlcConsistency`].
+    OhlcConsistency,
+    /// See [`QualityCheck::NegativeVolume`].
+    NegativeVolume,
+    /// See [`QualityCheck::JumpOutliers`].
+    JumpOutliers,
+    /// See [`QualityCheck::StalePrices`].
+    StalePrices,
+    /// See [`QualityCheck::CalendarGaps`].
+    CalendarGaps,
+}
+
+/// Validation action names used in configuration files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationActionConfig {
+    /// See [`ValidationAction::Warn`].
+    #[default]
+    Warn,
+    /// See [`ValidationAction::Drop`].
+    Drop,
+    /// See [`ValidationAction::Fill`].
+    Fill,
+    /// See [`ValidationAction::Error`].
+    Error,
+    /// Disable the check.
+    Off,
+}
+
+/// Routing mode names used in configuration files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingModeConfig {
+    /// See [`RoutingMode::Prefer`].
+    #[default]
+    Prefer,
+    /// See [`RoutingMode::Only`].
+    Only,
+}
+
+/// A routing rule.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingRuleConfig {
+    /// Symbol pattern, with `*` wildcards.
+    pub pattern: String,
+    /// Provider names, as reported by [`DataProvider::name`](data_core::DataProvider::name).
+    pub providers: Vec<String>,
+    /// Whether the providers are preferred or exclusive.
+    #[serde(default)]
+    pub mode: RoutingModeConfig,
+}
+
+impl RegistryConfig {
+    /// Parse a configuration from TOML.
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        toml::from_str(s).map_err(|e| DataError::InvalidParameter(e.to_string()))
+    }
+
+    /// Parse a configuration from YAML.
+    pub fn from_yaml_str(s: &str) -> Result<Self> {
+        serde_yaml::from_str(s).map_err(|e| DataError::InvalidParameter(e.to_string()))
+    }
+
+    /// Load a configuration file, choosing the format from its extension
+    /// (`.toml`, `.yaml` or `.yml`).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            DataError::InvalidParameter(format!("cannot read {}: {e}", path.display()))
+        })?;
+        let parsed = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml_str(&contents),
+            Some("yaml" | "yml") => Self::from_yaml_str(&contents),
+            _ => Err(DataError::InvalidParameter(
+                "unrecognized extension; expected .toml, .yaml or .yml".to_string(),
+            )),
+        };
+        parsed.map_err(|e| match e {
+            DataError::InvalidParameter(msg) => {
+                DataError::InvalidParameter(format!("{}: {msg}", path.display()))
+            }
+            other => other,
+        })
+    }
+
+    /// Check the configuration without building anything.
+    ///
+    /// Resolves every credential and verifies that each provider and cache
+    /// backend is enabled and fully specified.
+    pub fn validate(&self) -> Result<()> {
+        for (i, provider) in self.providers.iter().enumerate() {
+            provider.validate(&format!("providers[{i}]"))?;
+        }
+        if let Some(cache) = &self.cache {
+            cache.validate()?;
+        }
+        if let Some(health) = &self.health {
+            if let Some(rate) = health.error_rate_threshold {
+                if !(0.0..=1.0).contains(&rate) {
+                    return Err(invalid(
+                        "health.error_rate_threshold",
+                        "must be between 0.0 and 1.0",
+                    ));
+                }
+            }
+        }
+        if let Some(fallback) = &self.fallback {
+            fallback.to_mode()?;
+        }
+        if let Some(validation) = &self.validation {
+            if validation.max_jump.is_some_and(|jump| jump <= 0.0) {
+                return Err(invalid("validation.max_jump", "must be greater than 0"));
+            }
+            if validation.stale_bars.is_some_and(|bars| bars < 2) {
+                return Err(invalid("validation.stale_bars", "must be at least 2"));
+            }
+        }
+        for (i, rule) in self.routing.iter().enumerate() {
+            if rule.pattern.is_empty() {
+                return Err(invalid(
+                    &format!("routing[{i}].pattern"),
+                    "must not be empty",
+                ));
+            }
+            if rule.providers.is_empty() {
+                return Err(invalid(
+                    &format!("routing[{i}].providers"),
+                    "must name at least one provider",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a registry from this configuration.
+    ///
+    /// If `cache.ttl_secs` is set, entries older than the TTL are purged from
+    /// the cache before the registry is returned.
+    pub async fn build(&self) -> Result<DataProviderRegistry> {
+        self.validate()?;
+
+        let mut registry = DataProviderRegistry::new();
+
+        if let Some(cache_config) = &self.cache {
+            if let Some(cache) = cache_config.open()? {
+                if let Some(ttl) = cache_config.ttl_secs {
+                    cache.invalidate_stale(Duration::from_secs(ttl)).await?;
+                }
+                registry = registry.set_cache(cache);
+            }
+            if let Some(freshness) = &cache_config.freshness {
+                registry = registry.with_freshness_policy(freshness.to_policy());
+            }
+            registry = registry.with_cache_policy(match cache_config.policy {
+                CachePolicyConfig::Default => CachePolicy::Default,
+                CachePolicyConfig::CacheOnly => CachePolicy::CacheOnly,
+                CachePolicyConfig::NetworkOnly => CachePolicy::NetworkOnly,
+                CachePolicyConfig::Refresh => CachePolicy::Refresh,
+            });
+        }
+
+        if let Some(health) = &self.health {
+            registry = registry.with_health_config(health.to_config());
+        }
+
+        if let Some(fallback) = &self.fallback {
+            registry = registry.with_fallback_mode(fallback.to_mode()?);
+        }
+
+        if let Some(validation) = &self.validation {
+            registry = registry.with_validation(validation.to_policy());
+        }
+
+        for (i, provider) in self.providers.iter().enumerate() {
+            provider.register(&mut registry, &format!("providers[{i}]"))?;
+        }
+
+        for rule in &self.routing {
+            let mode = match rule.mode {
+                RoutingModeConfig::Prefer => RoutingMode::Prefer,
+                RoutingModeConfig::Only => RoutingMode::Only,
+            };
+            registry.add_routing_rule(RoutingRule::new(
+                rule.pattern.clone(),
+                rule.providers.iter().cloned(),
+                mode,
+            ));
+        }
+
+        Ok(registry)
+    }
+}
+
+impl ProviderConfig {
+    /// Roles to register, validated against what the provider supports.
+    fn roles(&self, key: &str) -> Result<Vec<ProviderRole>> {
+        let supported = self.kind.roles();
+        let Some(roles) = &self.roles else {
+            return Ok(supported.to_vec());
+        };
+        if roles.is_empty() {
+            return Err(invalid(&format!("{key}.roles"), "must not be empty"));
+        }
+        for role in roles {
+            if !supported.contains(role) {
+                return Err(invalid(
+                    &format!("{key}.roles"),
+                    format!("{} does not provide {role:?} data", self.kind.as_str()),
+                ));
+            }
+        }
+        Ok(roles.clone())
+    }
+
+    fn validate(&self, key: &str) -> Result<()> {
+        self.roles(key)?;
+
+        let allowed: &[&str] = match self.kind {
+            ProviderKind::Yahoo => &["rate_limit_ms"],
+            ProviderKind::Edgar => &["user_agent", "rate_limit_ms"],
+            ProviderKind::Fmp | ProviderKind::Nasdaq => &["api_key"],
+            ProviderKind::Ibkr => &["host", "port"],
+        };
+        let set = [
+            ("api_key", self.api_key.is_some()),
+            ("user_agent", self.user_agent.is_some()),
+            ("host", self.host.is_some()),
+            ("port", self.port.is_some()),
+            ("rate_limit_ms", self.rate_limit_ms.is_some()),
+        ];
+        for (field, present) in set {
+            if present && !allowed.contains(&field) {
+                return Err(invalid(
+                    &format!("{key}.{field}"),
+                    format!("not supported by {}", self.kind.as_str()),
+                ));
+            }
+        }
+
+        match self.kind {
+            ProviderKind::Edgar => {
+                required(&self.user_agent, key, "user_agent")?;
+            }
+            ProviderKind::Fmp | ProviderKind::Nasdaq => {
+                required(&self.api_key, key, "api_key")?;
+            }
+            ProviderKind::Ibkr => {
+                required(&self.host, key, "host")?;
+                required(&self.port, key, "port")?;
+            }
+            ProviderKind::Yahoo => {}
+        }
+        for (field, secret) in [("api_key", &self.api_key), ("user_agent", &self.user_agent)] {
+            if let Some(secret) = secret {
+                secret.resolve(&format!("{key}.{field}"))?;
+            }
+        }
+
+        if !self.kind_enabled() {
+            return Err(invalid(
+                &format!("{key}.kind"),
+                format!(
+                    "provider {0} requires the `{0}` feature",
+                    self.kind.as_str()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the provider's crate feature is enabled.
+    const fn kind_enabled(&self) -> bool {
+        match self.kind {
+            ProviderKind::Yahoo => cfg!(feature = "yahoo"),
+            ProviderKind::Edgar => cfg!(feature = "edgar"),
+            ProviderKind::Fmp => cfg!(feature = "fmp"),
+            ProviderKind::Nasdaq => cfg!(feature = "nasdaq"),
+            ProviderKind::Ibkr => cfg!(feature = "ibkr"),
+        }
+    }
+
+    /// Construct the provider and register it for its roles.
+    ///
+    /// Must only be called after [`validate`](Self::validate) succeeded.
+    #[allow(unused_variables)]
+    fn register(&self, registry: &mut DataProviderRegistry, key: &str) -> Result<()> {
+        let roles = self.roles(key)?;
+        let has = |role| roles.contains(&role);
+
+        match self.kind {
+            #[cfg(feature = "yahoo")]
+            ProviderKind::Yahoo => {
+                let provider = Arc::new(match self.rate_limit_ms {
+                    Some(ms) => {
+                        data_yahoo::YahooProvider::with_rate_limit(Duration::from_millis(ms))
+                    }
+                    None => data_yahoo::YahooProvider::new(),
+                });
+                if has(ProviderRole::Price) {
+                    registry.register_price(provider.clone());
+                }
+                if has(ProviderRole::Reference) {
+                    registry.register_reference(provider);
+                }
+            }
+            #[cfg(feature = "edgar")]
+            ProviderKind::Edgar => {
+                let user_agent = required(&self.user_agent, key, "user_agent")?
+                    .resolve(&format!("{key}.user_agent"))?;
+                let mut provider = data_edgar::EdgarProvider::new(&user_agent);
+                if let Some(ms) = self.rate_limit_ms {
+                    provider = provider.with_rate_limit(Duration::from_millis(ms));
+                }
+                let provider = Arc::new(provider);
+                if has(ProviderRole::Fundamental) {
+                    registry.register_fundamental(provider.clone());
+                }
+                if has(ProviderRole::Reference) {
+                    registry.register_reference(provider);
+                }
+            }
+            #[cfg(feature = "fmp")]
+            ProviderKind::Fmp => {
+                let api_key =
+                    required(&self.api_key, key, "api_key")?.resolve(&format!("{key}.api_key"))?;
+                let provider = Arc::new(data_fmp::FmpProvider::new(api_key));
+                if has(ProviderRole::Price) {
+                    registry.register_price(provider.clone());
+                }
+                if has(ProviderRole::Fundamental) {
+                    registry.register_fundamental(provider.clone());
+                }
+                if has(ProviderRole::Reference) {
+                    registry.register_reference(provider);
+                }
+            }
+            #[cfg(feature = "nasdaq")]
+            ProviderKind::Nasdaq => {
+                let api_key =
+                    required(&self.api_key, key, "api_key")?.resolve(&format!("{key}.api_key"))?;
+                registry.register_tick(Arc::new(data_nasdaq::NasdaqProvider::new(api_key)));
+            }
+            #[cfg(feature = "ibkr")]
+            ProviderKind::Ibkr => {
+                let host = required(&self.host, key, "host")?;
+                let port = *required(&self.port, key, "port")?;
+                let provider = Arc::new(data_ibkr::IbkrProvider::new(host, port));
+                if has(ProviderRole::Price) {
+                    registry.register_price(provider.clone());
+                }
+                if has(ProviderRole::Tick) {
+                    registry.register_tick(provider.clone());
+                }
+                if has(ProviderRole::Fundamental) {
+                    registry.register_fundamental(provider);
+                }
+            }
+            // Disabled providers are rejected by `validate`
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl CacheConfig {
+    fn validate(&self) -> Result<()> {
+        match self.backend {
+            CacheBackend::Sqlite => {
+                if !cfg!(feature = "cache-sqlite") {
+                    return Err(invalid(
+                        "cache.backend",
+                        "the sqlite backend requires the `cache-sqlite` feature",
+                    ));
+                }
+                required(&self.path, "cache", "path")?;
+            }
+            CacheBackend::Parquet => {
+                required(&self.path, "cache", "path")?;
+            }
+            CacheBackend::Memory | CacheBackend::None => {
+                if self.path.is_some() {
+                    return Err(invalid(
+                        "cache.path",
+                        "only supported by the sqlite and parquet backends",
+                    ));
+                }
+            }
+        }
+        if self.backend == CacheBackend::None {
+            if self.max_bytes.is_some() {
+                return Err(invalid("cache.max_bytes", "requires a cache backend"));
+            }
+            if self.eviction.is_some() {
+                return Err(invalid("cache.eviction", "requires a cache backend"));
+            }
+        } else if self.eviction.is_some() && self.max_bytes.is_none() {
+            return Err(invalid("cache.eviction", "requires cache.max_bytes"));
+        }
+        if self.bitemporal && self.backend != CacheBackend::Sqlite {
+            return Err(invalid(
+                "cache.bitemporal",
+                "only supported by the sqlite backend",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Open the configured cache backend, behind a bounded memory tier if
+    /// `max_bytes` is set.
+    fn open(&self) -> Result<Option<std::sync::Arc<dyn DataCache>>> {
+        let persistent: std::sync::Arc<dyn DataCache> = match self.backend {
+            #[cfg(feature = "cache-sqlite")]
+            CacheBackend::Sqlite => {
+                let path = required(&self.path, "cache", "path")?;
+                let cache = data_cache::SqliteCache::new(path).map_err(|e| {
+                    invalid("cache.path", format!("cannot open {}: {e}", path.display()))
+                })?;
+                std::sync::Arc::new(cache.with_bitemporal(self.bitemporal))
+            }
+            #[cfg(not(feature = "cache-sqlite"))]
+            CacheBackend::Sqlite => {
+                return Err(invalid(
+                    "cache.backend",
+                    "the sqlite backend requires the `cache-sqlite` feature",
+                ));
+            }
+            CacheBackend::Parquet => {
+                let path = required(&self.path, "cache", "path")?;
+                let cache = data_cache::ParquetCache::new(path).map_err(|e| {
+                    invalid("cache.path", format!("cannot open {}: {e}", path.display()))
+                })?;
+                std::sync::Arc::new(cache)
+            }
+            CacheBackend::Memory => {
+                return Ok(Some(self.memory_tier().unwrap_or_else(|| {
+                    std::sync::Arc::new(data_cache::InMemoryCache::new())
+                })));
+            }
+            CacheBackend::None => return Ok(None),
+        };
+        Ok(Some(match self.memory_tier() {
+            Some(memory) => std::sync::Arc::new(
+                data_cache::TieredCache::new()
+                    .with_tier(memory)
+                    .with_tier(persistent),
+            ),
+            None => persistent,
+        }))
+    }
+
+    /// Build the bounded memory cache, if `max_bytes` is set.
+    fn memory_tier(&self) -> Option<std::sync::Arc<dyn DataCache>> {
+        let max_bytes = self.max_bytes?;
+        let policy = match self.eviction.unwrap_or_default() {
+            EvictionPolicyConfig::Lru => EvictionPolicy::Lru,
+            EvictionPolicyConfig::Lfu => EvictionPolicy::Lfu,
+        };
+        Some(std::sync::Arc::new(
+            data_cache::BoundedMemoryCache::new(max_bytes).with_eviction_policy(policy),
+        ))
+    }
+}
+
+impl HealthSettings {
+    fn to_config(&self) -> HealthConfig {
+        let defaults = HealthConfig::default();
+        HealthConfig {
+            window: self.window.unwrap_or(defaults.window),
+            failure_threshold: self.failure_threshold.unwrap_or(defaults.failure_threshold),
+            error_rate_threshold: self
+                .error_rate_threshold
+                .unwrap_or(defaults.error_rate_threshold),
+            min_samples: self.min_samples.unwrap_or(defaults.min_samples),
+            open_duration: self
+                .open_duration_secs
+                .map_or(defaults.open_duration, Duration::from_secs),
+        }
+    }
+}
+
+impl FallbackSettings {
+    fn to_mode(&self) -> Result<FallbackMode> {
+        match (self.mode, self.hedge_delay_ms) {
+            (FallbackModeConfig::Sequential, None) => Ok(FallbackMode::Sequential),
+            (FallbackModeConfig::Race, None) => Ok(FallbackMode::Race),
+            (FallbackModeConfig::Hedged, Some(ms)) => Ok(FallbackMode::Hedged {
+                delay: Duration::from_millis(ms),
+            }),
+            (FallbackModeConfig::Hedged, None) => Err(invalid(
+                "fallback.hedge_delay_ms",
+                "is required when mode is hedged",
+            )),
+            (_, Some(_)) => Err(invalid(
+                "fallback.hedge_delay_ms",
+                "only applies when mode is hedged",
+            )),
+        }
+    }
+}
+
+impl ValidationSettings {
+    fn to_policy(&self) -> ValidationPolicy {
+        let mut policy = ValidationPolicy::new();
+        if let Some(weekdays) = self.max_gap_weekdays {
+            policy = policy.with_max_gap(weekdays);
+        }
+        if let Some(bars) = self.stale_bars {
+            policy = policy.with_stale_bars(bars);
+        }
+        if let Some(jump) = self.max_jump {
+            policy = policy.with_max_jump(jump);
+        }
+        for check in QualityCheck::ALL {
+            let configured = self.checks.iter().find(|(c, _)| c.to_check() == check);
+            let action = configured.map_or(self.action, |(_, action)| *action);
+            policy = match action {
+                ValidationActionConfig::Warn => policy.with_action(check, ValidationAction::Warn),
+                ValidationActionConfig::Drop => policy.with_action(check, ValidationAction::Drop),
+                ValidationActionConfig::Fill => policy.with_action(check, ValidationAction::Fill),
+                ValidationActionConfig::Error => policy.with_action(check, ValidationAction::Error),
+                ValidationActionConfig::Off => policy.without(check),
+            };
+        }
+        policy
+    }
+}
+
+impl QualityCheckConfig {
+    const fn to_check(self) -> QualityCheck {
+        match self {
+            Self::UnsortedDates => QualityCheck::UnsortedDates,
+            Self::DuplicateDates => QualityCheck::DuplicateDates,
+            Self::MissingPrices => QualityCheck::MissingPrices,
+            Self::OhlcConsistency => QualityCheck::OhlcConsistency,
+            Self::NegativeVolume => QualityCheck::NegativeVolume,
+            Self::JumpOutliers => QualityCheck::JumpOutliers,
+            Self::StalePrices => QualityCheck::StalePrices,
+            Self::CalendarGaps => QualityCheck::CalendarGaps,
+        }
+    }
+}
+
+impl FreshnessSettings {
+    fn to_policy(&self) -> FreshnessPolicy {
+        let mut policy = FreshnessPolicy::default();
+        for (kind, ttl) in [
+            (CacheDataKind::Ohlcv, self.ohlcv_ttl_secs),
+            (CacheDataKind::Ticks, self.ticks_ttl_secs),
+            (CacheDataKind::Financials, self.financials_ttl_secs),
+            (CacheDataKind::Metrics, self.metrics_ttl_secs),
+            (CacheDataKind::CompanyInfo, self.company_info_ttl_secs),
+            (CacheDataKind::Universe, self.universe_ttl_secs),
+        ] {
+            if let Some(ttl) = ttl {
+                policy = policy.with_ttl(kind, Duration::from_secs(ttl));
+            }
+        }
+        if let Some(ttl) = self.recent_ohlcv_ttl_secs {
+            policy = policy.with_recent_ohlcv_ttl(Duration::from_secs(ttl));
+        }
+        if let Some(days) = self.recent_window_days {
+            policy = policy.with_recent_window_days(days);
+        }
+        if let Some(window) = self.stale_while_revalidate_secs {
+            policy = policy.with_stale_while_revalidate(Duration::from_secs(window));
+        }
+        if let Some(ttl) = self.negative_ttl_secs {
+            policy = policy.with_negative_ttl(Duration::from_secs(ttl));
+        }
+        policy
+    }
+}
+
+/// Build a validation error for a configuration key.
+fn invalid(key: &str, message: impl fmt::Display) -> DataError {
+    DataError::InvalidParameter(format!("{key}: {message}"))
+}
+
+/// Require an optional field to be set.
+fn required<'a, T>(value: &'a Option<T>, key: &str, field: &str) -> Result<&'a T> {
+    value
+        .as_ref()
+        .ok_or_else(|| invalid(&format!("{key}.{field}"), "is required"))
+}
+
+impl DataProviderRegistry {
+    /// Build a registry from a TOML or YAML configuration file.
+    ///
+    /// See [`RegistryConfig`] for the file format.
+    pub async fn from_config_file(path: impl AsRef<Path>) -> Result<Self> {
+        RegistryConfig::from_file(path)?.build().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_message(result: Result<impl fmt::Debug>) -> String {
+        match result.unwrap_err() {
+            DataError::InvalidParameter(msg) => msg,
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_toml_and_yaml() {
+        let toml = r#"
+            [[providers]]
+            kind = "edgar"
+            user_agent = "Test/1.0 (test@example.com)"
+            rate_limit_ms = 200
+
+            [cache]
+            backend = "memory"
+
+            [cache.freshness]
+            financials_ttl_secs = 60
+            stale_while_revalidate_secs = 30
+            negative_ttl_secs = 120
+
+            [[routing]]
+            pattern = "*.L"
+            providers = ["FMP"]
+            mode = "only"
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.providers.len(), 1);
+        assert_eq!(config.providers[0].kind, ProviderKind::Edgar);
+        assert_eq!(config.routing[0].mode, RoutingModeConfig::Only);
+        let policy = config.cache.unwrap().freshness.unwrap().to_policy();
+        assert_eq!(
+            policy.ttl(CacheDataKind::Financials),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(policy.stale_while_revalidate(), Duration::from_secs(30));
+        assert_eq!(policy.negative_ttl(), Duration::from_secs(120));
+
+        let yaml = "
+providers:
+  - kind: yahoo
+    rate_limit_ms: 500
+health:
+  failure_threshold: 3
+";
+        let config = RegistryConfig::from_yaml_str(yaml).unwrap();
+        assert_eq!(config.providers[0].rate_limit_ms, Some(500));
+        assert_eq!(config.health.unwrap().to_config().failure_threshold, 3);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let toml = r#"
+            [[providers]]
+            kind = "yahoo"
+            api_kee = "typo"
+        "#;
+        assert!(error_message(RegistryConfig::from_toml_str(toml)).contains("api_kee"));
+    }
+
+    #[cfg(all(feature = "yahoo", feature = "edgar"))]
+    #[test]
+    fn test_validation_errors_name_the_key() {
+        let toml = r#"
+            [[providers]]
+            kind = "yahoo"
+
+            [[providers]]
+            kind = "edgar"
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "providers[1].user_agent: is required"
+        );
+
+        let toml = r#"
+            [[providers]]
+            kind = "edgar"
+            user_agent = { env = "DATA_CONFIG_TEST_UNSET_VARIABLE" }
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "providers[0].user_agent: environment variable DATA_CONFIG_TEST_UNSET_VARIABLE is not set"
+        );
+
+        let toml = r#"
+            [[providers]]
+            kind = "yahoo"
+            roles = ["fundamental"]
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert!(error_message(config.validate()).starts_with("providers[0].roles:"));
+    }
+
+    #[test]
+    fn test_memory_budget_requires_backend() {
+        let toml = r#"
+            [cache]
+            max_bytes = 1024
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "cache.max_bytes: requires a cache backend"
+        );
+
+        let toml = r#"
+            [cache]
+            backend = "memory"
+            eviction = "lfu"
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "cache.eviction: requires cache.max_bytes"
+        );
+    }
+
+    #[test]
+    fn test_hedged_fallback_requires_delay() {
+        let toml = r#"
+            [fallback]
+            mode = "hedged"
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "fallback.hedge_delay_ms: is required when mode is hedged"
+        );
+
+        let toml = r#"
+            [fallback]
+            mode = "race"
+            hedge_delay_ms = 100
+        "#;
+        let config = RegistryConfig::from_toml_str(toml).unwrap();
+        assert_eq!(
+            error_message(config.validate()),
+            "fallback.hedge_delay_ms: only applies when mode is hedged"
+        );
+    }
+
+    #[test]
+    fn test_secret_from_file() {
+        let path = std::env::temp_dir().join(format!("data-config-secret-{}", std::process::id()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+        let secret = Secret::File { file: path.clone() };
+        assert_eq!(secret.resolve("api_key").unwrap(), "s3cret");
+        assert!(!format!("{:?}", Secret::Value("s3cret".to_string())).contains("s3cret"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(all(feature = "yahoo", feature = "edgar"))]
+    #[tokio::test]
+    async fn test_build_registry() {
+        let toml = r#"
+            [[providers]]
+            kind = "yahoo"
+
+            [[providers]]
+            kind = "edgar"
+            user_agent = "Test/1.0 (test@example.com)"
+            roles = ["fundamental"]
+
+            [cache]
+            backend = "memory"
+            ttl_secs = 3600
+            max_bytes = 1048576
+            eviction = "lfu"
+            policy = "cache_only"
+
+            [fallback]
+            mode = "hedged"
+            hedge_delay_ms = 250
+
+            [validation]
+            action = "drop"
+
+            [validation.checks]
+            missing_prices = "fill"
+            calendar_gaps = "off"
+
+            [[routing]]
+            pattern = "^*"
+            providers = ["Yahoo Finance"]
+        "#;
+        let registry = RegistryConfig::from_toml_str(toml)
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(registry.routing_rules().len(), 1);
+        assert_eq!(registry.cache_policy(), CachePolicy::CacheOnly);
+        assert_eq!(
+            registry.fallback_mode(),
+            FallbackMode::Hedged {
+                delay: Duration::from_millis(250)
+            }
+        );
+        let validation = registry.validation_policy().unwrap();
+        assert_eq!(
+            validation.action(QualityCheck::MissingPrices),
+            Some(ValidationAction::Fill)
+        );
+        assert_eq!(
+            validation.action(QualityCheck::StalePrices),
+            Some(ValidationAction::Drop)
+        );
+        assert_eq!(validation.action(QualityCheck::CalendarGaps), None);
+        let debug = format!("{registry:?}");
+        assert!(debug.contains("Yahoo Finance"));
+        assert!(debug.contains("SEC EDGAR"));
+    }
+}
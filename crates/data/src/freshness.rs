@@ -0,0 +1,328 @@
+//! Cache freshness policy.
+//!
+//! A [`FreshnessPolicy`] decides, on every cache read, whether a cached value
+//! is fresh enough to serve. Each kind of data has its own TTL, and OHLCV bars
+//! close to today (which providers may still revise) can expire sooner than
+//! historical bars. Optionally, values past their TTL can still be served for a
+//! grace period while the registry refreshes them in the background.
+//! Remembered "not found" answers have a TTL of their own.
+//!
+//! A [`CachePolicy`] decides whether the cache is consulted at all, for
+//! offline runs and forced refreshes.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use data_core::{CacheDataKind, CoveredRange, DateRange};
+
+/// Default TTL for OHLCV bars within the recent window.
+const DEFAULT_RECENT_OHLCV_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Default number of days before today treated as recent.
+const DEFAULT_RECENT_WINDOW_DAYS: u32 = 7;
+
+/// Default TTL for financial statements and key metrics.
+const DEFAULT_FUNDAMENTALS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Default TTL for company information.
+const DEFAULT_COMPANY_INFO_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Default TTL for universe snapshots.
+const DEFAULT_UNIVERSE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Default TTL for remembered "not found" answers.
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How a cached value compares to its TTL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Freshness {
+    /// Within its TTL; serve it.
+    Fresh,
+    /// Past its TTL but within the stale-while-revalidate window; serve it
+    /// and refresh it in the background.
+    Stale,
+    /// Too old to serve; fetch it again.
+    Expired,
+}
+
+/// Whether a request reads from and writes to the registry's cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Serve fresh cached data, fetch the rest from providers and cache it.
+    #[default]
+    Default,
+    /// Serve cached data regardless of its age and never call a provider;
+    /// requests the cache cannot answer fail.
+    CacheOnly,
+    /// Always fetch from providers without reading or writing the cache.
+    NetworkOnly,
+    /// Always fetch from providers and cache the result, replacing what was
+    /// cached.
+    Refresh,
+}
+
+impl CachePolicy {
+    /// Returns whether cached data may be served.
+    #[must_use]
+    pub const fn reads_cache(self) -> bool {
+        matches!(self, Self::Default | Self::CacheOnly)
+    }
+
+    /// Returns whether fetched data is written to the cache.
+    #[must_use]
+    pub const fn writes_cache(self) -> bool {
+        matches!(self, Self::Default | Self::Refresh)
+    }
+
+    /// Returns whether providers may be called.
+    #[must_use]
+    pub const fn uses_network(self) -> bool {
+        !matches!(self, Self::CacheOnly)
+    }
+}
+
+/// Per-data-type TTLs applied by the registry when reading from its cache.
+///
+/// A data type without a TTL never expires. The default policy keeps
+/// historical bars and ticks indefinitely, refreshes bars from the last week
+/// hourly, fundamentals and universes daily, and company information weekly.
+/// Symbols and requests a provider reported as missing are skipped for an
+/// hour.
+#[derive(Clone, Debug)]
+pub struct FreshnessPolicy {
+    ttls: HashMap<CacheDataKind, Duration>,
+    recent_ohlcv_ttl: Option<Duration>,
+    recent_window_days: u32,
+    stale_while_revalidate: Duration,
+    negative_ttl: Duration,
+}
+
+impl Default for FreshnessPolicy {
+    fn default() -> Self {
+        Self {
+            ttls: HashMap::from([
+                (CacheDataKind::Financials, DEFAULT_FUNDAMENTALS_TTL),
+                (CacheDataKind::Metrics, DEFAULT_FUNDAMENTALS_TTL),
+                (CacheDataKind::CompanyInfo, DEFAULT_COMPANY_INFO_TTL),
+                (CacheDataKind::Universe, DEFAULT_UNIVERSE_TTL),
+            ]),
+            recent_ohlcv_ttl: Some(DEFAULT_RECENT_OHLCV_TTL),
+            recent_window_days: DEFAULT_RECENT_WINDOW_DAYS,
+            stale_while_revalidate: Duration::ZERO,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+        }
+    }
+}
+
+impl FreshnessPolicy {
+    /// Create the default policy.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy under which cached data never expires.
+    ///
+    /// "Not found" answers are still only remembered for the default
+    /// negative TTL, so newly listed symbols are eventually picked up.
+    #[must_use]
+    pub fn unbounded() -> Self {
+        Self {
+            ttls: HashMap::new(),
+            recent_ohlcv_ttl: None,
+            recent_window_days: 0,
+            stale_while_revalidate: Duration::ZERO,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+        }
+    }
+
+    /// Set the TTL for a kind of data. For OHLCV this applies to bars older
+    /// than the recent window.
+    #[must_use]
+    pub fn with_ttl(mut self, kind: CacheDataKind, ttl: Duration) -> Self {
+        self.ttls.insert(kind, ttl);
+        self
+    }
+
+    /// Remove the TTL for a kind of data so it never expires.
+    #[must_use]
+    pub fn without_ttl(mut self, kind: CacheDataKind) -> Self {
+        self.ttls.remove(&kind);
+        self
+    }
+
+    /// Set the TTL for OHLCV bars dated within the recent window.
+    #[must_use]
+    pub const fn with_recent_ohlcv_ttl(mut self, ttl: Duration) -> Self {
+        self.recent_ohlcv_ttl = Some(ttl);
+        self
+    }
+
+    /// Set how many days before today count as recent for OHLCV bars.
+    #[must_use]
+    pub const fn with_recent_window_days(mut self, days: u32) -> Self {
+        self.recent_window_days = days;
+        self
+    }
+
+    /// Serve values up to `window` past their TTL while refreshing them in
+    /// the background.
+    #[must_use]
+    pub const fn with_stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = window;
+        self
+    }
+
+    /// Set how long a provider's "not found" answer is remembered. A zero TTL
+    /// turns negative caching off.
+    #[must_use]
+    pub const fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Returns the TTL for a kind of data, if it expires.
+    #[must_use]
+    pub fn ttl(&self, kind: CacheDataKind) -> Option<Duration> {
+        self.ttls.get(&kind).copied()
+    }
+
+    /// Returns the TTL for OHLCV bars within the recent window.
+    #[must_use]
+    pub fn recent_ohlcv_ttl(&self) -> Option<Duration> {
+        self.recent_ohlcv_ttl
+            .or_else(|| self.ttl(CacheDataKind::Ohlcv))
+    }
+
+    /// Returns the stale-while-revalidate window.
+    #[must_use]
+    pub const fn stale_while_revalidate(&self) -> Duration {
+        self.stale_while_revalidate
+    }
+
+    /// Returns how long a provider's "not found" answer is remembered.
+    #[must_use]
+    pub const fn negative_ttl(&self) -> Duration {
+        self.negative_ttl
+    }
+
+    /// Returns whether a "not found" answer stored at `cached_at` should
+    /// still be trusted. Remembered answers are never served stale.
+    #[must_use]
+    pub fn is_negative_fresh(&self, cached_at: DateTime<Utc>) -> bool {
+        let age = Utc::now()
+            .signed_duration_since(cached_at)
+            .to_std()
+            .unwrap_or_default();
+        age < self.negative_ttl
+    }
+
+    /// Classify a value of the given kind stored at `cached_at`.
+    #[must_use]
+    pub fn classify(&self, kind: CacheDataKind, cached_at: DateTime<Utc>) -> Freshness {
+        self.classify_with(self.ttl(kind), cached_at)
+    }
+
+    fn classify_with(&self, ttl: Option<Duration>, cached_at: DateTime<Utc>) -> Freshness {
+        let Some(ttl) = ttl else {
+            return Freshness::Fresh;
+        };
+        let age = Utc::now()
+            .signed_duration_since(cached_at)
+            .to_std()
+            .unwrap_or_default();
+        if age <= ttl {
+            Freshness::Fresh
+        } else if age <= ttl.saturating_add(self.stale_while_revalidate) {
+            Freshness::Stale
+        } else {
+            Freshness::Expired
+        }
+    }
+
+    /// Split cached OHLCV coverage into the ranges that are fresh and the
+    /// ranges that may only be served while being refreshed.
+    ///
+    /// Expired ranges are left out of both.
+    pub(crate) fn ohlcv_coverage(
+        &self,
+        covered: &[CoveredRange],
+        today: NaiveDate,
+    ) -> (Vec<DateRange>, Vec<DateRange>) {
+        let recent_start = today - chrono::Days::new(u64::from(self.recent_window_days));
+        let historical_ttl = self.ttl(CacheDataKind::Ohlcv);
+        let recent_ttl = self.recent_ohlcv_ttl();
+
+        let mut fresh = Vec::new();
+        let mut stale = Vec::new();
+        for covered in covered {
+            let historical = DateRange::new(
+                covered.range.start,
+                covered
+                    .range
+                    .end
+                    .min(recent_start.pred_opt().unwrap_or(recent_start)),
+            );
+            let recent = DateRange::new(covered.range.start.max(recent_start), covered.range.end);
+            for (range, ttl) in [(historical, historical_ttl), (recent, recent_ttl)] {
+                if range.is_empty() {
+                    continue;
+                }
+                match self.classify_with(ttl, covered.cached_at) {
+                    Freshness::Fresh => fresh.push(range),
+                    Freshness::Stale => stale.push(range),
+                    Freshness::Expired => {}
+                }
+            }
+        }
+        (fresh, stale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_classify_with_stale_window() {
+        let policy = FreshnessPolicy::unbounded()
+            .with_ttl(CacheDataKind::Financials, Duration::from_secs(60))
+            .with_stale_while_revalidate(Duration::from_secs(60));
+        let now = Utc::now();
+
+        let classify = |secs| {
+            policy.classify(
+                CacheDataKind::Financials,
+                now - chrono::Duration::seconds(secs),
+            )
+        };
+        assert_eq!(classify(30), Freshness::Fresh);
+        assert_eq!(classify(90), Freshness::Stale);
+        assert_eq!(classify(150), Freshness::Expired);
+        assert_eq!(
+            policy.classify(CacheDataKind::Metrics, now - chrono::Duration::days(365)),
+            Freshness::Fresh
+        );
+    }
+
+    #[test]
+    fn test_recent_bars_expire_before_historical() {
+        let policy = FreshnessPolicy::unbounded()
+            .with_recent_ohlcv_ttl(Duration::from_secs(60))
+            .with_recent_window_days(7);
+        let today = date(2024, 6, 30);
+        let covered = [CoveredRange::new(
+            DateRange::new(date(2024, 6, 1), today),
+            Utc::now() - chrono::Duration::hours(1),
+        )];
+
+        let (fresh, stale) = policy.ohlcv_coverage(&covered, today);
+        assert_eq!(fresh, [DateRange::new(date(2024, 6, 1), date(2024, 6, 22))]);
+        assert!(stale.is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+//! Provider health tracking and circuit breaking.
+//!
+//! The registry records the outcome and latency of every provider call. When a
+//! provider fails repeatedly (or its rolling error rate is too high) its
+//! circuit opens and the registry skips it until a cool-down elapses. The
+//! circuit then half-opens, letting a single probe request through: success
+//! closes the circuit, failure re-opens it.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use data_core::DataError;
+
+/// Default number of recent calls used for rolling statistics.
+const DEFAULT_WINDOW: usize = 20;
+
+/// Default number of consecutive failures that opens a circuit.
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default rolling error rate that opens a circuit.
+const DEFAULT_ERROR_RATE_THRESHOLD: f64 = 0.5;
+
+/// Default minimum number of calls before the error rate is considered.
+const DEFAULT_MIN_SAMPLES: usize = 10;
+
+/// Default time a circuit stays open before probing.
+const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Circuit breaker configuration.
+#[derive(Clone, Debug)]
+pub struct HealthConfig {
+    /// Number of recent calls used for the rolling error rate and latency.
+    pub window: usize,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Rolling error rate (0.0 to 1.0) that opens the circuit.
+    pub error_rate_threshold: f64,
+    /// Minimum calls in the window before the error rate is considered.
+    pub min_samples: usize,
+    /// How long an open circuit waits before letting a probe through.
+    pub open_duration: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            error_rate_threshold: DEFAULT_ERROR_RATE_THRESHOLD,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            open_duration: DEFAULT_OPEN_DURATION,
+        }
+    }
+}
+
+/// State of a provider's circuit breaker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// The provider is skipped until the cool-down elapses.
+    Open,
+    /// A single probe request is allowed to test recovery.
+    HalfOpen,
+}
+
+/// Point-in-time health of a provider.
+#[derive(Clone, Debug)]
+pub struct ProviderHealth {
+    /// Provider name.
+    pub provider: String,
+    /// Current circuit state.
+    pub state: CircuitState,
+    /// Error rate over the rolling window (0.0 to 1.0).
+    pub error_rate: f64,
+    /// Mean latency over the rolling window.
+    pub avg_latency: Option<Duration>,
+    /// Number of consecutive failed calls.
+    pub consecutive_failures: u32,
+    /// Total calls made to the provider.
+    pub total_requests: u64,
+    /// Total failed calls.
+    pub total_failures: u64,
+    /// Message of the most recent failure.
+    pub last_error: Option<String>,
+    /// Time until an open circuit lets a probe through.
+    pub retry_in: Option<Duration>,
+}
+
+/// Mutable per-provider state.
+#[derive(Debug)]
+struct ProviderState {
+    recent: VecDeque<(bool, Duration)>,
+    consecutive_failures: u32,
+    total_requests: u64,
+    total_failures: u64,
+    last_error: Option<String>,
+    open_until: Option<Instant>,
+    probing: bool,
+}
+
+impl ProviderState {
+    fn new(window: usize) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(window),
+            consecutive_failures: 0,
+            total_requests: 0,
+            total_failures: 0,
+            last_error: None,
+            open_until: None,
+            probing: false,
+        }
+    }
+
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.open_until {
+            Some(until) if now < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    fn error_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        let failures = self.recent.iter().filter(|(ok, _)| !ok).count();
+        failures as f64 / self.recent.len() as f64
+    }
+
+    fn record(&mut self, success: bool, latency: Duration, window: usize) {
+        if self.recent.len() == window {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((success, latency));
+        self.total_requests += 1;
+    }
+}
+
+/// How a call was let through a provider's circuit breaker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Admission {
+    /// The circuit was closed.
+    Request,
+    /// The circuit was half-open and this call holds its single probe slot.
+    Probe,
+}
+
+/// Tracks health for all providers used by a registry.
+#[derive(Debug, Default)]
+pub(crate) struct HealthTracker {
+    config: HealthConfig,
+    states: Mutex<HashMap<String, ProviderState>>,
+}
+
+impl HealthTracker {
+    /// Create a tracker with the given configuration.
+    pub(crate) fn new(config: HealthConfig) -> Self {
+        Self {
+            config,
+            states: Mutex::default(),
+        }
+    }
+
+    /// Returns how a request may be sent to the provider, or `None` if it may
+    /// not.
+    ///
+    /// A half-open circuit admits exactly one probe at a time.
+    pub(crate) fn allow(&self, provider: &str) -> Option<Admission> {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(state) = states.get_mut(provider) else {
+            return Some(Admission::Request);
+        };
+        match state.state(Instant::now()) {
+            CircuitState::Closed => Some(Admission::Request),
+            CircuitState::Open => None,
+            CircuitState::HalfOpen if state.probing => None,
+            CircuitState::HalfOpen => {
+                state.probing = true;
+                Some(Admission::Probe)
+            }
+        }
+    }
+
+    /// Release the half-open probe of a call that was dropped before finishing.
+    ///
+    /// Only call this for a call admitted as [`Admission::Probe`].
+    ///
+    /// A cancelled call says nothing about the provider, so the circuit stays
+    /// half-open and the next request becomes the probe.
+    pub(crate) fn cancel(&self, provider: &str) {
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = states.get_mut(provider) {
+            state.probing = false;
+        }
+    }
+
+    /// Record the outcome of a provider call.
+    ///
+    /// Errors that describe the request rather than the provider (unknown
+    /// symbols, unavailable ranges, unsupported features) count as successes,
+    /// since the provider responded normally. So do frames rejected by OHLCV
+    /// validation: bad data is a data-quality problem, not an outage.
+    ///
+    /// `admission` is how the call was let through [`allow`](Self::allow); only
+    /// the probe's own outcome frees the half-open probe slot, so a call that
+    /// started while the circuit was closed cannot let a second probe in.
+    pub(crate) fn record<T>(
+        &self,
+        provider: &str,
+        admission: Admission,
+        latency: Duration,
+        result: &Result<T, DataError>,
+    ) {
+        let window = self.config.window.max(1);
+        let mut states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        let state = states
+            .entry(provider.to_string())
+            .or_insert_with(|| ProviderState::new(window));
+        if admission == Admission::Probe {
+            state.probing = false;
+        }
+
+        let error = result.as_ref().err().filter(|e| is_provider_fault(e));
+        let Some(error) = error else {
+            state.record(true, latency, window);
+            state.consecutive_failures = 0;
+            state.open_until = None;
+            // A closed circuit has no probe outstanding
+            state.probing = false;
+            return;
+        };
+
+        state.record(false, latency, window);
+        state.consecutive_failures += 1;
+        state.total_failures += 1;
+        state.last_error = Some(error.to_string());
+
+        let now = Instant::now();
+        let tripped = state.state(now) == CircuitState::HalfOpen
+            || state.consecutive_failures >= self.config.failure_threshold
+            || (state.recent.len() >= self.config.min_samples
+                && state.error_rate() >= self.config.error_rate_threshold);
+        if tripped {
+            let cool_down = match error {
+                DataError::RateLimited {
+                    retry_after: Some(retry_after),
+                    ..
+                } => (*retry_after).max(self.config.open_duration),
+                _ => self.config.open_duration,
+            };
+            state.open_until = Some(now + cool_down);
+        }
+    }
+
+    /// Returns a snapshot of every tracked provider's health.
+    pub(crate) fn snapshot(&self) -> Vec<ProviderHealth> {
+        let states = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let mut snapshot: Vec<ProviderHealth> = states
+            .iter()
+            .map(|(name, state)| {
+                let latencies: Vec<Duration> = state.recent.iter().map(|(_, l)| *l).collect();
+                ProviderHealth {
+                    provider: name.clone(),
+                    state: state.state(now),
+                    error_rate: state.error_rate(),
+                    avg_latency: (!latencies.is_empty())
+                        .then(|| latencies.iter().sum::<Duration>() / latencies.len() as u32),
+                    consecutive_failures: state.consecutive_failures,
+                    total_requests: state.total_requests,
+                    total_failures: state.total_failures,
+                    last_error: state.last_error.clone(),
+                    retry_in: state
+                        .open_until
+                        .and_then(|until| until.checked_duration_since(now)),
+                }
+            })
+            .collect();
+        snapshot.sort_by(|a, b| a.provider.cmp(&b.provider));
+        snapshot
+    }
+}
+
+/// Returns true if the error indicates a problem with the provider itself.
+const fn is_provider_fault(error: &DataError) -> bool {
+    matches!(
+        error,
+        DataError::Network(_)
+            | DataError::RateLimited { .. }
+            | DataError::Parse(_)
+            | DataError::AuthenticationFailed(_)
+            | DataError::Other(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_error() -> Result<(), DataError> {
+        Err(DataError::Network("timeout".to_string()))
+    }
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures() {
+        let tracker = HealthTracker::new(HealthConfig {
+            failure_threshold: 3,
+            ..Default::default()
+        });
+
+        for _ in 0..3 {
+            assert_eq!(tracker.allow("yahoo"), Some(Admission::Request));
+            tracker.record(
+                "yahoo",
+                Admission::Request,
+                Duration::from_millis(10),
+                &network_error(),
+            );
+        }
+
+        assert_eq!(tracker.allow("yahoo"), None);
+        let health = &tracker.snapshot()[0];
+        assert_eq!(health.state, CircuitState::Open);
+        assert_eq!(health.consecutive_failures, 3);
+        assert_eq!(health.total_failures, 3);
+        assert!(health.retry_in.is_some());
+    }
+
+    #[test]
+    fn test_half_open_admits_single_probe() {
+        let tracker = HealthTracker::new(HealthConfig {
+            failure_threshold: 1,
+            open_duration: Duration::ZERO,
+            ..Default::default()
+        });
+
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &network_error(),
+        );
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Probe));
+        assert_eq!(tracker.allow("yahoo"), None);
+
+        tracker.record(
+            "yahoo",
+            Admission::Probe,
+            Duration::from_millis(10),
+            &Ok::<_, DataError>(()),
+        );
+        assert_eq!(tracker.snapshot()[0].state, CircuitState::Closed);
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Request));
+    }
+
+    #[test]
+    fn test_only_the_probe_releases_its_slot() {
+        let tracker = HealthTracker::new(HealthConfig {
+            failure_threshold: 1,
+            open_duration: Duration::ZERO,
+            ..Default::default()
+        });
+
+        // A request admitted while the circuit was closed
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Request));
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &network_error(),
+        );
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Probe));
+
+        // A slow request from before the circuit opened finishes with an
+        // error while the probe is in flight
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &network_error(),
+        );
+        assert_eq!(tracker.allow("yahoo"), None);
+
+        tracker.record(
+            "yahoo",
+            Admission::Probe,
+            Duration::from_millis(10),
+            &network_error(),
+        );
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Probe));
+    }
+
+    #[test]
+    fn test_cancelled_probe_is_released() {
+        let tracker = HealthTracker::new(HealthConfig {
+            failure_threshold: 1,
+            open_duration: Duration::ZERO,
+            ..Default::default()
+        });
+
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &network_error(),
+        );
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Probe));
+        tracker.cancel("yahoo");
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Probe));
+        assert_eq!(tracker.allow("yahoo"), None);
+    }
+
+    #[test]
+    fn test_request_errors_do_not_trip_circuit() {
+        let tracker = HealthTracker::new(HealthConfig {
+            failure_threshold: 1,
+            ..Default::default()
+        });
+
+        let not_found: Result<(), _> = Err(DataError::SymbolNotFound("ZZZZ".to_string()));
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &not_found,
+        );
+        let rejected: Result<(), _> = Err(DataError::Validation("duplicate date".to_string()));
+        tracker.record(
+            "yahoo",
+            Admission::Request,
+            Duration::from_millis(10),
+            &rejected,
+        );
+
+        assert_eq!(tracker.allow("yahoo"), Some(Admission::Request));
+        assert_eq!(tracker.snapshot()[0].error_rate, 0.0);
+    }
+}
@@ -19,6 +19,7 @@
 //! - `nasdaq` - NASDAQ tick data provider
 //! - `ibkr` - Interactive Brokers provider
 //! - `cache-sqlite` - SQLite-based caching
+//! - `config` - Build registries from TOML/YAML configuration files
 //!
 //! # Example
 //!
@@ -48,7 +49,10 @@ pub use data_core::*;
 // Cache implementations
 #[cfg(feature = "cache-sqlite")]
 pub use data_cache::SqliteCache;
-pub use data_cache::{InMemoryCache, NoopCache};
+pub use data_cache::{
+    BoundedMemoryCache, EvictionPolicy, InMemoryCache, NoopCache, ParquetCache, TieredCache,
+    WriteMode,
+};
 
 // Providers
 #[cfg(feature = "edgar")]
@@ -62,5 +66,43 @@ pub use data_nasdaq::NasdaqProvider;
 #[cfg(feature = "yahoo")]
 pub use data_yahoo::YahooProvider;
 
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "config")]
+pub use config::{
+    CacheBackend, CacheConfig, CachePolicyConfig, EvictionPolicyConfig, FallbackModeConfig,
+    FallbackSettings, FreshnessSettings, HealthSettings, ProviderConfig, ProviderKind,
+    ProviderRole, QualityCheckConfig, RegistryConfig, RoutingModeConfig, RoutingRuleConfig, Secret,
+    ValidationActionConfig, ValidationSettings,
+};
+
+mod batch;
+pub use batch::{BatchResult, SymbolOutcome};
+
+mod coalesce;
+
+mod freshness;
+pub use freshness::{CachePolicy, Freshness, FreshnessPolicy};
+
+mod health;
+pub use health::{CircuitState, HealthConfig, ProviderHealth};
+
+mod merge;
+pub use merge::{MergePolicy, MergedStatement};
+
+mod provenance;
+pub use provenance::{FetchAttempt, FetchRequest, Fetched};
+
+mod reconcile;
+pub use reconcile::{DiscrepancyStatus, ReconcilePolicy, Reconciliation, Tolerance};
+
+mod reference;
+
 mod registry;
 pub use registry::DataProviderRegistry;
+
+mod routing;
+pub use routing::{FallbackMode, RoutingMode, RoutingRule};
+
+mod validation;
+pub use validation::{QualityCheck, QualityIssue, Validated, ValidationAction, ValidationPolicy};
@@ -0,0 +1,317 @@
+//! Field-level merging of financial statements across providers.
+//!
+//! Fundamental providers rarely cover the same line items: EDGAR often lacks
+//! `ebitda` or `operating_expenses`, while FMP lacks `inventory` or
+//! `long_term_debt`. The types in this module align statements from every
+//! provider by reporting period and fill each field from the first provider,
+//! in a configurable precedence order, that has a value for it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+
+use data_core::{DataError, FinancialStatement, Result};
+
+/// Default tolerance, in days, when aligning period end dates across providers.
+pub(crate) const DEFAULT_PERIOD_TOLERANCE_DAYS: i64 = 7;
+
+/// Precedence rules for merging financial statements across providers.
+///
+/// Providers are identified by their [`name`](data_core::DataProvider::name).
+/// Providers not mentioned in an ordering are consulted after the listed ones,
+/// in registration order.
+#[derive(Clone, Debug)]
+pub struct MergePolicy {
+    default_order: Vec<String>,
+    field_order: HashMap<String, Vec<String>>,
+    period_tolerance_days: i64,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        Self {
+            default_order: Vec::new(),
+            field_order: HashMap::new(),
+            period_tolerance_days: DEFAULT_PERIOD_TOLERANCE_DAYS,
+        }
+    }
+}
+
+impl MergePolicy {
+    /// Create a policy that prefers providers in registration order.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the provider precedence used for fields without an override.
+    #[must_use]
+    pub fn with_default_order<I, S>(mut self, providers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.default_order = providers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the provider precedence for a single field (e.g. `"ebitda"`).
+    ///
+    /// Field names match those in [`FinancialStatement::FIELDS`].
+    ///
+    /// # Errors
+    /// Returns [`DataError::InvalidParameter`] if `field` is not one of
+    /// [`FinancialStatement::FIELDS`], since an override for a misspelled
+    /// field would otherwise never apply.
+    pub fn with_field_order<I, S>(mut self, field: impl Into<String>, providers: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let field = field.into();
+        if !FinancialStatement::FIELDS.contains(&field.as_str()) {
+            return Err(DataError::InvalidParameter(format!(
+                "Unknown financial statement field: {field}"
+            )));
+        }
+        self.field_order
+            .insert(field, providers.into_iter().map(Into::into).collect());
+        Ok(self)
+    }
+
+    /// Set how many days period end dates may differ and still be treated
+    /// as the same reporting period.
+    #[must_use]
+    pub const fn with_period_tolerance(mut self, days: i64) -> Self {
+        self.period_tolerance_days = days;
+        self
+    }
+
+    /// Resolve the full provider order for fields without an override.
+    fn default_order<'a>(&'a self, registered: &'a [String]) -> Vec<&'a str> {
+        resolve_order(&self.default_order, registered)
+    }
+
+    /// Resolve the full provider order for a field.
+    fn order_for<'a>(&'a self, field: &str, registered: &'a [String]) -> Vec<&'a str> {
+        let preferred = self.field_order.get(field).unwrap_or(&self.default_order);
+        resolve_order(preferred, registered)
+    }
+}
+
+/// Append registered providers missing from `preferred`, in registration order.
+fn resolve_order<'a>(preferred: &'a [String], registered: &'a [String]) -> Vec<&'a str> {
+    let mut order: Vec<&str> = preferred.iter().map(String::as_str).collect();
+    for name in registered {
+        if !order.contains(&name.as_str()) {
+            order.push(name);
+        }
+    }
+    order
+}
+
+/// A financial statement assembled from several providers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MergedStatement {
+    /// The merged statement.
+    pub statement: FinancialStatement,
+    /// Provider that supplied each populated line item, keyed by field name.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl MergedStatement {
+    /// Returns the provider that supplied a field, if it has a value.
+    #[must_use]
+    pub fn source(&self, field: &str) -> Option<&str> {
+        self.sources.get(field).map(String::as_str)
+    }
+}
+
+/// Statements from every provider that fall in the same reporting period.
+#[derive(Debug)]
+struct PeriodGroup<'a> {
+    period_end: NaiveDate,
+    by_provider: HashMap<&'a str, &'a FinancialStatement>,
+}
+
+/// Merge statements from several providers field by field.
+///
+/// `results` holds each provider's name and statements, in registration
+/// order. Statements are aligned by period type and period end date (within
+/// the policy's tolerance), and the merged output is sorted most recent first.
+pub(crate) fn merge_statements(
+    results: &[(String, Vec<FinancialStatement>)],
+    policy: &MergePolicy,
+) -> Vec<MergedStatement> {
+    let registered: Vec<String> = results.iter().map(|(name, _)| name.clone()).collect();
+    let mut groups: Vec<PeriodGroup<'_>> = Vec::new();
+
+    // Align in default precedence order so the anchor period end comes from
+    // the most preferred provider.
+    for name in policy.default_order(&registered) {
+        let Some((_, statements)) = results.iter().find(|(n, _)| n == name) else {
+            continue;
+        };
+        for stmt in statements {
+            let group = groups.iter_mut().find(|g| {
+                g.by_provider
+                    .values()
+                    .next()
+                    .is_some_and(|other| other.period_type == stmt.period_type)
+                    && (g.period_end - stmt.period_end).num_days().abs()
+                        <= policy.period_tolerance_days
+                    && !g.by_provider.contains_key(name)
+            });
+            match group {
+                Some(group) => {
+                    group.by_provider.insert(name, stmt);
+                }
+                None => groups.push(PeriodGroup {
+                    period_end: stmt.period_end,
+                    by_provider: HashMap::from([(name, stmt)]),
+                }),
+            }
+        }
+    }
+
+    let mut merged: Vec<MergedStatement> = groups
+        .into_iter()
+        .map(|group| merge_group(&group, policy, &registered))
+        .collect();
+    merged.sort_by_key(|m| std::cmp::Reverse(m.statement.period_end));
+    merged
+}
+
+/// Merge a single period's statements according to the policy.
+fn merge_group(
+    group: &PeriodGroup<'_>,
+    policy: &MergePolicy,
+    registered: &[String],
+) -> MergedStatement {
+    let default_order = policy.default_order(registered);
+    let anchor = default_order
+        .iter()
+        .find_map(|name| group.by_provider.get(name))
+        .copied()
+        .expect("period group is never empty");
+
+    let mut statement =
+        FinancialStatement::new(anchor.symbol.clone(), anchor.period_end, anchor.period_type);
+    statement.fiscal_year = default_order
+        .iter()
+        .filter_map(|name| group.by_provider.get(name))
+        .find_map(|s| s.fiscal_year);
+    statement.fiscal_quarter = default_order
+        .iter()
+        .filter_map(|name| group.by_provider.get(name))
+        .find_map(|s| s.fiscal_quarter);
+
+    let mut sources = BTreeMap::new();
+    for field in FinancialStatement::FIELDS {
+        let found = policy
+            .order_for(field, registered)
+            .into_iter()
+            .filter_map(|name| group.by_provider.get(name).map(|s| (name, s)))
+            .find_map(|(name, s)| s.field(field).map(|value| (name, value)));
+        if let (Some((name, value)), Some(slot)) = (found, statement.field_mut(field)) {
+            *slot = Some(value);
+            sources.insert((*field).to_string(), name.to_string());
+        }
+    }
+
+    MergedStatement { statement, sources }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_core::{PeriodType, Symbol};
+
+    fn statement(period_end: NaiveDate) -> FinancialStatement {
+        FinancialStatement::new(Symbol::new("AAPL"), period_end, PeriodType::Annual)
+    }
+
+    #[test]
+    fn test_merge_fills_missing_fields_from_other_providers() {
+        let end = NaiveDate::from_ymd_opt(2024, 9, 28).unwrap();
+        let edgar = FinancialStatement {
+            revenue: Some(391.0),
+            inventory: Some(7.0),
+            ..statement(end)
+        };
+        let fmp = FinancialStatement {
+            revenue: Some(390.0),
+            ebitda: Some(134.0),
+            // FMP reports the calendar month end
+            ..statement(NaiveDate::from_ymd_opt(2024, 9, 30).unwrap())
+        };
+
+        let results = vec![
+            ("SEC EDGAR".to_string(), vec![edgar]),
+            ("FMP".to_string(), vec![fmp]),
+        ];
+        let merged = merge_statements(&results, &MergePolicy::new());
+
+        assert_eq!(merged.len(), 1);
+        let m = &merged[0];
+        assert_eq!(m.statement.period_end, end);
+        assert_eq!(m.statement.revenue, Some(391.0));
+        assert_eq!(m.statement.ebitda, Some(134.0));
+        assert_eq!(m.statement.inventory, Some(7.0));
+        assert_eq!(m.source("revenue"), Some("SEC EDGAR"));
+        assert_eq!(m.source("ebitda"), Some("FMP"));
+        assert_eq!(m.source("net_income"), None);
+    }
+
+    #[test]
+    fn test_merge_respects_field_precedence() {
+        let end = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        let results = vec![
+            (
+                "SEC EDGAR".to_string(),
+                vec![FinancialStatement {
+                    revenue: Some(100.0),
+                    net_income: Some(10.0),
+                    ..statement(end)
+                }],
+            ),
+            (
+                "FMP".to_string(),
+                vec![FinancialStatement {
+                    revenue: Some(101.0),
+                    net_income: Some(11.0),
+                    ..statement(end)
+                }],
+            ),
+        ];
+        let policy = MergePolicy::new()
+            .with_field_order("net_income", ["FMP"])
+            .unwrap();
+        let merged = merge_statements(&results, &policy);
+
+        assert_eq!(merged[0].statement.revenue, Some(100.0));
+        assert_eq!(merged[0].statement.net_income, Some(11.0));
+        assert_eq!(merged[0].source("net_income"), Some("FMP"));
+    }
+
+    #[test]
+    fn test_field_order_rejects_unknown_field() {
+        let result = MergePolicy::new().with_field_order("net_incom", ["FMP"]);
+        assert!(matches!(result, Err(DataError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_merge_keeps_distinct_periods_separate() {
+        let results = vec![(
+            "SEC EDGAR".to_string(),
+            vec![
+                statement(NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()),
+                statement(NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()),
+            ],
+        )];
+        let merged = merge_statements(&results, &MergePolicy::new());
+
+        assert_eq!(merged.len(), 2);
+        assert!(merged[0].statement.period_end > merged[1].statement.period_end);
+    }
+}
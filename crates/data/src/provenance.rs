@@ -0,0 +1,186 @@
+//! Provenance metadata for data served by the registry.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use data_core::{DataError, DataFrequency, PeriodType, Symbol};
+
+use crate::freshness::CachePolicy;
+
+/// The parameters of a registry request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FetchRequest {
+    /// An OHLCV request.
+    Ohlcv {
+        /// Instrument symbol.
+        symbol: Symbol,
+        /// First date requested.
+        start: NaiveDate,
+        /// Last date requested.
+        end: NaiveDate,
+        /// Bar frequency.
+        frequency: DataFrequency,
+    },
+    /// A financial statements request.
+    Financials {
+        /// Instrument symbol.
+        symbol: Symbol,
+        /// Annual or quarterly statements.
+        period_type: PeriodType,
+        /// Maximum number of statements requested.
+        limit: Option<usize>,
+    },
+    /// A key metrics request.
+    Metrics {
+        /// Instrument symbol.
+        symbol: Symbol,
+        /// Date the metrics apply to.
+        date: NaiveDate,
+    },
+}
+
+impl FetchRequest {
+    /// Returns the symbol the request is for.
+    #[must_use]
+    pub const fn symbol(&self) -> &Symbol {
+        match self {
+            Self::Ohlcv { symbol, .. }
+            | Self::Financials { symbol, .. }
+            | Self::Metrics { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// A provider that failed before the request was served.
+#[derive(Clone, Debug)]
+pub struct FetchAttempt {
+    /// Name of the provider.
+    pub provider: String,
+    /// The error it returned.
+    pub error: DataError,
+}
+
+impl FetchAttempt {
+    /// Records a failed provider call.
+    #[must_use]
+    pub fn new(provider: impl Into<String>, error: DataError) -> Self {
+        Self {
+            provider: provider.into(),
+            error,
+        }
+    }
+}
+
+/// Returns the error to report once every provider has failed: the last
+/// provider's error, or `none_tried` if no provider was called.
+pub(crate) fn last_error(
+    mut attempts: Vec<FetchAttempt>,
+    none_tried: impl FnOnce() -> DataError,
+) -> DataError {
+    attempts.pop().map_or_else(none_tried, |a| a.error)
+}
+
+/// A value served by the registry along with where it came from.
+///
+/// Returned by the registry's `*_with_provenance` methods for data lineage.
+#[derive(Clone, Debug)]
+pub struct Fetched<T> {
+    /// The data.
+    pub value: T,
+    /// Name of the provider that supplied the data.
+    pub provider: String,
+    /// Whether the data was served entirely from the cache.
+    pub cache_hit: bool,
+    /// Whether part of the data was served from the cache and only the
+    /// missing rows were fetched from the provider.
+    pub partial_cache_hit: bool,
+    /// When the provider returned the data. For cached data this is when it
+    /// was cached; for data assembled from several fetches, the oldest. For a
+    /// partial cache hit it is when the missing rows were fetched.
+    pub fetched_at: DateTime<Utc>,
+    /// The request that was served.
+    pub request: FetchRequest,
+    /// The cache policy the request ran under.
+    pub cache_policy: CachePolicy,
+    /// Providers tried before `provider`, in order, with their errors.
+    pub attempts: Vec<FetchAttempt>,
+}
+
+impl<T> Fetched<T> {
+    /// Wraps data served from the cache.
+    pub(crate) fn from_cache(
+        value: T,
+        provider: impl Into<String>,
+        cached_at: DateTime<Utc>,
+        request: FetchRequest,
+        cache_policy: CachePolicy,
+    ) -> Self {
+        Self {
+            value,
+            provider: provider.into(),
+            cache_hit: true,
+            partial_cache_hit: false,
+            fetched_at: cached_at,
+            request,
+            cache_policy,
+            attempts: Vec::new(),
+        }
+    }
+
+    /// Wraps data returned by a provider at `fetched_at`.
+    pub(crate) fn from_provider(
+        value: T,
+        provider: impl Into<String>,
+        fetched_at: DateTime<Utc>,
+        request: FetchRequest,
+        cache_policy: CachePolicy,
+        attempts: Vec<FetchAttempt>,
+    ) -> Self {
+        Self {
+            value,
+            provider: provider.into(),
+            cache_hit: false,
+            partial_cache_hit: false,
+            fetched_at,
+            request,
+            cache_policy,
+            attempts,
+        }
+    }
+
+    /// Wraps cached data whose missing rows were fetched from a provider at
+    /// `fetched_at`.
+    pub(crate) fn from_partial_cache(
+        value: T,
+        provider: impl Into<String>,
+        fetched_at: DateTime<Utc>,
+        request: FetchRequest,
+        cache_policy: CachePolicy,
+        attempts: Vec<FetchAttempt>,
+    ) -> Self {
+        Self {
+            partial_cache_hit: true,
+            ..Self::from_provider(value, provider, fetched_at, request, cache_policy, attempts)
+        }
+    }
+
+    /// Returns true if any provider failed before the data was served.
+    #[must_use]
+    pub fn fell_back(&self) -> bool {
+        !self.attempts.is_empty()
+    }
+
+    /// Transforms the data, keeping its provenance.
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Fetched<U> {
+        Fetched {
+            value: f(self.value),
+            provider: self.provider,
+            cache_hit: self.cache_hit,
+            partial_cache_hit: self.partial_cache_hit,
+            fetched_at: self.fetched_at,
+            request: self.request,
+            cache_policy: self.cache_policy,
+            attempts: self.attempts,
+        }
+    }
+}
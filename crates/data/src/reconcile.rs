@@ -0,0 +1,653 @@
+//! Cross-provider reconciliation of OHLCV and fundamental data.
+//!
+//! Providers regularly disagree: Yahoo and FMP on closes, EDGAR and FMP on
+//! net income. Reconciliation takes the same data from every provider, aligns
+//! it by date (or by reporting period, for financial statements) and compares
+//! each field against a reference provider, producing a discrepancy report
+//! with absolute and relative differences, missing dates and tolerance
+//! breaches.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::NaiveDate;
+use polars::prelude::{BooleanChunked, Column, DataFrame, DataType};
+
+use data_core::{DataError, FinancialStatement, Result};
+
+use crate::merge::DEFAULT_PERIOD_TOLERANCE_DAYS;
+use crate::provenance::FetchAttempt;
+
+/// Default relative tolerance: differences up to 0.1% are not breaches.
+const DEFAULT_RELATIVE_TOLERANCE: f64 = 0.001;
+
+/// Values from one provider, keyed by date and then by field.
+type Table = BTreeMap<String, BTreeMap<String, f64>>;
+
+/// How far two providers' values may differ before it counts as a breach.
+///
+/// A difference is within tolerance if it is no larger than `absolute`, or
+/// no larger than `relative` times the reference value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// Largest allowed absolute difference.
+    pub absolute: f64,
+    /// Largest allowed difference as a fraction of the reference value.
+    pub relative: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::new(0.0, DEFAULT_RELATIVE_TOLERANCE)
+    }
+}
+
+impl Tolerance {
+    /// Create a tolerance from absolute and relative limits.
+    #[must_use]
+    pub const fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Returns true if a difference is within this tolerance.
+    #[must_use]
+    pub fn allows(&self, abs_diff: f64, rel_diff: Option<f64>) -> bool {
+        abs_diff <= self.absolute || rel_diff.is_some_and(|rel| rel <= self.relative)
+    }
+}
+
+/// Tolerances and alignment rules for reconciling providers.
+///
+/// Providers are identified by their [`name`](data_core::DataProvider::name).
+#[derive(Clone, Debug)]
+pub struct ReconcilePolicy {
+    tolerance: Tolerance,
+    field_tolerance: HashMap<String, Tolerance>,
+    reference: Option<String>,
+    period_tolerance_days: i64,
+}
+
+impl Default for ReconcilePolicy {
+    fn default() -> Self {
+        Self {
+            tolerance: Tolerance::default(),
+            field_tolerance: HashMap::new(),
+            reference: None,
+            period_tolerance_days: DEFAULT_PERIOD_TOLERANCE_DAYS,
+        }
+    }
+}
+
+impl ReconcilePolicy {
+    /// Create a policy with a 0.1% relative tolerance that compares against
+    /// the first provider to answer, in routing order.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tolerance used for fields without an override.
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the tolerance for a single field (e.g. `"volume"` or
+    /// `"net_income"`).
+    #[must_use]
+    pub fn with_field_tolerance(mut self, field: impl Into<String>, tolerance: Tolerance) -> Self {
+        self.field_tolerance.insert(field.into(), tolerance);
+        self
+    }
+
+    /// Compare every other provider against this one, if it answers.
+    #[must_use]
+    pub fn with_reference(mut self, provider: impl Into<String>) -> Self {
+        self.reference = Some(provider.into());
+        self
+    }
+
+    /// Set how many days statement period end dates may differ and still be
+    /// treated as the same reporting period.
+    #[must_use]
+    pub const fn with_period_tolerance(mut self, days: i64) -> Self {
+        self.period_tolerance_days = days;
+        self
+    }
+
+    /// Returns the tolerance for a field.
+    #[must_use]
+    pub fn tolerance_for(&self, field: &str) -> Tolerance {
+        self.field_tolerance
+            .get(field)
+            .copied()
+            .unwrap_or(self.tolerance)
+    }
+}
+
+/// The outcome of comparing one field on one date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscrepancyStatus {
+    /// Both providers have a value and they agree within tolerance.
+    Match,
+    /// Both providers have a value and they differ beyond tolerance.
+    Breach,
+    /// Both providers have the date but only one has a value for the field.
+    MissingValue,
+    /// Only one of the providers has the date at all.
+    MissingDate,
+}
+
+impl DiscrepancyStatus {
+    /// Returns the name used in the report's `status` column.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::Breach => "breach",
+            Self::MissingValue => "missing_value",
+            Self::MissingDate => "missing_date",
+        }
+    }
+}
+
+/// A discrepancy report comparing providers against a reference.
+///
+/// The report has one row per provider, date and field, with columns `date`,
+/// `field`, `reference`, `provider`, `reference_value`, `value`, `abs_diff`,
+/// `rel_diff` and `status` (see [`DiscrepancyStatus::as_str`]). For financial
+/// statements `date` is the reference provider's period end.
+#[derive(Debug)]
+pub struct Reconciliation {
+    /// The comparison of every provider against the reference.
+    pub report: DataFrame,
+    /// Name of the provider the others were compared against.
+    pub reference: String,
+    /// Providers that answered, reference first.
+    pub providers: Vec<String>,
+    /// Providers that failed, with their errors.
+    pub failures: Vec<FetchAttempt>,
+}
+
+impl Reconciliation {
+    /// Returns the report rows that are not matches.
+    pub fn discrepancies(&self) -> Result<DataFrame> {
+        let status = self
+            .report
+            .column("status")
+            .map_err(|e| DataError::Other(e.to_string()))?
+            .str()
+            .map_err(|e| DataError::Other(e.to_string()))?;
+        let mask: BooleanChunked = status
+            .into_iter()
+            .map(|s| s != Some(DiscrepancyStatus::Match.as_str()))
+            .collect();
+        self.report
+            .filter(&mask)
+            .map_err(|e| DataError::Other(e.to_string()))
+    }
+
+    /// Returns true if at least two providers answered, none failed and every
+    /// compared value matched within tolerance.
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.failures.is_empty()
+            && self.providers.len() >= 2
+            && self.discrepancies().is_ok_and(|d| d.height() == 0)
+    }
+}
+
+/// Reconcile OHLCV frames from several providers.
+///
+/// `results` holds each provider's name and data, in routing order, and must
+/// not be empty. Rows are aligned on the `date` column and every numeric
+/// column is compared.
+pub(crate) fn reconcile_frames(
+    mut results: Vec<(String, DataFrame)>,
+    failures: Vec<FetchAttempt>,
+    policy: &ReconcilePolicy,
+) -> Result<Reconciliation> {
+    reference_first(&mut results, policy);
+
+    let mut fields: Vec<String> = Vec::new();
+    let mut tables = Vec::with_capacity(results.len());
+    for (name, df) in &results {
+        let table = frame_table(df, &mut fields)?;
+        tables.push((name.clone(), table));
+    }
+
+    finish(tables, &fields, failures, policy)
+}
+
+/// Reconcile financial statements from several providers.
+///
+/// `results` holds each provider's name and statements, in routing order, and
+/// must not be empty. Statements are aligned with the reference provider's
+/// reporting periods within the policy's period tolerance. When a provider
+/// returns several statements for one period, such as a filing and its
+/// amendment, the one whose period end is closest to the period is compared,
+/// or the first of them in the provider's order on a tie.
+pub(crate) fn reconcile_statements(
+    mut results: Vec<(String, Vec<FinancialStatement>)>,
+    failures: Vec<FetchAttempt>,
+    policy: &ReconcilePolicy,
+) -> Result<Reconciliation> {
+    reference_first(&mut results, policy);
+
+    let periods: Vec<_> = results
+        .first()
+        .map(|(_, statements)| statements.iter().map(|s| s.period_end).collect())
+        .unwrap_or_default();
+    let tables = results
+        .iter()
+        .map(|(name, statements)| {
+            let mut chosen: BTreeMap<NaiveDate, (i64, &FinancialStatement)> = BTreeMap::new();
+            for stmt in statements {
+                let (period_end, distance) = periods
+                    .iter()
+                    .map(|p| (*p, (*p - stmt.period_end).num_days().abs()))
+                    .filter(|(_, distance)| *distance <= policy.period_tolerance_days)
+                    .min_by_key(|(_, distance)| *distance)
+                    .unwrap_or((stmt.period_end, 0));
+                let entry = chosen.entry(period_end).or_insert((distance, stmt));
+                if distance < entry.0 {
+                    *entry = (distance, stmt);
+                }
+            }
+            let table = chosen
+                .into_iter()
+                .map(|(period_end, (_, stmt))| {
+                    let values = FinancialStatement::FIELDS
+                        .iter()
+                        .filter_map(|field| {
+                            let value = stmt.field(field).filter(|v| !v.is_nan())?;
+                            Some(((*field).to_string(), value))
+                        })
+                        .collect();
+                    (period_end.to_string(), values)
+                })
+                .collect();
+            (name.clone(), table)
+        })
+        .collect();
+    let fields: Vec<String> = FinancialStatement::FIELDS
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    finish(tables, &fields, failures, policy)
+}
+
+/// Move the policy's reference provider, if it answered, to the front.
+fn reference_first<T>(results: &mut Vec<(String, T)>, policy: &ReconcilePolicy) {
+    let Some(reference) = &policy.reference else {
+        return;
+    };
+    if let Some(i) = results.iter().position(|(name, _)| name == reference) {
+        let result = results.remove(i);
+        results.insert(0, result);
+    }
+}
+
+/// Read a frame's numeric columns into a table, appending new field names to
+/// `fields`.
+fn frame_table(df: &DataFrame, fields: &mut Vec<String>) -> Result<Table> {
+    let dates = df
+        .column("date")
+        .map_err(|e| DataError::Other(e.to_string()))?
+        .cast(&DataType::String)
+        .map_err(|e| DataError::Other(e.to_string()))?;
+    let dates = dates.str().map_err(|e| DataError::Other(e.to_string()))?;
+
+    let mut table = Table::new();
+    for date in dates.into_iter().flatten() {
+        table.entry(date.to_string()).or_default();
+    }
+    for column in df.get_columns() {
+        let name = column.name().as_str();
+        if name == "date" || !column.dtype().is_primitive_numeric() {
+            continue;
+        }
+        let values = column
+            .cast(&DataType::Float64)
+            .map_err(|e| DataError::Other(e.to_string()))?;
+        let values = values.f64().map_err(|e| DataError::Other(e.to_string()))?;
+        for (date, value) in dates.into_iter().zip(values) {
+            if let (Some(date), Some(value)) = (date, value) {
+                if !value.is_nan() {
+                    table
+                        .entry(date.to_string())
+                        .or_default()
+                        .insert(name.to_string(), value);
+                }
+            }
+        }
+        if !fields.iter().any(|f| f == name) {
+            fields.push(name.to_string());
+        }
+    }
+    Ok(table)
+}
+
+/// Compare every table against the first and assemble the result.
+fn finish(
+    tables: Vec<(String, Table)>,
+    fields: &[String],
+    failures: Vec<FetchAttempt>,
+    policy: &ReconcilePolicy,
+) -> Result<Reconciliation> {
+    let Some(((reference, expected), others)) = tables.split_first() else {
+        return Err(DataError::InvalidParameter(
+            "Reconciliation needs data from at least one provider".to_string(),
+        ));
+    };
+    Ok(Reconciliation {
+        report: compare(reference, expected, others, fields, policy)?,
+        reference: reference.clone(),
+        providers: tables.iter().map(|(name, _)| name.clone()).collect(),
+        failures,
+    })
+}
+
+/// The result of comparing one field on one date.
+struct Comparison {
+    abs_diff: Option<f64>,
+    rel_diff: Option<f64>,
+    status: DiscrepancyStatus,
+}
+
+impl Comparison {
+    /// Compare a provider's value with the reference value.
+    fn values(expected: Option<f64>, actual: Option<f64>, tolerance: Tolerance) -> Self {
+        let (Some(expected), Some(actual)) = (expected, actual) else {
+            return Self::missing(DiscrepancyStatus::MissingValue);
+        };
+        let abs_diff = (actual - expected).abs();
+        let rel_diff = (expected != 0.0).then(|| abs_diff / expected.abs());
+        let status = if tolerance.allows(abs_diff, rel_diff) {
+            DiscrepancyStatus::Match
+        } else {
+            DiscrepancyStatus::Breach
+        };
+        Self {
+            abs_diff: Some(abs_diff),
+            rel_diff,
+            status,
+        }
+    }
+
+    const fn missing(status: DiscrepancyStatus) -> Self {
+        Self {
+            abs_diff: None,
+            rel_diff: None,
+            status,
+        }
+    }
+}
+
+/// Report columns, filled one row at a time.
+struct ReportBuilder<'a> {
+    reference: &'a str,
+    date: Vec<&'a str>,
+    field: Vec<&'a str>,
+    provider: Vec<&'a str>,
+    reference_value: Vec<Option<f64>>,
+    value: Vec<Option<f64>>,
+    abs_diff: Vec<Option<f64>>,
+    rel_diff: Vec<Option<f64>>,
+    status: Vec<&'static str>,
+}
+
+impl<'a> ReportBuilder<'a> {
+    const fn new(reference: &'a str) -> Self {
+        Self {
+            reference,
+            date: Vec::new(),
+            field: Vec::new(),
+            provider: Vec::new(),
+            reference_value: Vec::new(),
+            value: Vec::new(),
+            abs_diff: Vec::new(),
+            rel_diff: Vec::new(),
+            status: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        date: &'a str,
+        field: &'a str,
+        provider: &'a str,
+        (reference_value, value): (Option<f64>, Option<f64>),
+        comparison: Comparison,
+    ) {
+        self.date.push(date);
+        self.field.push(field);
+        self.provider.push(provider);
+        self.reference_value.push(reference_value);
+        self.value.push(value);
+        self.abs_diff.push(comparison.abs_diff);
+        self.rel_diff.push(comparison.rel_diff);
+        self.status.push(comparison.status.as_str());
+    }
+
+    fn build(self) -> Result<DataFrame> {
+        DataFrame::new(vec![
+            Column::new("date".into(), self.date),
+            Column::new("field".into(), self.field),
+            Column::new(
+                "reference".into(),
+                vec![self.reference; self.provider.len()],
+            ),
+            Column::new("provider".into(), self.provider),
+            Column::new("reference_value".into(), self.reference_value),
+            Column::new("value".into(), self.value),
+            Column::new("abs_diff".into(), self.abs_diff),
+            Column::new("rel_diff".into(), self.rel_diff),
+            Column::new("status".into(), self.status),
+        ])
+        .map_err(|e| DataError::Other(e.to_string()))
+    }
+}
+
+/// Compare every other provider's table against the reference, field by field.
+fn compare(
+    reference: &str,
+    expected: &Table,
+    others: &[(String, Table)],
+    fields: &[String],
+    policy: &ReconcilePolicy,
+) -> Result<DataFrame> {
+    let mut report = ReportBuilder::new(reference);
+
+    for (provider, actual) in others {
+        let dates: BTreeSet<&String> = expected.keys().chain(actual.keys()).collect();
+        for date in dates {
+            let (e, a) = (expected.get(date), actual.get(date));
+            for field in fields {
+                let values = (
+                    e.and_then(|v| v.get(field)).copied(),
+                    a.and_then(|v| v.get(field)).copied(),
+                );
+                let comparison = match values {
+                    (None, None) => continue,
+                    _ if e.is_none() || a.is_none() => {
+                        Comparison::missing(DiscrepancyStatus::MissingDate)
+                    }
+                    (expected, actual) => {
+                        Comparison::values(expected, actual, policy.tolerance_for(field))
+                    }
+                };
+                report.push(date, field, provider, values, comparison);
+            }
+        }
+    }
+    report.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_core::{PeriodType, Symbol};
+
+    fn ohlcv(dates: &[&str], closes: &[f64], volumes: &[f64]) -> DataFrame {
+        DataFrame::new(vec![
+            Column::new("symbol".into(), vec!["AAPL"; dates.len()]),
+            Column::new("date".into(), dates.to_vec()),
+            Column::new("close".into(), closes.to_vec()),
+            Column::new("volume".into(), volumes.to_vec()),
+        ])
+        .unwrap()
+    }
+
+    fn strings(df: &DataFrame, column: &str) -> Vec<String> {
+        df.column(column)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_reconcile_frames_reports_breaches_and_missing_dates() {
+        let results = vec![
+            (
+                "Yahoo Finance".to_string(),
+                ohlcv(
+                    &["2024-01-02", "2024-01-03", "2024-01-04"],
+                    &[100.0, 101.0, 102.0],
+                    &[1e6, 1e6, 1e6],
+                ),
+            ),
+            (
+                "FMP".to_string(),
+                ohlcv(&["2024-01-02", "2024-01-03"], &[100.05, 103.0], &[1e6, 2e6]),
+            ),
+        ];
+        let policy =
+            ReconcilePolicy::new().with_field_tolerance("volume", Tolerance::new(0.0, 1.5));
+        let reconciliation = reconcile_frames(results, Vec::new(), &policy).unwrap();
+
+        assert_eq!(reconciliation.reference, "Yahoo Finance");
+        assert_eq!(
+            strings(&reconciliation.report, "status"),
+            [
+                "match",
+                "match",
+                "breach",
+                "match",
+                "missing_date",
+                "missing_date"
+            ]
+        );
+        let discrepancies = reconciliation.discrepancies().unwrap();
+        assert_eq!(discrepancies.height(), 3);
+        let abs_diff = discrepancies.column("abs_diff").unwrap().f64().unwrap();
+        assert_eq!(abs_diff.get(0), Some(2.0));
+        assert_eq!(abs_diff.get(1), None);
+        assert!(!reconciliation.is_consistent());
+    }
+
+    #[test]
+    fn test_reconcile_statements_aligns_periods() {
+        let statement = |period_end, net_income, revenue| FinancialStatement {
+            net_income: Some(net_income),
+            revenue,
+            ..FinancialStatement::new(Symbol::new("AAPL"), period_end, PeriodType::Annual)
+        };
+        let results = vec![
+            (
+                "FMP".to_string(),
+                vec![statement(
+                    NaiveDate::from_ymd_opt(2024, 9, 30).unwrap(),
+                    93.7,
+                    Some(391.0),
+                )],
+            ),
+            (
+                "SEC EDGAR".to_string(),
+                vec![statement(
+                    NaiveDate::from_ymd_opt(2024, 9, 28).unwrap(),
+                    93.736,
+                    None,
+                )],
+            ),
+        ];
+        let policy = ReconcilePolicy::new().with_reference("SEC EDGAR");
+        let reconciliation = reconcile_statements(results, Vec::new(), &policy).unwrap();
+
+        assert_eq!(reconciliation.providers, ["SEC EDGAR", "FMP"]);
+        let report = &reconciliation.report;
+        assert_eq!(strings(report, "date"), ["2024-09-28", "2024-09-28"]);
+        assert_eq!(strings(report, "field"), ["revenue", "net_income"]);
+        assert_eq!(strings(report, "status"), ["missing_value", "match"]);
+    }
+
+    #[test]
+    fn test_reconcile_statements_picks_one_statement_per_period() {
+        let statement = |period_end, net_income| FinancialStatement {
+            net_income: Some(net_income),
+            ..FinancialStatement::new(Symbol::new("AAPL"), period_end, PeriodType::Annual)
+        };
+        let period_end = NaiveDate::from_ymd_opt(2024, 9, 28).unwrap();
+        let results = vec![
+            ("SEC EDGAR".to_string(), vec![statement(period_end, 93.7)]),
+            (
+                "FMP".to_string(),
+                vec![
+                    statement(NaiveDate::from_ymd_opt(2024, 9, 30).unwrap(), 50.0),
+                    statement(period_end, 93.7),
+                    statement(period_end, 60.0),
+                ],
+            ),
+        ];
+        let reconciliation =
+            reconcile_statements(results, Vec::new(), &ReconcilePolicy::new()).unwrap();
+
+        let report = &reconciliation.report;
+        assert_eq!(strings(report, "field"), ["net_income"]);
+        assert_eq!(strings(report, "status"), ["match"]);
+    }
+
+    #[test]
+    fn test_consistency_needs_two_providers_and_no_failures() {
+        let frame = || ohlcv(&["2024-01-02"], &[100.0], &[1e6]);
+        let both = vec![
+            ("Yahoo Finance".to_string(), frame()),
+            ("FMP".to_string(), frame()),
+        ];
+        let reconciliation = reconcile_frames(both, Vec::new(), &ReconcilePolicy::new()).unwrap();
+        assert!(reconciliation.is_consistent());
+
+        let failed = vec![FetchAttempt::new(
+            "FMP",
+            DataError::Network("timeout".to_string()),
+        )];
+        let reconciliation = reconcile_frames(
+            vec![("Yahoo Finance".to_string(), frame())],
+            failed,
+            &ReconcilePolicy::new(),
+        )
+        .unwrap();
+        assert_eq!(reconciliation.discrepancies().unwrap().height(), 0);
+        assert!(!reconciliation.is_consistent());
+
+        let reconciliation = reconcile_frames(
+            vec![("Yahoo Finance".to_string(), frame())],
+            Vec::new(),
+            &ReconcilePolicy::new(),
+        )
+        .unwrap();
+        assert!(!reconciliation.is_consistent());
+    }
+
+    #[test]
+    fn test_reconcile_without_results_is_an_error() {
+        let result = reconcile_frames(Vec::new(), Vec::new(), &ReconcilePolicy::new());
+        assert!(matches!(result, Err(DataError::InvalidParameter(_))));
+        let result = reconcile_statements(Vec::new(), Vec::new(), &ReconcilePolicy::new());
+        assert!(matches!(result, Err(DataError::InvalidParameter(_))));
+    }
+}
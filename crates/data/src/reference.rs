@@ -0,0 +1,85 @@
+//! In-process memo for reference data lookups.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use data_core::{Cached, CompanyInfo, Symbol};
+
+/// Memo of company info, universes and symbol support checks.
+///
+/// Entries keep the time their value was stored, either in the
+/// [`DataCache`](data_core::DataCache) they were read from or when they were
+/// fetched, so the registry can classify them with its
+/// [`FreshnessPolicy`](crate::FreshnessPolicy) like any other cached value.
+/// The memo itself never expires entries.
+#[derive(Debug, Default)]
+pub(crate) struct ReferenceCache {
+    company_info: Mutex<HashMap<Symbol, Cached<CompanyInfo>>>,
+    universes: Mutex<HashMap<String, Cached<Vec<Symbol>>>>,
+    supported: Mutex<HashMap<Symbol, Cached<bool>>>,
+}
+
+impl ReferenceCache {
+    pub(crate) fn company_info(&self, symbol: &Symbol) -> Option<Cached<CompanyInfo>> {
+        get(&self.company_info, symbol)
+    }
+
+    pub(crate) fn put_company_info(
+        &self,
+        symbol: &Symbol,
+        info: &CompanyInfo,
+        cached_at: DateTime<Utc>,
+    ) {
+        put(&self.company_info, symbol.clone(), info.clone(), cached_at);
+    }
+
+    pub(crate) fn universe(&self, universe_id: &str) -> Option<Cached<Vec<Symbol>>> {
+        get(&self.universes, universe_id)
+    }
+
+    pub(crate) fn put_universe(
+        &self,
+        universe_id: &str,
+        symbols: &[Symbol],
+        cached_at: DateTime<Utc>,
+    ) {
+        put(
+            &self.universes,
+            universe_id.to_string(),
+            symbols.to_vec(),
+            cached_at,
+        );
+    }
+
+    pub(crate) fn supports_symbol(&self, symbol: &Symbol) -> Option<Cached<bool>> {
+        get(&self.supported, symbol)
+    }
+
+    pub(crate) fn put_supports_symbol(
+        &self,
+        symbol: &Symbol,
+        supported: bool,
+        cached_at: DateTime<Utc>,
+    ) {
+        put(&self.supported, symbol.clone(), supported, cached_at);
+    }
+}
+
+fn get<K, Q, T>(map: &Mutex<HashMap<K, Cached<T>>>, key: &Q) -> Option<Cached<T>>
+where
+    K: std::borrow::Borrow<Q> + Eq + std::hash::Hash,
+    Q: Eq + std::hash::Hash + ?Sized,
+    T: Clone,
+{
+    let map = map.lock().unwrap_or_else(|e| e.into_inner());
+    map.get(key).cloned()
+}
+
+fn put<K, T>(map: &Mutex<HashMap<K, Cached<T>>>, key: K, value: T, cached_at: DateTime<Utc>)
+where
+    K: Eq + std::hash::Hash,
+{
+    let mut map = map.lock().unwrap_or_else(|e| e.into_inner());
+    map.insert(key, Cached::new(value, cached_at));
+}
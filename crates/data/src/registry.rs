@@ -1,21 +1,44 @@
 //! Data provider registry for managing multiple providers with fallback behavior.
 
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use chrono::NaiveDate;
-use polars::prelude::DataFrame;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
+use polars::prelude::{Column, DataFrame, IntoLazy, LazyFrame, UnionArgs, concat};
 use tracing::{debug, warn};
 
 use data_core::{
-    DataCache, DataError, DataFrequency, FinancialStatement, FundamentalDataProvider, KeyMetrics,
-    PeriodType, PriceDataProvider, ReferenceDataProvider, Result, Symbol, TickDataProvider,
+    CacheDataKind, Cached, CompanyInfo, DataCache, DataError, DataFrequency, DataProvider,
+    DateRange, FinancialStatement, FundamentalDataProvider, KeyMetrics, NegativeKey, OhlcvCacheKey,
+    PeriodType, PriceDataProvider, ReferenceDataProvider, Result, Symbol, Tick, TickDataProvider,
+    missing_ranges, stitch_ohlcv,
 };
 
+use crate::batch::{BatchResult, SymbolOutcome};
+use crate::coalesce::SingleFlight;
+use crate::freshness::{CachePolicy, Freshness, FreshnessPolicy};
+use crate::health::{Admission, HealthConfig, HealthTracker, ProviderHealth};
+use crate::merge::{MergePolicy, MergedStatement, merge_statements};
+use crate::provenance::{FetchAttempt, FetchRequest, Fetched, last_error};
+use crate::reconcile::{ReconcilePolicy, Reconciliation, reconcile_frames, reconcile_statements};
+use crate::reference::ReferenceCache;
+use crate::routing::{FallbackMode, RouteRequest, Router, RoutingRule};
+use crate::validation::ValidationPolicy;
+
+/// Maximum number of symbols a batch fetch has in flight at once.
+const BATCH_CONCURRENCY: usize = 8;
+
 /// Registry for managing multiple data providers with automatic fallback.
 ///
 /// The `DataProviderRegistry` allows you to register multiple providers for each
 /// data type (price, fundamental, tick, reference) and will automatically try
-/// them in order until one succeeds.
+/// them in order until one succeeds. Concurrent identical OHLCV, financials and
+/// metrics requests share a single fetch.
 ///
 /// # Example
 ///
@@ -42,6 +65,17 @@ pub struct DataProviderRegistry {
     tick_providers: Vec<Arc<dyn TickDataProvider>>,
     reference_providers: Vec<Arc<dyn ReferenceDataProvider>>,
     cache: Option<Arc<dyn DataCache>>,
+    router: Router,
+    health: Arc<HealthTracker>,
+    reference_cache: ReferenceCache,
+    freshness: FreshnessPolicy,
+    cache_policy: CachePolicy,
+    fallback: FallbackMode,
+    validation: Option<ValidationPolicy>,
+    revalidating: Arc<Mutex<HashSet<String>>>,
+    ohlcv_flights: SingleFlight<Result<Fetched<DataFrame>>>,
+    financials_flights: SingleFlight<Result<Fetched<Vec<FinancialStatement>>>>,
+    metrics_flights: SingleFlight<Result<Fetched<KeyMetrics>>>,
 }
 
 impl std::fmt::Debug for DataProviderRegistry {
@@ -80,6 +114,12 @@ impl std::fmt::Debug for DataProviderRegistry {
                     .collect::<Vec<_>>(),
             )
             .field("cache", &self.cache.as_ref().map(|_| "configured"))
+            .field("routing_rules", &self.router.rules())
+            .field("health", &self.health.snapshot())
+            .field("freshness", &self.freshness)
+            .field("cache_policy", &self.cache_policy)
+            .field("fallback", &self.fallback)
+            .field("validation", &self.validation)
             .finish()
     }
 }
@@ -134,10 +174,459 @@ impl DataProviderRegistry {
         self.reference_providers.push(provider);
     }
 
+    /// Add a routing rule.
+    ///
+    /// Rules are evaluated in the order they were added; the first rule whose
+    /// pattern matches a symbol decides which providers are preferred or
+    /// allowed for it. Providers are always filtered by their declared
+    /// capabilities as well.
+    pub fn add_routing_rule(&mut self, rule: RoutingRule) {
+        debug!(pattern = rule.pattern(), "Adding routing rule");
+        self.router.add_rule(rule);
+    }
+
+    /// Add a routing rule, returning the registry for chaining.
+    #[must_use]
+    pub fn with_routing_rule(mut self, rule: RoutingRule) -> Self {
+        self.add_routing_rule(rule);
+        self
+    }
+
+    /// Returns the configured routing rules.
+    #[must_use]
+    pub fn routing_rules(&self) -> &[RoutingRule] {
+        self.router.rules()
+    }
+
+    /// Set whether OHLCV, financials and metrics requests try their providers
+    /// one at a time or race them.
+    ///
+    /// With [`FallbackMode::Hedged`] or [`FallbackMode::Race`], the first
+    /// successful answer is used and the calls still running are cancelled.
+    #[must_use]
+    pub const fn with_fallback_mode(mut self, mode: FallbackMode) -> Self {
+        self.fallback = mode;
+        self
+    }
+
+    /// Returns how requests move through their providers.
+    #[must_use]
+    pub const fn fallback_mode(&self) -> FallbackMode {
+        self.fallback
+    }
+
+    /// Validate OHLCV data from providers before it is cached or returned.
+    ///
+    /// A frame rejected by a check whose action is
+    /// [`ValidationAction::Error`](crate::ValidationAction::Error) is treated
+    /// like a failed call, so the next provider is tried, but it does not count
+    /// against the provider's circuit breaker. Issues are logged as warnings.
+    #[must_use]
+    pub fn with_validation(mut self, policy: ValidationPolicy) -> Self {
+        self.validation = Some(policy);
+        self
+    }
+
+    /// Returns the OHLCV validation policy, if one is set.
+    #[must_use]
+    pub const fn validation_policy(&self) -> Option<&ValidationPolicy> {
+        self.validation.as_ref()
+    }
+
+    /// Configure the circuit breaker used to skip unhealthy providers.
+    ///
+    /// Replaces any health statistics collected so far.
+    #[must_use]
+    pub fn with_health_config(mut self, config: HealthConfig) -> Self {
+        self.health = Arc::new(HealthTracker::new(config));
+        self
+    }
+
+    /// Returns a snapshot of the health of every provider that has been called.
+    ///
+    /// Includes the circuit state, rolling error rate and latency, and
+    /// failure counters, for export to monitoring.
+    #[must_use]
+    pub fn health_snapshot(&self) -> Vec<ProviderHealth> {
+        self.health.snapshot()
+    }
+
+    /// Call a provider through its circuit breaker, recording the outcome.
+    ///
+    /// Returns `None` without calling the provider if its circuit is open.
+    async fn call_provider<T>(
+        &self,
+        provider: &str,
+        call: impl Future<Output = Result<T>>,
+    ) -> Option<Result<T>> {
+        let Some(admission) = self.health.allow(provider) else {
+            debug!(provider, "Circuit open, skipping provider");
+            return None;
+        };
+        // Hedged and racing fetches drop the calls that lose, which must not
+        // leave a half-open probe outstanding forever
+        let mut guard = ProbeGuard {
+            health: &self.health,
+            provider,
+            admission,
+            finished: false,
+        };
+        let started = Instant::now();
+        let result = call.await;
+        guard.finished = true;
+        self.health
+            .record(provider, admission, started.elapsed(), &result);
+        Some(result)
+    }
+
+    /// Call a provider for one symbol through its circuit breaker, skipping it
+    /// if it recently reported the symbol or the request as missing.
+    ///
+    /// `SymbolNotFound` and `DataNotAvailable` answers are remembered in the
+    /// cache for the freshness policy's negative TTL and replayed without
+    /// calling the provider.
+    async fn call_provider_for<T>(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        request: &NegativeRequest,
+        policy: CachePolicy,
+        call: impl Future<Output = Result<T>>,
+    ) -> Option<Result<T>> {
+        let remembered = if policy.reads_cache() {
+            self.remembered_missing(provider, symbol, request).await
+        } else {
+            None
+        };
+        if let Some(e) = remembered {
+            debug!(
+                provider,
+                symbol = %symbol,
+                request = %request.description,
+                "Provider recently reported data missing, skipping"
+            );
+            return Some(Err(e));
+        }
+        let result = self.call_provider(provider, call).await?;
+        if let Err(e) = &result {
+            if policy.writes_cache() {
+                self.remember_missing(provider, symbol, request, e).await;
+            }
+        }
+        Some(result)
+    }
+
+    /// Call providers for one symbol until one succeeds, following the
+    /// registry's fallback mode.
+    ///
+    /// Returns the provider that succeeded with its data, if any, and the
+    /// providers that failed, in the order they failed.
+    async fn first_success<'p, P, T, Fut>(
+        &self,
+        providers: &[&'p Arc<P>],
+        symbol: &Symbol,
+        request: &NegativeRequest,
+        policy: CachePolicy,
+        call: impl Fn(&'p Arc<P>) -> Fut,
+    ) -> (Option<(&'p Arc<P>, T)>, Vec<FetchAttempt>)
+    where
+        P: DataProvider + ?Sized,
+        Fut: Future<Output = Result<T>>,
+    {
+        let call = &call;
+        let launch = |provider: &'p Arc<P>| async move {
+            debug!(
+                provider = provider.name(),
+                symbol = %symbol,
+                request = %request.description,
+                "Calling provider"
+            );
+            let result = self
+                .call_provider_for(provider.name(), symbol, request, policy, call(provider))
+                .await;
+            (provider, result)
+        };
+
+        let initial = match self.fallback {
+            FallbackMode::Race => providers.len(),
+            FallbackMode::Sequential | FallbackMode::Hedged { .. } => 1,
+        };
+        let mut pending = providers.iter().copied();
+        let mut in_flight: FuturesUnordered<_> =
+            pending.by_ref().take(initial).map(launch).collect();
+        let mut attempts = Vec::new();
+        while !in_flight.is_empty() {
+            let finished = match self.fallback {
+                FallbackMode::Hedged { delay } if pending.len() > 0 => {
+                    tokio::select! {
+                        finished = in_flight.next() => finished,
+                        () = tokio::time::sleep(delay) => None,
+                    }
+                }
+                _ => in_flight.next().await,
+            };
+            let Some((provider, result)) = finished else {
+                debug!(symbol = %symbol, "Providers slow to answer, hedging");
+                in_flight.extend(pending.next().map(launch));
+                continue;
+            };
+            match result {
+                Some(Ok(data)) => return (Some((provider, data)), attempts),
+                Some(Err(e)) => {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying next"
+                    );
+                    attempts.push(FetchAttempt::new(provider.name(), e));
+                }
+                // Circuit open
+                None => {}
+            }
+            in_flight.extend(pending.next().map(launch));
+        }
+        (None, attempts)
+    }
+
+    /// Look up a remembered "not found" answer that is still within its TTL.
+    async fn remembered_missing(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        request: &NegativeRequest,
+    ) -> Option<DataError> {
+        let cache = self.cache.as_ref()?;
+        if self.freshness.negative_ttl().is_zero() {
+            return None;
+        }
+        let symbol_key = NegativeKey::symbol(provider, symbol);
+        let request_key = NegativeKey::request(provider, symbol, &request.description);
+        for key in [symbol_key, request_key] {
+            let Ok(Some(cached)) = cache.get_negative(&key).await else {
+                continue;
+            };
+            if !self.freshness.is_negative_fresh(cached.cached_at) {
+                continue;
+            }
+            return Some(match key.request {
+                None => DataError::SymbolNotFound(symbol.to_string()),
+                Some(_) => DataError::DataNotAvailable {
+                    symbol: symbol.to_string(),
+                    start: request.start.clone(),
+                    end: request.end.clone(),
+                },
+            });
+        }
+        None
+    }
+
+    /// Remember a provider's "not found" answer. Other errors are transient
+    /// and are not remembered.
+    async fn remember_missing(
+        &self,
+        provider: &str,
+        symbol: &Symbol,
+        request: &NegativeRequest,
+        error: &DataError,
+    ) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        if self.freshness.negative_ttl().is_zero() {
+            return;
+        }
+        let key = match error {
+            DataError::SymbolNotFound(_) => NegativeKey::symbol(provider, symbol),
+            DataError::DataNotAvailable { .. } => {
+                NegativeKey::request(provider, symbol, &request.description)
+            }
+            _ => return,
+        };
+        if let Err(e) = cache.put_negative(&key, &error.to_string()).await {
+            warn!(provider, error = %e, "Failed to cache negative entry");
+        }
+    }
+
+    /// Set how long each kind of cached data is served before it is fetched
+    /// again.
+    #[must_use]
+    pub fn with_freshness_policy(mut self, policy: FreshnessPolicy) -> Self {
+        self.freshness = policy;
+        self
+    }
+
+    /// Returns the freshness policy applied to cache reads.
+    #[must_use]
+    pub const fn freshness_policy(&self) -> &FreshnessPolicy {
+        &self.freshness
+    }
+
+    /// Set whether requests read from and write to the cache, e.g.
+    /// [`CachePolicy::CacheOnly`] for offline runs.
+    ///
+    /// The policy applies to every data type, including ticks, company info,
+    /// universes and symbol checks. OHLCV, financials and metrics requests
+    /// can override it with the `*_with_policy` methods.
+    #[must_use]
+    pub const fn with_cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Returns the cache policy applied to requests that do not set one.
+    #[must_use]
+    pub const fn cache_policy(&self) -> CachePolicy {
+        self.cache_policy
+    }
+
+    /// Returns the cache if the policy allows serving from it.
+    fn readable_cache(&self, policy: CachePolicy) -> Option<&Arc<dyn DataCache>> {
+        self.cache.as_ref().filter(|_| policy.reads_cache())
+    }
+
+    /// Returns the cache if the policy allows storing fetched data in it.
+    fn writable_cache(&self, policy: CachePolicy) -> Option<&Arc<dyn DataCache>> {
+        self.cache.as_ref().filter(|_| policy.writes_cache())
+    }
+
+    /// Check a cached value against the freshness policy.
+    ///
+    /// Returns true if the value may be served. Stale values are served while
+    /// `refresh` runs in the background; expired values are not served.
+    fn serve_cached<F>(
+        &self,
+        kind: CacheDataKind,
+        cached_at: DateTime<Utc>,
+        provider: &str,
+        subject: &str,
+        refresh: impl FnOnce() -> F,
+    ) -> bool
+    where
+        F: Future<Output = Result<()>> + Send + 'static,
+    {
+        match self.freshness.classify(kind, cached_at) {
+            Freshness::Fresh => true,
+            Freshness::Stale => {
+                let key = format!("{}:{provider}:{subject}", kind.as_str());
+                self.revalidate(key, provider, refresh());
+                true
+            }
+            Freshness::Expired => {
+                debug!(
+                    provider,
+                    kind = kind.as_str(),
+                    subject,
+                    "Cache entry expired"
+                );
+                false
+            }
+        }
+    }
+
+    /// Read cached financial statements if they may be served.
+    ///
+    /// Without network access, cached statements are served whatever their
+    /// age.
+    async fn cached_financials(
+        &self,
+        provider: &Arc<dyn FundamentalDataProvider>,
+        cache: &Arc<dyn DataCache>,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        policy: CachePolicy,
+    ) -> Option<Cached<Vec<FinancialStatement>>> {
+        let cached = cache
+            .get_financials(provider.name(), symbol, period_type)
+            .await
+            .ok()
+            .flatten()?;
+        let fresh_enough = !policy.uses_network()
+            || self.serve_cached(
+                CacheDataKind::Financials,
+                cached.cached_at,
+                provider.name(),
+                &format!("{symbol}:{period_type:?}"),
+                || {
+                    let (provider, cache, symbol) =
+                        (Arc::clone(provider), Arc::clone(cache), symbol.clone());
+                    async move {
+                        let data = provider
+                            .fetch_financials(&symbol, period_type, None)
+                            .await?;
+                        cache.put_financials(provider.name(), &symbol, &data).await
+                    }
+                },
+            );
+        fresh_enough.then_some(cached)
+    }
+
+    /// Returns a value memoised in-process if the cache policy allows reading
+    /// it and, when providers may be called, the freshness policy still
+    /// considers it fresh.
+    fn memoised<T>(
+        &self,
+        kind: CacheDataKind,
+        cached: Option<Cached<T>>,
+        policy: CachePolicy,
+    ) -> Option<T> {
+        cached
+            .filter(|_| policy.reads_cache())
+            .filter(|c| {
+                !policy.uses_network()
+                    || matches!(self.freshness.classify(kind, c.cached_at), Freshness::Fresh)
+            })
+            .map(|c| c.value)
+    }
+
+    /// Refresh a stale cache entry in the background.
+    ///
+    /// Does nothing outside a Tokio runtime, when the provider's circuit is
+    /// open, or when a refresh for `key` is already running.
+    fn revalidate(
+        &self,
+        key: String,
+        provider: &str,
+        refresh: impl Future<Output = Result<()>> + Send + 'static,
+    ) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let Some(admission) = self.health.allow(provider) else {
+            return;
+        };
+        let mut revalidating = self.revalidating.lock().unwrap_or_else(|e| e.into_inner());
+        if !revalidating.insert(key.clone()) {
+            if admission == Admission::Probe {
+                self.health.cancel(provider);
+            }
+            return;
+        }
+        drop(revalidating);
+
+        debug!(provider, key = %key, "Refreshing stale cache entry");
+        let health = Arc::clone(&self.health);
+        let revalidating = Arc::clone(&self.revalidating);
+        let provider = provider.to_string();
+        runtime.spawn(async move {
+            let started = Instant::now();
+            let result = refresh.await;
+            health.record(&provider, admission, started.elapsed(), &result);
+            if let Err(e) = result {
+                warn!(provider = %provider, error = %e, "Failed to refresh stale cache entry");
+            }
+            revalidating
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&key);
+        });
+    }
+
     /// Fetch OHLCV data, trying providers in order until one succeeds.
     ///
+    /// Providers that do not declare support for the frequency, the requested
+    /// history depth, or the symbol's exchange and asset class are skipped.
     /// If a cache is configured, it will be checked first and results will
-    /// be cached on success.
+    /// be cached on success, as allowed by the registry's cache policy.
     pub async fn fetch_ohlcv(
         &self,
         symbol: &Symbol,
@@ -145,107 +634,370 @@ impl DataProviderRegistry {
         end: NaiveDate,
         frequency: DataFrequency,
     ) -> Result<DataFrame> {
+        self.fetch_ohlcv_with_policy(symbol, start, end, frequency, self.cache_policy)
+            .await
+    }
+
+    /// Fetch OHLCV data under the given cache policy.
+    ///
+    /// With [`CachePolicy::CacheOnly`], the request fails unless the cache
+    /// covers the whole range for one of the routed providers.
+    pub async fn fetch_ohlcv_with_policy(
+        &self,
+        symbol: &Symbol,
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: DataFrequency,
+        policy: CachePolicy,
+    ) -> Result<DataFrame> {
+        self.fetch_ohlcv_sourced(symbol, start, end, frequency, policy)
+            .await
+            .map(|fetched| fetched.value)
+    }
+
+    /// Fetch OHLCV data under the given cache policy, along with the provider
+    /// that served it, whether it came from the cache, and the providers that
+    /// failed first.
+    pub async fn fetch_ohlcv_with_provenance(
+        &self,
+        symbol: &Symbol,
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: DataFrequency,
+        policy: CachePolicy,
+    ) -> Result<Fetched<DataFrame>> {
+        self.fetch_ohlcv_sourced(symbol, start, end, frequency, policy)
+            .await
+    }
+
+    /// Fetch OHLCV data, sharing the fetch with identical requests already in
+    /// flight.
+    async fn fetch_ohlcv_sourced(
+        &self,
+        symbol: &Symbol,
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: DataFrequency,
+        policy: CachePolicy,
+    ) -> Result<Fetched<DataFrame>> {
+        let key = format!("{symbol}:{start}:{end}:{}:{policy:?}", frequency.as_str());
+        self.ohlcv_flights
+            .run(
+                key,
+                self.fetch_ohlcv_direct(symbol, start, end, frequency, policy),
+            )
+            .await
+    }
+
+    async fn fetch_ohlcv_direct(
+        &self,
+        symbol: &Symbol,
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: DataFrequency,
+        policy: CachePolicy,
+    ) -> Result<Fetched<DataFrame>> {
         if self.price_providers.is_empty() {
             return Err(DataError::ProviderNotConfigured(
                 "No price providers registered".to_string(),
             ));
         }
 
-        // Check cache first
-        if let Some(cache) = &self.cache {
-            // Try each provider's cache key
-            for provider in &self.price_providers {
-                if let Ok(Some(cached)) = cache.get_ohlcv(provider.name(), symbol, start, end).await
-                {
+        let request = RouteRequest::symbol(symbol)
+            .frequency(frequency)
+            .since(start);
+        let providers = self.router.route(&self.price_providers, &request);
+        if providers.is_empty() {
+            return Err(DataError::NotSupported(format!(
+                "No registered price provider supports {symbol} at {frequency:?} from {start}"
+            )));
+        }
+
+        let fetch_request = FetchRequest::Ohlcv {
+            symbol: symbol.clone(),
+            start,
+            end,
+            frequency,
+        };
+
+        // Check cache first, fetching only the missing or expired dates when a
+        // provider's series is partially cached
+        let mut attempts = Vec::new();
+        if let Some(cache) = self.readable_cache(policy) {
+            let requested = DateRange::new(start, end);
+            let today = Utc::now().date_naive();
+            for provider in &providers {
+                let key = OhlcvCacheKey::new(provider.name(), symbol, frequency);
+                let Ok(lookup) = cache.get_ohlcv(&key, start, end).await else {
+                    continue;
+                };
+                let Some(cached) = lookup.data else {
+                    continue;
+                };
+                let cached_at = lookup
+                    .covered
+                    .iter()
+                    .map(|c| c.cached_at)
+                    .min()
+                    .unwrap_or_else(Utc::now);
+                let (fresh, stale) = if policy.uses_network() {
+                    self.freshness.ohlcv_coverage(&lookup.covered, today)
+                } else {
+                    (lookup.covered.iter().map(|c| c.range).collect(), Vec::new())
+                };
+                let usable: Vec<DateRange> = fresh.iter().chain(&stale).copied().collect();
+                let gaps = missing_ranges(&usable, requested);
+                if gaps.is_empty() {
                     debug!(
                         provider = provider.name(),
                         symbol = %symbol,
                         "Cache hit for OHLCV data"
                     );
-                    return Ok(cached);
+                    let refresh: Vec<DateRange> = stale
+                        .iter()
+                        .flat_map(|range| missing_ranges(&fresh, *range))
+                        .collect();
+                    if !refresh.is_empty() {
+                        self.revalidate(
+                            format!("ohlcv:{}:{symbol}:{frequency:?}", provider.name()),
+                            provider.name(),
+                            refresh_ohlcv(
+                                Arc::clone(provider),
+                                key,
+                                refresh,
+                                Arc::clone(cache),
+                                self.validation.clone(),
+                            ),
+                        );
+                    }
+                    return Ok(Fetched::from_cache(
+                        cached,
+                        provider.name(),
+                        cached_at,
+                        fetch_request,
+                        policy,
+                    ));
+                }
+                if !policy.uses_network() {
+                    continue;
+                }
+                debug!(
+                    provider = provider.name(),
+                    symbol = %symbol,
+                    gaps = gaps.len(),
+                    "Partial cache hit for OHLCV data, fetching gaps"
+                );
+                match self
+                    .fill_ohlcv_gaps(provider, &key, cached, &gaps, cache.as_ref())
+                    .await
+                {
+                    Some(Ok(data)) => {
+                        return Ok(Fetched::from_partial_cache(
+                            data,
+                            provider.name(),
+                            Utc::now(),
+                            fetch_request,
+                            policy,
+                            attempts,
+                        ));
+                    }
+                    Some(Err(e)) => attempts.push(FetchAttempt::new(provider.name(), e)),
+                    None => {}
                 }
             }
         }
 
-        // Try each provider in order
-        let mut last_error = None;
-        for provider in &self.price_providers {
-            debug!(
-                provider = provider.name(),
-                symbol = %symbol,
-                "Fetching OHLCV data"
-            );
+        if !policy.uses_network() {
+            return Err(not_cached(format!(
+                "OHLCV data for {symbol} from {start} to {end}"
+            )));
+        }
 
-            match provider.fetch_ohlcv(symbol, start, end, frequency).await {
-                Ok(data) => {
-                    // Cache the result
-                    if let Some(cache) = &self.cache {
-                        if let Err(e) = cache.put_ohlcv(provider.name(), symbol, &data).await {
-                            warn!(
-                                provider = provider.name(),
-                                error = %e,
-                                "Failed to cache OHLCV data"
-                            );
-                        }
-                    }
-                    return Ok(data);
+        // Try each provider in order
+        let negative_request =
+            NegativeRequest::range(format!("ohlcv:{}", frequency.as_str()), start, end);
+        let (served, fetch_attempts) = self
+            .first_success(
+                &providers,
+                symbol,
+                &negative_request,
+                policy,
+                |provider| async {
+                    let data = provider.fetch_ohlcv(symbol, start, end, frequency).await?;
+                    validate_ohlcv(
+                        self.validation.as_ref(),
+                        provider.name(),
+                        symbol,
+                        frequency,
+                        data,
+                    )
+                },
+            )
+            .await;
+        attempts.extend(fetch_attempts);
+        if let Some((provider, data)) = served {
+            let fetched_at = Utc::now();
+            // Cache the result
+            if let Some(cache) = self.writable_cache(policy) {
+                let key = OhlcvCacheKey::new(provider.name(), symbol, frequency);
+                if let Err(e) = cache.put_ohlcv(&key, start, end, &data).await {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Failed to cache OHLCV data"
+                    );
                 }
+            }
+            return Ok(Fetched::from_provider(
+                data,
+                provider.name(),
+                fetched_at,
+                fetch_request,
+                policy,
+                attempts,
+            ));
+        }
+
+        Err(last_error(attempts, all_circuits_open))
+    }
+
+    /// Fetch the missing ranges of a partially cached series from its
+    /// provider, cache them, and stitch them onto the cached rows.
+    ///
+    /// Returns an error if any gap could not be fetched or the stitched series
+    /// fails validation, and `None` if the provider's circuit is open. Either
+    /// way the caller falls back to fetching the whole range.
+    async fn fill_ohlcv_gaps(
+        &self,
+        provider: &Arc<dyn PriceDataProvider>,
+        key: &OhlcvCacheKey,
+        cached: DataFrame,
+        gaps: &[DateRange],
+        cache: &dyn DataCache,
+    ) -> Option<Result<DataFrame>> {
+        let mut frames = vec![cached];
+        for gap in gaps {
+            let result = self
+                .call_provider(
+                    provider.name(),
+                    provider.fetch_ohlcv(&key.symbol, gap.start, gap.end, key.frequency),
+                )
+                .await?;
+            let data = match result.and_then(|data| {
+                validate_ohlcv(
+                    self.validation.as_ref(),
+                    provider.name(),
+                    &key.symbol,
+                    key.frequency,
+                    data,
+                )
+            }) {
+                Ok(data) => data,
+                // No rows in the gap (e.g. a weekend); remember that it is covered
+                Err(DataError::DataNotAvailable { .. }) => DataFrame::empty(),
                 Err(e) => {
                     warn!(
                         provider = provider.name(),
                         error = %e,
-                        "Provider failed, trying next"
+                        "Failed to fill cache gap"
                     );
-                    last_error = Some(e);
+                    return Some(Err(e));
                 }
+            };
+            if let Err(e) = cache.put_ohlcv(key, gap.start, gap.end, &data).await {
+                warn!(
+                    provider = provider.name(),
+                    error = %e,
+                    "Failed to cache OHLCV data"
+                );
             }
+            frames.push(data);
         }
 
-        Err(last_error
-            .unwrap_or_else(|| DataError::Other("All providers failed with no error".to_string())))
+        let stitched = match stitch_ohlcv(frames) {
+            Ok(stitched) => stitched,
+            Err(e) => {
+                warn!(error = %e, "Failed to stitch cached OHLCV data");
+                return Some(Err(e));
+            }
+        };
+        // Checks such as duplicates, jumps and gaps span the seams between
+        // cached and fetched rows, so the stitched series is validated too
+        Some(validate_ohlcv(
+            self.validation.as_ref(),
+            provider.name(),
+            &key.symbol,
+            key.frequency,
+            stitched,
+        ))
     }
 
     /// Fetch OHLCV data for multiple symbols.
+    ///
+    /// Each symbol is fetched independently with cache lookup and provider
+    /// fallback, with a bounded number of symbols in flight at once, so a
+    /// failure for one symbol does not abort the batch. The returned
+    /// [`BatchResult`] holds the combined frame for all successful symbols and
+    /// a per-symbol outcome recording the provider used, whether the cache was
+    /// hit, or the error that caused the symbol to be dropped.
     pub async fn fetch_ohlcv_batch(
         &self,
         symbols: &[Symbol],
         start: NaiveDate,
         end: NaiveDate,
         frequency: DataFrequency,
-    ) -> Result<DataFrame> {
+    ) -> Result<BatchResult> {
         if self.price_providers.is_empty() {
             return Err(DataError::ProviderNotConfigured(
                 "No price providers registered".to_string(),
             ));
         }
 
-        // Try each provider in order
-        let mut last_error = None;
-        for provider in &self.price_providers {
-            debug!(
-                provider = provider.name(),
-                symbol_count = symbols.len(),
-                "Fetching batch OHLCV data"
-            );
+        debug!(symbol_count = symbols.len(), "Fetching batch OHLCV data");
 
-            match provider
-                .fetch_ohlcv_batch(symbols, start, end, frequency)
-                .await
-            {
-                Ok(data) => return Ok(data),
-                Err(e) => {
-                    warn!(
-                        provider = provider.name(),
-                        error = %e,
-                        "Provider failed, trying next"
+        let mut frames = Vec::with_capacity(symbols.len());
+        let mut outcomes = HashMap::with_capacity(symbols.len());
+
+        // `buffered` keeps results in the order of `symbols`, so the combined
+        // frame is deterministic
+        let mut results = futures::stream::iter(symbols)
+            .map(|symbol| async move {
+                let result = async {
+                    let fetched = self
+                        .fetch_ohlcv_sourced(symbol, start, end, frequency, self.cache_policy)
+                        .await?;
+                    let data = with_symbol_column(fetched.value, symbol)?;
+                    Ok::<_, DataError>((fetched.provider, fetched.cache_hit, data))
+                };
+                (symbol, result.await)
+            })
+            .buffered(BATCH_CONCURRENCY);
+
+        while let Some((symbol, result)) = results.next().await {
+            match result {
+                Ok((provider, cache_hit, data)) => {
+                    outcomes.insert(
+                        symbol.clone(),
+                        SymbolOutcome::success(provider, cache_hit, data.height()),
                     );
-                    last_error = Some(e);
+                    frames.push(data.lazy());
+                }
+                Err(e) => {
+                    warn!(symbol = %symbol, error = %e, "Dropping symbol from batch");
+                    outcomes.insert(symbol.clone(), SymbolOutcome::failure(e));
                 }
             }
         }
 
-        Err(last_error
-            .unwrap_or_else(|| DataError::Other("All providers failed with no error".to_string())))
+        let data = if frames.is_empty() {
+            DataFrame::empty()
+        } else {
+            concat(frames, UnionArgs::default())
+                .and_then(LazyFrame::collect)
+                .map_err(|e| DataError::Other(e.to_string()))?
+        };
+
+        Ok(BatchResult { data, outcomes })
     }
 
     /// Fetch financial statements, trying providers in order until one succeeds.
@@ -255,47 +1007,205 @@ impl DataProviderRegistry {
         period_type: PeriodType,
         limit: Option<usize>,
     ) -> Result<Vec<FinancialStatement>> {
-        if self.fundamental_providers.is_empty() {
-            return Err(DataError::ProviderNotConfigured(
-                "No fundamental providers registered".to_string(),
-            ));
-        }
+        self.fetch_financials_with_policy(symbol, period_type, limit, self.cache_policy)
+            .await
+    }
 
-        // Check cache first
-        if let Some(cache) = &self.cache {
-            for provider in &self.fundamental_providers {
-                if let Ok(Some(cached)) = cache
-                    .get_financials(provider.name(), symbol, period_type)
-                    .await
-                {
-                    debug!(
-                        provider = provider.name(),
-                        symbol = %symbol,
-                        "Cache hit for financials"
-                    );
-                    // Apply limit if specified
-                    let result = match limit {
-                        Some(n) => cached.into_iter().take(n).collect(),
-                        None => cached,
-                    };
-                    return Ok(result);
-                }
-            }
-        }
+    /// Fetch financial statements under the given cache policy.
+    pub async fn fetch_financials_with_policy(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: CachePolicy,
+    ) -> Result<Vec<FinancialStatement>> {
+        self.fetch_financials_sourced(symbol, period_type, limit, policy)
+            .await
+            .map(|fetched| fetched.value)
+    }
 
-        // Try each provider in order
-        let mut last_error = None;
-        for provider in &self.fundamental_providers {
-            debug!(
-                provider = provider.name(),
+    /// Fetch financial statements under the given cache policy, along with
+    /// the provider that served them, whether they came from the cache, and
+    /// the providers that failed first.
+    pub async fn fetch_financials_with_provenance(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: CachePolicy,
+    ) -> Result<Fetched<Vec<FinancialStatement>>> {
+        self.fetch_financials_sourced(symbol, period_type, limit, policy)
+            .await
+    }
+
+    /// Fetch financial statements, sharing the fetch with identical requests
+    /// already in flight.
+    async fn fetch_financials_sourced(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: CachePolicy,
+    ) -> Result<Fetched<Vec<FinancialStatement>>> {
+        let key = format!("{symbol}:{period_type:?}:{limit:?}:{policy:?}");
+        self.financials_flights
+            .run(
+                key,
+                self.fetch_financials_direct(symbol, period_type, limit, policy),
+            )
+            .await
+    }
+
+    async fn fetch_financials_direct(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: CachePolicy,
+    ) -> Result<Fetched<Vec<FinancialStatement>>> {
+        let providers = self.route_fundamental(symbol)?;
+        let fetch_request = FetchRequest::Financials {
+            symbol: symbol.clone(),
+            period_type,
+            limit,
+        };
+
+        // Check cache first
+        if let Some(cache) = self.readable_cache(policy) {
+            for provider in &providers {
+                if let Some(cached) = self
+                    .cached_financials(provider, cache, symbol, period_type, policy)
+                    .await
+                {
+                    debug!(
+                        provider = provider.name(),
+                        symbol = %symbol,
+                        "Cache hit for financials"
+                    );
+                    // Apply limit if specified
+                    let result = match limit {
+                        Some(n) => cached.value.into_iter().take(n).collect(),
+                        None => cached.value,
+                    };
+                    return Ok(Fetched::from_cache(
+                        result,
+                        provider.name(),
+                        cached.cached_at,
+                        fetch_request,
+                        policy,
+                    ));
+                }
+            }
+        }
+
+        if !policy.uses_network() {
+            return Err(not_cached(format!(
+                "{period_type:?} financials for {symbol}"
+            )));
+        }
+
+        // Try each provider in order
+        let negative_request = NegativeRequest::whole(format!("financials:{period_type:?}"));
+        let (served, attempts) = self
+            .first_success(&providers, symbol, &negative_request, policy, |provider| {
+                provider.fetch_financials(symbol, period_type, limit)
+            })
+            .await;
+        if let Some((provider, data)) = served {
+            let fetched_at = Utc::now();
+            // Cache the result
+            if let Some(cache) = self.writable_cache(policy) {
+                if let Err(e) = cache.put_financials(provider.name(), symbol, &data).await {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Failed to cache financials"
+                    );
+                }
+            }
+            return Ok(Fetched::from_provider(
+                data,
+                provider.name(),
+                fetched_at,
+                fetch_request,
+                policy,
+                attempts,
+            ));
+        }
+
+        Err(last_error(attempts, all_circuits_open))
+    }
+
+    /// Fetch financial statements from every fundamental provider and merge
+    /// them field by field.
+    ///
+    /// Statements are aligned by reporting period, and each line item is
+    /// taken from the first provider in the policy's precedence order that
+    /// has a value for it. The returned statements record which provider
+    /// supplied every populated field. Providers that fail are skipped; an
+    /// error is returned only if all of them fail. `cache_policy` applies to
+    /// every provider.
+    pub async fn fetch_financials_merged(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: &MergePolicy,
+        cache_policy: CachePolicy,
+    ) -> Result<Vec<MergedStatement>> {
+        let providers = self.route_fundamental(symbol)?;
+
+        let negative_request = NegativeRequest::whole(format!("financials:{period_type:?}"));
+        let mut results = Vec::with_capacity(providers.len());
+        let mut last_error = None;
+        for provider in &providers {
+            if let Some(cache) = self.readable_cache(cache_policy) {
+                if let Some(cached) = self
+                    .cached_financials(provider, cache, symbol, period_type, cache_policy)
+                    .await
+                {
+                    debug!(
+                        provider = provider.name(),
+                        symbol = %symbol,
+                        "Cache hit for financials"
+                    );
+                    let cached = match limit {
+                        Some(n) => cached.value.into_iter().take(n).collect(),
+                        None => cached.value,
+                    };
+                    results.push((provider.name().to_string(), cached));
+                    continue;
+                }
+            }
+            if !cache_policy.uses_network() {
+                last_error = Some(not_cached(format!(
+                    "{period_type:?} financials for {symbol} from {}",
+                    provider.name()
+                )));
+                continue;
+            }
+
+            debug!(
+                provider = provider.name(),
                 symbol = %symbol,
-                "Fetching financials"
+                "Fetching financials for merge"
             );
 
-            match provider.fetch_financials(symbol, period_type, limit).await {
+            let Some(result) = self
+                .call_provider_for(
+                    provider.name(),
+                    symbol,
+                    &negative_request,
+                    cache_policy,
+                    provider.fetch_financials(symbol, period_type, limit),
+                )
+                .await
+            else {
+                continue;
+            };
+            match result {
                 Ok(data) => {
-                    // Cache the result
-                    if let Some(cache) = &self.cache {
+                    if let Some(cache) = self.writable_cache(cache_policy) {
                         if let Err(e) = cache.put_financials(provider.name(), symbol, &data).await {
                             warn!(
                                 provider = provider.name(),
@@ -304,67 +1214,355 @@ impl DataProviderRegistry {
                             );
                         }
                     }
-                    return Ok(data);
+                    results.push((provider.name().to_string(), data));
                 }
                 Err(e) => {
                     warn!(
                         provider = provider.name(),
                         error = %e,
-                        "Provider failed, trying next"
+                        "Provider failed, merging without it"
                     );
                     last_error = Some(e);
                 }
             }
         }
 
-        Err(last_error
-            .unwrap_or_else(|| DataError::Other("All providers failed with no error".to_string())))
+        if results.is_empty() {
+            return Err(last_error.unwrap_or_else(all_circuits_open));
+        }
+
+        let mut merged = merge_statements(&results, policy);
+        if let Some(n) = limit {
+            merged.truncate(n);
+        }
+        Ok(merged)
     }
 
-    /// Fetch key metrics for a symbol on a specific date.
-    pub async fn fetch_metrics(&self, symbol: &Symbol, date: NaiveDate) -> Result<KeyMetrics> {
-        if self.fundamental_providers.is_empty() {
+    /// Fetch OHLCV data from every capable price provider and compare it
+    /// against a reference provider.
+    ///
+    /// Providers are called concurrently and directly, bypassing the cache,
+    /// so the report reflects what each one currently returns. The reference
+    /// is the policy's reference provider if it answers, otherwise the first
+    /// provider in routing order that does. Providers that fail are listed in
+    /// the result; an error is returned only if all of them fail.
+    pub async fn reconcile_ohlcv(
+        &self,
+        symbol: &Symbol,
+        start: NaiveDate,
+        end: NaiveDate,
+        frequency: DataFrequency,
+        policy: &ReconcilePolicy,
+    ) -> Result<Reconciliation> {
+        if self.price_providers.is_empty() {
             return Err(DataError::ProviderNotConfigured(
-                "No fundamental providers registered".to_string(),
+                "No price providers registered".to_string(),
             ));
         }
 
+        let request = RouteRequest::symbol(symbol)
+            .frequency(frequency)
+            .since(start);
+        let providers = self.router.route(&self.price_providers, &request);
+        if providers.is_empty() {
+            return Err(DataError::NotSupported(format!(
+                "No registered price provider supports {symbol} at {frequency:?} from {start}"
+            )));
+        }
+
+        let (results, failures) = self
+            .call_all(&providers, |provider| {
+                provider.fetch_ohlcv(symbol, start, end, frequency)
+            })
+            .await?;
+        reconcile_frames(results, failures, policy)
+    }
+
+    /// Fetch financial statements from every capable fundamental provider and
+    /// compare them against a reference provider.
+    ///
+    /// Statements are aligned by reporting period within the policy's period
+    /// tolerance. Providers are called as in
+    /// [`reconcile_ohlcv`](Self::reconcile_ohlcv).
+    pub async fn reconcile_financials(
+        &self,
+        symbol: &Symbol,
+        period_type: PeriodType,
+        limit: Option<usize>,
+        policy: &ReconcilePolicy,
+    ) -> Result<Reconciliation> {
+        let providers = self.route_fundamental(symbol)?;
+
+        let (results, failures) = self
+            .call_all(&providers, |provider| {
+                provider.fetch_financials(symbol, period_type, limit)
+            })
+            .await?;
+        reconcile_statements(results, failures, policy)
+    }
+
+    /// Call every provider concurrently through its circuit breaker.
+    ///
+    /// Returns the data from each provider that answered, in routing order,
+    /// and the providers that failed, or the last error if none answered.
+    async fn call_all<'p, P, T, Fut>(
+        &self,
+        providers: &[&'p Arc<P>],
+        call: impl Fn(&'p Arc<P>) -> Fut,
+    ) -> Result<(Vec<(String, T)>, Vec<FetchAttempt>)>
+    where
+        P: DataProvider + ?Sized,
+        Fut: Future<Output = Result<T>>,
+    {
+        let calls = providers.iter().map(|provider| async {
+            let result = self.call_provider(provider.name(), call(provider)).await;
+            (provider.name().to_string(), result)
+        });
+
+        let mut results = Vec::with_capacity(providers.len());
+        let mut failures = Vec::new();
+        for (name, result) in futures::future::join_all(calls).await {
+            match result {
+                Some(Ok(data)) => results.push((name, data)),
+                Some(Err(e)) => {
+                    warn!(provider = %name, error = %e, "Provider failed, reconciling without it");
+                    failures.push(FetchAttempt::new(name, e));
+                }
+                // Circuit open
+                None => {}
+            }
+        }
+
+        if results.is_empty() {
+            return Err(last_error(failures, all_circuits_open));
+        }
+        Ok((results, failures))
+    }
+
+    /// Fetch key metrics for a symbol on a specific date.
+    pub async fn fetch_metrics(&self, symbol: &Symbol, date: NaiveDate) -> Result<KeyMetrics> {
+        self.fetch_metrics_with_policy(symbol, date, self.cache_policy)
+            .await
+    }
+
+    /// Fetch key metrics under the given cache policy.
+    pub async fn fetch_metrics_with_policy(
+        &self,
+        symbol: &Symbol,
+        date: NaiveDate,
+        policy: CachePolicy,
+    ) -> Result<KeyMetrics> {
+        self.fetch_metrics_sourced(symbol, date, policy)
+            .await
+            .map(|fetched| fetched.value)
+    }
+
+    /// Fetch key metrics under the given cache policy, along with the
+    /// provider that served them, whether they came from the cache, and the
+    /// providers that failed first.
+    pub async fn fetch_metrics_with_provenance(
+        &self,
+        symbol: &Symbol,
+        date: NaiveDate,
+        policy: CachePolicy,
+    ) -> Result<Fetched<KeyMetrics>> {
+        self.fetch_metrics_sourced(symbol, date, policy).await
+    }
+
+    /// Fetch key metrics, sharing the fetch with identical requests already
+    /// in flight.
+    async fn fetch_metrics_sourced(
+        &self,
+        symbol: &Symbol,
+        date: NaiveDate,
+        policy: CachePolicy,
+    ) -> Result<Fetched<KeyMetrics>> {
+        let key = format!("{symbol}:{date}:{policy:?}");
+        self.metrics_flights
+            .run(key, self.fetch_metrics_direct(symbol, date, policy))
+            .await
+    }
+
+    async fn fetch_metrics_direct(
+        &self,
+        symbol: &Symbol,
+        date: NaiveDate,
+        policy: CachePolicy,
+    ) -> Result<Fetched<KeyMetrics>> {
+        let providers = self.route_fundamental(symbol)?;
+        let fetch_request = FetchRequest::Metrics {
+            symbol: symbol.clone(),
+            date,
+        };
+
         // Check cache first
-        if let Some(cache) = &self.cache {
-            for provider in &self.fundamental_providers {
-                if let Ok(Some(cached)) = cache.get_metrics(provider.name(), symbol, date).await {
+        if let Some(cache) = self.readable_cache(policy) {
+            for provider in &providers {
+                let Ok(Some(cached)) = cache.get_metrics(provider.name(), symbol, date).await
+                else {
+                    continue;
+                };
+                let fresh_enough = !policy.uses_network()
+                    || self.serve_cached(
+                        CacheDataKind::Metrics,
+                        cached.cached_at,
+                        provider.name(),
+                        &format!("{symbol}:{date}"),
+                        || {
+                            let (provider, cache, symbol) =
+                                (Arc::clone(provider), Arc::clone(cache), symbol.clone());
+                            async move {
+                                let data = provider.fetch_metrics(&symbol, date).await?;
+                                cache.put_metrics(provider.name(), &symbol, &data).await
+                            }
+                        },
+                    );
+                if fresh_enough {
                     debug!(
                         provider = provider.name(),
                         symbol = %symbol,
                         "Cache hit for metrics"
                     );
-                    return Ok(cached);
+                    return Ok(Fetched::from_cache(
+                        cached.value,
+                        provider.name(),
+                        cached.cached_at,
+                        fetch_request,
+                        policy,
+                    ));
                 }
             }
         }
 
+        if !policy.uses_network() {
+            return Err(not_cached(format!("key metrics for {symbol} on {date}")));
+        }
+
         // Try each provider in order
+        let negative_request = NegativeRequest::range("metrics", date, date);
+        let (served, attempts) = self
+            .first_success(&providers, symbol, &negative_request, policy, |provider| {
+                provider.fetch_metrics(symbol, date)
+            })
+            .await;
+        if let Some((provider, data)) = served {
+            let fetched_at = Utc::now();
+            // Cache the result
+            if let Some(cache) = self.writable_cache(policy) {
+                if let Err(e) = cache.put_metrics(provider.name(), symbol, &data).await {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Failed to cache metrics"
+                    );
+                }
+            }
+            return Ok(Fetched::from_provider(
+                data,
+                provider.name(),
+                fetched_at,
+                fetch_request,
+                policy,
+                attempts,
+            ));
+        }
+
+        Err(last_error(attempts, all_circuits_open))
+    }
+
+    /// Fetch company information, trying reference providers in order.
+    ///
+    /// Results are cached in the configured [`DataCache`], if any, and
+    /// memoised in-process; both are served only while the freshness policy's
+    /// company info TTL considers them fresh.
+    pub async fn company_info(&self, symbol: &Symbol) -> Result<CompanyInfo> {
+        let policy = self.cache_policy;
+        let memoised = self.reference_cache.company_info(symbol);
+        if let Some(info) = self.memoised(CacheDataKind::CompanyInfo, memoised, policy) {
+            debug!(symbol = %symbol, "Cache hit for company info");
+            return Ok(info);
+        }
+
+        let providers = self.route_reference(&RouteRequest::symbol(symbol))?;
+
+        if let Some(cache) = self.readable_cache(policy) {
+            for provider in &providers {
+                let Ok(Some(cached)) = cache.get_company_info(provider.name(), symbol).await else {
+                    continue;
+                };
+                let fresh_enough = !policy.uses_network()
+                    || self.serve_cached(
+                        CacheDataKind::CompanyInfo,
+                        cached.cached_at,
+                        provider.name(),
+                        symbol.as_str(),
+                        || {
+                            let (provider, cache, symbol) =
+                                (Arc::clone(provider), Arc::clone(cache), symbol.clone());
+                            async move {
+                                let info = provider.company_info(&symbol).await?;
+                                cache
+                                    .put_company_info(provider.name(), &symbol, &info)
+                                    .await
+                            }
+                        },
+                    );
+                if fresh_enough {
+                    let info = cached.value;
+                    debug!(
+                        provider = provider.name(),
+                        symbol = %symbol,
+                        "Cache hit for company info"
+                    );
+                    self.reference_cache
+                        .put_company_info(symbol, &info, cached.cached_at);
+                    return Ok(info);
+                }
+            }
+        }
+
+        if !policy.uses_network() {
+            return Err(not_cached(format!("company info for {symbol}")));
+        }
+
+        let negative_request = NegativeRequest::whole("company_info");
         let mut last_error = None;
-        for provider in &self.fundamental_providers {
+        for provider in &providers {
             debug!(
                 provider = provider.name(),
                 symbol = %symbol,
-                "Fetching metrics"
+                "Fetching company info"
             );
 
-            match provider.fetch_metrics(symbol, date).await {
-                Ok(data) => {
-                    // Cache the result
-                    if let Some(cache) = &self.cache {
-                        if let Err(e) = cache.put_metrics(provider.name(), symbol, &data).await {
+            let Some(result) = self
+                .call_provider_for(
+                    provider.name(),
+                    symbol,
+                    &negative_request,
+                    policy,
+                    provider.company_info(symbol),
+                )
+                .await
+            else {
+                continue;
+            };
+            match result {
+                Ok(info) => {
+                    if policy.writes_cache() {
+                        self.reference_cache
+                            .put_company_info(symbol, &info, Utc::now());
+                    }
+                    if let Some(cache) = self.writable_cache(policy) {
+                        if let Err(e) = cache.put_company_info(provider.name(), symbol, &info).await
+                        {
                             warn!(
                                 provider = provider.name(),
                                 error = %e,
-                                "Failed to cache metrics"
+                                "Failed to cache company info"
                             );
                         }
                     }
-                    return Ok(data);
+                    return Ok(info);
                 }
                 Err(e) => {
                     warn!(
@@ -377,65 +1575,1733 @@ impl DataProviderRegistry {
             }
         }
 
-        Err(last_error
-            .unwrap_or_else(|| DataError::Other("All providers failed with no error".to_string())))
+        Err(last_error.unwrap_or_else(all_circuits_open))
     }
 
-    // Builder methods for easy setup with specific providers
+    /// Fetch the symbols in a named universe (e.g. `"sp500"`).
+    ///
+    /// Only providers that declare the universe in their capabilities are
+    /// tried. Results are cached in the configured [`DataCache`] as a snapshot
+    /// dated today and memoised in-process; both are served only while the
+    /// freshness policy's universe TTL considers them fresh.
+    pub async fn universe(&self, universe_id: &str) -> Result<Vec<Symbol>> {
+        let policy = self.cache_policy;
+        let universe_id = universe_id.to_lowercase();
+        let memoised = self.reference_cache.universe(&universe_id);
+        if let Some(symbols) = self.memoised(CacheDataKind::Universe, memoised, policy) {
+            debug!(universe = %universe_id, "Cache hit for universe");
+            return Ok(symbols);
+        }
 
-    /// Add the Yahoo Finance provider.
-    #[cfg(feature = "yahoo")]
-    #[must_use]
-    pub fn with_yahoo(mut self) -> Self {
-        let provider = Arc::new(data_yahoo::YahooProvider::new());
-        self.register_price(provider.clone());
-        self.register_reference(provider);
-        self
-    }
+        let providers = self.route_reference(&RouteRequest::universe(&universe_id))?;
+        let today = Utc::now().date_naive();
 
-    /// Add the SEC EDGAR provider.
-    #[cfg(feature = "edgar")]
-    #[must_use]
-    pub fn with_edgar(mut self, user_agent: &str) -> Self {
-        let provider = Arc::new(data_edgar::EdgarProvider::new(user_agent));
-        self.register_fundamental(provider.clone());
-        self.register_reference(provider);
-        self
-    }
+        if let Some(cache) = self.readable_cache(policy) {
+            for provider in &providers {
+                let Ok(Some(cached)) = cache
+                    .get_universe(provider.name(), &universe_id, today)
+                    .await
+                else {
+                    continue;
+                };
+                let fresh_enough = !policy.uses_network()
+                    || self.serve_cached(
+                        CacheDataKind::Universe,
+                        cached.cached_at,
+                        provider.name(),
+                        &universe_id,
+                        || {
+                            let (provider, cache, universe_id) =
+                                (Arc::clone(provider), Arc::clone(cache), universe_id.clone());
+                            async move {
+                                let symbols = provider.universe(&universe_id).await?;
+                                cache
+                                    .put_universe(provider.name(), &universe_id, today, &symbols)
+                                    .await
+                            }
+                        },
+                    );
+                if fresh_enough {
+                    let symbols = cached.value;
+                    debug!(
+                        provider = provider.name(),
+                        universe = %universe_id,
+                        "Cache hit for universe"
+                    );
+                    self.reference_cache
+                        .put_universe(&universe_id, &symbols, cached.cached_at);
+                    return Ok(symbols);
+                }
+            }
+        }
 
-    /// Add the Financial Modeling Prep provider.
-    #[cfg(feature = "fmp")]
-    #[must_use]
-    pub fn with_fmp(mut self, api_key: &str) -> Self {
-        let provider = Arc::new(data_fmp::FmpProvider::new(api_key));
-        self.register_price(provider.clone());
-        self.register_fundamental(provider.clone());
-        self.register_reference(provider);
-        self
-    }
+        if !policy.uses_network() {
+            return Err(not_cached(format!("universe {universe_id}")));
+        }
 
-    /// Add the NASDAQ tick data provider.
-    #[cfg(feature = "nasdaq")]
-    #[must_use]
-    pub fn with_nasdaq(mut self, api_key: &str) -> Self {
-        let provider = Arc::new(data_nasdaq::NasdaqProvider::new(api_key));
-        self.register_tick(provider);
-        self
+        let mut last_error = None;
+        for provider in &providers {
+            debug!(
+                provider = provider.name(),
+                universe = %universe_id,
+                "Fetching universe"
+            );
+
+            let Some(result) = self
+                .call_provider(provider.name(), provider.universe(&universe_id))
+                .await
+            else {
+                continue;
+            };
+            match result {
+                Ok(symbols) => {
+                    if policy.writes_cache() {
+                        self.reference_cache
+                            .put_universe(&universe_id, &symbols, Utc::now());
+                    }
+                    if let Some(cache) = self.writable_cache(policy) {
+                        if let Err(e) = cache
+                            .put_universe(provider.name(), &universe_id, today, &symbols)
+                            .await
+                        {
+                            warn!(
+                                provider = provider.name(),
+                                error = %e,
+                                "Failed to cache universe"
+                            );
+                        }
+                    }
+                    return Ok(symbols);
+                }
+                Err(e) => {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying next"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(all_circuits_open))
     }
 
-    /// Add the Interactive Brokers provider.
-    ///
-    /// # Arguments
+    /// Check whether any reference provider supports a symbol.
     ///
-    /// * `host` - Host address for TWS or IB Gateway (usually "127.0.0.1")
-    /// * `port` - Port number (7496 for TWS paper, 7497 for TWS live, 4001/4002 for Gateway)
-    #[cfg(feature = "ibkr")]
-    #[must_use]
-    pub fn with_ibkr(mut self, host: &str, port: u16) -> Self {
-        let provider = Arc::new(data_ibkr::IbkrProvider::new(host, port));
-        self.register_price(provider.clone());
-        self.register_tick(provider.clone());
-        self.register_fundamental(provider);
-        self
+    /// Returns `Ok(true)` as soon as one provider recognizes the symbol and
+    /// `Ok(false)` if at least one provider answered and none recognized it.
+    /// An error is returned only if every provider failed. Answers are
+    /// memoised in-process for the freshness policy's company info TTL.
+    pub async fn supports_symbol(&self, symbol: &Symbol) -> Result<bool> {
+        let policy = self.cache_policy;
+        let memoised = self.reference_cache.supports_symbol(symbol);
+        if let Some(supported) = self.memoised(CacheDataKind::CompanyInfo, memoised, policy) {
+            return Ok(supported);
+        }
+        if !policy.uses_network() {
+            return Err(not_cached(format!("support for {symbol}")));
+        }
+
+        let providers = match self.route_reference(&RouteRequest::symbol(symbol)) {
+            Ok(providers) => providers,
+            Err(DataError::NotSupported(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        let mut answered = false;
+        let mut last_error = None;
+        for provider in &providers {
+            let Some(result) = self
+                .call_provider(provider.name(), provider.supports_symbol(symbol))
+                .await
+            else {
+                continue;
+            };
+            match result {
+                Ok(true) => {
+                    if policy.writes_cache() {
+                        self.reference_cache
+                            .put_supports_symbol(symbol, true, Utc::now());
+                    }
+                    return Ok(true);
+                }
+                Ok(false) => answered = true,
+                Err(e) => {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying next"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if answered {
+            if policy.writes_cache() {
+                self.reference_cache
+                    .put_supports_symbol(symbol, false, Utc::now());
+            }
+            return Ok(false);
+        }
+        Err(last_error.unwrap_or_else(all_circuits_open))
+    }
+
+    /// Fetch historical ticks, trying tick providers in order until one succeeds.
+    pub async fn fetch_ticks(
+        &self,
+        symbol: &Symbol,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Tick>> {
+        let policy = self.cache_policy;
+        let request = RouteRequest::symbol(symbol).frequency(DataFrequency::Tick);
+        let providers = self.route_tick(&request)?;
+
+        // Check cache first
+        if let Some(cache) = self.readable_cache(policy) {
+            for provider in &providers {
+                let Ok(Some(cached)) = cache.get_ticks(provider.name(), symbol, start, end).await
+                else {
+                    continue;
+                };
+                let fresh_enough = !policy.uses_network()
+                    || self.serve_cached(
+                        CacheDataKind::Ticks,
+                        cached.cached_at,
+                        provider.name(),
+                        &format!("{symbol}:{start}:{end}"),
+                        || {
+                            let (provider, cache, symbol) =
+                                (Arc::clone(provider), Arc::clone(cache), symbol.clone());
+                            async move {
+                                let ticks = provider.fetch_ticks(&symbol, start, end).await?;
+                                cache
+                                    .put_ticks(provider.name(), &symbol, start, end, &ticks)
+                                    .await
+                            }
+                        },
+                    );
+                if fresh_enough {
+                    debug!(
+                        provider = provider.name(),
+                        symbol = %symbol,
+                        "Cache hit for ticks"
+                    );
+                    return Ok(cached.value);
+                }
+            }
+        }
+
+        if !policy.uses_network() {
+            return Err(not_cached(format!(
+                "ticks for {symbol} from {start} to {end}"
+            )));
+        }
+
+        let negative_request =
+            NegativeRequest::range("ticks", start.to_rfc3339(), end.to_rfc3339());
+        let mut last_error = None;
+        for provider in &providers {
+            debug!(
+                provider = provider.name(),
+                symbol = %symbol,
+                "Fetching ticks"
+            );
+
+            let Some(result) = self
+                .call_provider_for(
+                    provider.name(),
+                    symbol,
+                    &negative_request,
+                    policy,
+                    provider.fetch_ticks(symbol, start, end),
+                )
+                .await
+            else {
+                continue;
+            };
+            match result {
+                Ok(ticks) => {
+                    // Cache the result
+                    if let Some(cache) = self.writable_cache(policy) {
+                        if let Err(e) = cache
+                            .put_ticks(provider.name(), symbol, start, end, &ticks)
+                            .await
+                        {
+                            warn!(
+                                provider = provider.name(),
+                                error = %e,
+                                "Failed to cache ticks"
+                            );
+                        }
+                    }
+                    return Ok(ticks);
+                }
+                Err(e) => {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying next"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(all_circuits_open))
+    }
+
+    /// Subscribe to real-time ticks, using the first tick provider that
+    /// accepts the subscription.
+    pub async fn subscribe(
+        &self,
+        symbols: &[Symbol],
+    ) -> Result<Pin<Box<dyn Stream<Item = Tick> + Send>>> {
+        let request = RouteRequest::default().frequency(DataFrequency::Tick);
+        let providers = self.route_tick(&request)?;
+
+        let mut last_error = None;
+        for provider in &providers {
+            debug!(
+                provider = provider.name(),
+                symbol_count = symbols.len(),
+                "Subscribing to ticks"
+            );
+
+            let Some(result) = self
+                .call_provider(provider.name(), provider.subscribe(symbols))
+                .await
+            else {
+                continue;
+            };
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    warn!(
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying next"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(all_circuits_open))
+    }
+
+    /// Route a reference request to capable providers.
+    fn route_reference(
+        &self,
+        request: &RouteRequest<'_>,
+    ) -> Result<Vec<&Arc<dyn ReferenceDataProvider>>> {
+        if self.reference_providers.is_empty() {
+            return Err(DataError::ProviderNotConfigured(
+                "No reference providers registered".to_string(),
+            ));
+        }
+
+        let providers = self.router.route(&self.reference_providers, request);
+        if providers.is_empty() {
+            return Err(DataError::NotSupported(
+                "No registered reference provider supports this request".to_string(),
+            ));
+        }
+        Ok(providers)
+    }
+
+    /// Route a tick request to capable providers.
+    fn route_tick(&self, request: &RouteRequest<'_>) -> Result<Vec<&Arc<dyn TickDataProvider>>> {
+        if self.tick_providers.is_empty() {
+            return Err(DataError::ProviderNotConfigured(
+                "No tick providers registered".to_string(),
+            ));
+        }
+
+        let providers = self.router.route(&self.tick_providers, request);
+        if providers.is_empty() {
+            return Err(DataError::NotSupported(
+                "No registered tick provider supports this request".to_string(),
+            ));
+        }
+        Ok(providers)
+    }
+
+    /// Route a fundamental request to capable providers.
+    ///
+    /// Fundamental providers are not filtered by frequency: their declared
+    /// frequencies describe price data, not statement periods.
+    fn route_fundamental(&self, symbol: &Symbol) -> Result<Vec<&Arc<dyn FundamentalDataProvider>>> {
+        if self.fundamental_providers.is_empty() {
+            return Err(DataError::ProviderNotConfigured(
+                "No fundamental providers registered".to_string(),
+            ));
+        }
+
+        let request = RouteRequest::symbol(symbol);
+        let providers = self.router.route(&self.fundamental_providers, &request);
+        if providers.is_empty() {
+            return Err(DataError::NotSupported(format!(
+                "No registered fundamental provider supports {symbol}"
+            )));
+        }
+        Ok(providers)
+    }
+
+    // Builder methods for easy setup with specific providers
+
+    /// Add the Yahoo Finance provider.
+    #[cfg(feature = "yahoo")]
+    #[must_use]
+    pub fn with_yahoo(mut self) -> Self {
+        let provider = Arc::new(data_yahoo::YahooProvider::new());
+        self.register_price(provider.clone());
+        self.register_reference(provider);
+        self
+    }
+
+    /// Add the SEC EDGAR provider.
+    #[cfg(feature = "edgar")]
+    #[must_use]
+    pub fn with_edgar(mut self, user_agent: &str) -> Self {
+        let provider = Arc::new(data_edgar::EdgarProvider::new(user_agent));
+        self.register_fundamental(provider.clone());
+        self.register_reference(provider);
+        self
+    }
+
+    /// Add the Financial Modeling Prep provider.
+    #[cfg(feature = "fmp")]
+    #[must_use]
+    pub fn with_fmp(mut self, api_key: &str) -> Self {
+        let provider = Arc::new(data_fmp::FmpProvider::new(api_key));
+        self.register_price(provider.clone());
+        self.register_fundamental(provider.clone());
+        self.register_reference(provider);
+        self
+    }
+
+    /// Add the NASDAQ tick data provider.
+    #[cfg(feature = "nasdaq")]
+    #[must_use]
+    pub fn with_nasdaq(mut self, api_key: &str) -> Self {
+        let provider = Arc::new(data_nasdaq::NasdaqProvider::new(api_key));
+        self.register_tick(provider);
+        self
+    }
+
+    /// Add the Interactive Brokers provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - Host address for TWS or IB Gateway (usually "127.0.0.1")
+    /// * `port` - Port number (7496 for TWS paper, 7497 for TWS live, 4001/4002 for Gateway)
+    #[cfg(feature = "ibkr")]
+    #[must_use]
+    pub fn with_ibkr(mut self, host: &str, port: u16) -> Self {
+        let provider = Arc::new(data_ibkr::IbkrProvider::new(host, port));
+        self.register_price(provider.clone());
+        self.register_tick(provider.clone());
+        self.register_fundamental(provider);
+        self
+    }
+}
+
+/// Fetch OHLCV ranges from a provider and store them in the cache.
+async fn refresh_ohlcv(
+    provider: Arc<dyn PriceDataProvider>,
+    key: OhlcvCacheKey,
+    ranges: Vec<DateRange>,
+    cache: Arc<dyn DataCache>,
+    validation: Option<ValidationPolicy>,
+) -> Result<()> {
+    for range in ranges {
+        let data = match provider
+            .fetch_ohlcv(&key.symbol, range.start, range.end, key.frequency)
+            .await
+        {
+            Ok(data) => validate_ohlcv(
+                validation.as_ref(),
+                provider.name(),
+                &key.symbol,
+                key.frequency,
+                data,
+            )?,
+            Err(DataError::DataNotAvailable { .. }) => DataFrame::empty(),
+            Err(e) => return Err(e),
+        };
+        cache.put_ohlcv(&key, range.start, range.end, &data).await?;
+    }
+    Ok(())
+}
+
+/// Run a validation policy, if any, over OHLCV data from a provider, logging
+/// every issue found.
+fn validate_ohlcv(
+    policy: Option<&ValidationPolicy>,
+    provider: &str,
+    symbol: &Symbol,
+    frequency: DataFrequency,
+    data: DataFrame,
+) -> Result<DataFrame> {
+    let Some(policy) = policy else {
+        return Ok(data);
+    };
+    let validated = policy.validate(data, frequency).inspect_err(|e| {
+        warn!(provider, symbol = %symbol, error = %e, "OHLCV data rejected by validation");
+    })?;
+    for issue in &validated.issues {
+        warn!(
+            provider,
+            symbol = %symbol,
+            action = ?issue.action,
+            "OHLCV data quality issue: {issue}"
+        );
+    }
+    Ok(validated.data)
+}
+
+/// A provider request whose "not found" answer may be remembered.
+struct NegativeRequest {
+    /// Identifies the request within a symbol's negative cache entries.
+    description: String,
+    /// Range reported when a remembered `DataNotAvailable` is replayed.
+    start: String,
+    end: String,
+}
+
+impl NegativeRequest {
+    fn range(kind: impl Into<String>, start: impl fmt::Display, end: impl fmt::Display) -> Self {
+        let kind = kind.into();
+        Self {
+            description: format!("{kind}:{start}:{end}"),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    fn whole(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            start: "N/A".to_string(),
+            end: "N/A".to_string(),
+        }
+    }
+}
+
+/// Error for a cache-only request the cache cannot answer.
+fn not_cached(subject: String) -> DataError {
+    DataError::Cache(format!(
+        "{subject} is not cached and the cache policy is cache-only"
+    ))
+}
+
+/// Error returned when every candidate provider was skipped by its circuit breaker.
+fn all_circuits_open() -> DataError {
+    DataError::Other("No healthy providers available: all circuit breakers are open".to_string())
+}
+
+/// Releases a provider's half-open probe if its call is dropped unfinished.
+struct ProbeGuard<'a> {
+    health: &'a HealthTracker,
+    provider: &'a str,
+    admission: Admission,
+    finished: bool,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if !self.finished && self.admission == Admission::Probe {
+            self.health.cancel(self.provider);
+        }
+    }
+}
+
+/// Add a `symbol` column to a frame that does not already have one.
+fn with_symbol_column(mut df: DataFrame, symbol: &Symbol) -> Result<DataFrame> {
+    if df.get_column_names().iter().any(|c| c.as_str() == "symbol") {
+        return Ok(df);
+    }
+    let symbol_col = Column::new("symbol".into(), vec![symbol.as_str(); df.height()]);
+    df.with_column(symbol_col)
+        .map_err(|e| DataError::Other(e.to_string()))?;
+    Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::{QualityCheck, ValidationAction};
+    use async_trait::async_trait;
+    use data_cache::InMemoryCache;
+    use data_core::{DataProvider, FundamentalDataProvider, PriceDataProvider};
+    use std::time::Duration;
+
+    /// Price provider that serves a fixed frame for known symbols.
+    #[derive(Debug)]
+    struct MockPriceProvider {
+        name: &'static str,
+        symbols: Vec<&'static str>,
+    }
+
+    impl DataProvider for MockPriceProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Mock price provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl PriceDataProvider for MockPriceProvider {
+        async fn fetch_ohlcv(
+            &self,
+            symbol: &Symbol,
+            _start: NaiveDate,
+            _end: NaiveDate,
+            _frequency: DataFrequency,
+        ) -> Result<DataFrame> {
+            match symbol.as_str() {
+                "FAIL" => Err(DataError::Network("connection reset".to_string())),
+                s if self.symbols.contains(&s) => DataFrame::new(vec![
+                    Column::new("date".into(), vec!["2024-01-02", "2024-01-03"]),
+                    Column::new("close".into(), vec![100.0, 101.0]),
+                ])
+                .map_err(|e| DataError::Other(e.to_string())),
+                _ => Err(DataError::SymbolNotFound(symbol.to_string())),
+            }
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Price provider that returns one daily bar per requested date and
+    /// records the ranges it was asked for.
+    #[derive(Debug, Default)]
+    struct RangePriceProvider {
+        requests: std::sync::Mutex<Vec<(NaiveDate, NaiveDate)>>,
+    }
+
+    impl DataProvider for RangePriceProvider {
+        fn name(&self) -> &str {
+            "range"
+        }
+
+        fn description(&self) -> &str {
+            "Range price provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl PriceDataProvider for RangePriceProvider {
+        async fn fetch_ohlcv(
+            &self,
+            _symbol: &Symbol,
+            start: NaiveDate,
+            end: NaiveDate,
+            _frequency: DataFrequency,
+        ) -> Result<DataFrame> {
+            self.requests.lock().unwrap().push((start, end));
+            let dates: Vec<NaiveDate> = start.iter_days().take_while(|d| *d <= end).collect();
+            let closes: Vec<f64> = (0..dates.len()).map(|i| 100.0 + i as f64).collect();
+            DataFrame::new(vec![
+                Column::new("date".into(), dates),
+                Column::new("close".into(), closes),
+            ])
+            .map_err(|e| DataError::Other(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ohlcv_fills_cache_gaps() {
+        let provider = Arc::new(RangePriceProvider::default());
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()));
+        registry.register_price(provider.clone());
+        let symbol = Symbol::new("AAPL");
+
+        let data = registry
+            .fetch_ohlcv(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+        assert_eq!(data.height(), 5);
+
+        // Only the uncached tail is requested from the provider
+        let data = registry
+            .fetch_ohlcv(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 10),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+        assert_eq!(data.height(), 10);
+
+        // A covered range is served entirely from the cache
+        let data = registry
+            .fetch_ohlcv(
+                &symbol,
+                date(2024, 1, 3),
+                date(2024, 1, 8),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+        assert_eq!(data.height(), 6);
+
+        assert_eq!(
+            *provider.requests.lock().unwrap(),
+            [
+                (date(2024, 1, 1), date(2024, 1, 5)),
+                (date(2024, 1, 6), date(2024, 1, 10)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gap_fill_reports_partial_cache_hit() {
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()));
+        registry.register_price(Arc::new(RangePriceProvider::default()));
+        let symbol = Symbol::new("AAPL");
+
+        let first = registry
+            .fetch_ohlcv_with_provenance(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+        let fetched = registry
+            .fetch_ohlcv_with_provenance(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 10),
+                DataFrequency::Daily,
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.value.height(), 10);
+        assert_eq!(fetched.provider, "range");
+        assert!(!fetched.cache_hit);
+        assert!(fetched.partial_cache_hit);
+        // Stamped when the gap was fetched, not when the cached rows were
+        assert!(fetched.fetched_at > first.fetched_at);
+        assert!(!fetched.fell_back());
+    }
+
+    #[tokio::test]
+    async fn test_stitched_gaps_are_validated() {
+        let provider = Arc::new(RangePriceProvider::default());
+        // Each fetch rises steadily, so only the seam between the cached and
+        // fetched rows (104 then 100) looks like a spike
+        let policy = ValidationPolicy::new()
+            .with_max_jump(0.005)
+            .with_action(QualityCheck::JumpOutliers, ValidationAction::Error);
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()))
+            .with_validation(policy);
+        registry.register_price(provider.clone());
+        let symbol = Symbol::new("AAPL");
+
+        registry
+            .fetch_ohlcv(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+        let fetched = registry
+            .fetch_ohlcv_with_provenance(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 10),
+                DataFrequency::Daily,
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+        assert!(!fetched.partial_cache_hit);
+        assert_eq!(fetched.attempts.len(), 1);
+        assert_eq!(fetched.attempts[0].provider, "range");
+        assert!(matches!(
+            fetched.attempts[0].error,
+            DataError::Validation(_)
+        ));
+
+        // The stitched series was rejected, so the whole range was refetched
+        assert_eq!(
+            *provider.requests.lock().unwrap(),
+            [
+                (date(2024, 1, 1), date(2024, 1, 5)),
+                (date(2024, 1, 6), date(2024, 1, 10)),
+                (date(2024, 1, 1), date(2024, 1, 10)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_recent_bars_are_refetched() {
+        let provider = Arc::new(RangePriceProvider::default());
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()))
+            .with_freshness_policy(
+                FreshnessPolicy::unbounded()
+                    .with_recent_ohlcv_ttl(Duration::ZERO)
+                    .with_recent_window_days(3),
+            );
+        registry.register_price(provider.clone());
+        let symbol = Symbol::new("AAPL");
+        let today = Utc::now().date_naive();
+        let start = today - chrono::Days::new(10);
+
+        for _ in 0..2 {
+            let data = registry
+                .fetch_ohlcv(&symbol, start, today, DataFrequency::Daily)
+                .await
+                .unwrap();
+            assert_eq!(data.height(), 11);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        // Historical bars are served from the cache; recent ones are refetched
+        assert_eq!(
+            *provider.requests.lock().unwrap(),
+            [(start, today), (today - chrono::Days::new(3), today)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_bars_are_served_while_refreshing() {
+        let provider = Arc::new(RangePriceProvider::default());
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()))
+            .with_freshness_policy(
+                FreshnessPolicy::unbounded()
+                    .with_recent_ohlcv_ttl(Duration::ZERO)
+                    .with_recent_window_days(3)
+                    .with_stale_while_revalidate(Duration::from_secs(3600)),
+            );
+        registry.register_price(provider.clone());
+        let symbol = Symbol::new("AAPL");
+        let today = Utc::now().date_naive();
+        let start = today - chrono::Days::new(10);
+
+        registry
+            .fetch_ohlcv(&symbol, start, today, DataFrequency::Daily)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let fetched = registry
+            .fetch_ohlcv_with_provenance(
+                &symbol,
+                start,
+                today,
+                DataFrequency::Daily,
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+        assert!(fetched.cache_hit);
+        assert_eq!(fetched.value.height(), 11);
+
+        // The stale recent bars are refreshed in the background
+        for _ in 0..100 {
+            if provider.requests.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            provider.requests.lock().unwrap()[1],
+            (today - chrono::Days::new(3), today)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache_policy_modes() {
+        let provider = Arc::new(RangePriceProvider::default());
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()))
+            .with_freshness_policy(
+                FreshnessPolicy::default().with_ttl(CacheDataKind::Ohlcv, Duration::ZERO),
+            );
+        registry.register_price(provider.clone());
+        let symbol = Symbol::new("AAPL");
+        let fetch = |end: NaiveDate, policy: CachePolicy| {
+            registry.fetch_ohlcv_with_policy(
+                &symbol,
+                date(2024, 1, 1),
+                end,
+                DataFrequency::Daily,
+                policy,
+            )
+        };
+        let requests = || provider.requests.lock().unwrap().len();
+
+        // Offline requests fail on a miss without calling the provider
+        let result = fetch(date(2024, 1, 5), CachePolicy::CacheOnly).await;
+        assert!(matches!(result, Err(DataError::Cache(_))));
+        assert_eq!(requests(), 0);
+
+        // Network-only requests leave the cache untouched
+        fetch(date(2024, 1, 5), CachePolicy::NetworkOnly)
+            .await
+            .unwrap();
+        assert!(
+            fetch(date(2024, 1, 5), CachePolicy::CacheOnly)
+                .await
+                .is_err()
+        );
+
+        // Refreshes skip the cache but store what they fetch
+        fetch(date(2024, 1, 5), CachePolicy::Refresh).await.unwrap();
+        fetch(date(2024, 1, 5), CachePolicy::Refresh).await.unwrap();
+        assert_eq!(requests(), 3);
+
+        // Offline requests serve expired bars, but not partial coverage
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let data = fetch(date(2024, 1, 5), CachePolicy::CacheOnly)
+            .await
+            .unwrap();
+        assert_eq!(data.height(), 5);
+        assert!(
+            fetch(date(2024, 1, 8), CachePolicy::CacheOnly)
+                .await
+                .is_err()
+        );
+        assert_eq!(requests(), 3);
+
+        // The default policy refetches the expired bars
+        fetch(date(2024, 1, 5), CachePolicy::Default).await.unwrap();
+        assert_eq!(requests(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_provenance() {
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "primary",
+            symbols: vec![],
+        }));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "secondary",
+            symbols: vec!["AAPL"],
+        }));
+        let symbol = Symbol::new("AAPL");
+        let fetch = |policy| {
+            registry.fetch_ohlcv_with_provenance(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+                policy,
+            )
+        };
+
+        let first = fetch(CachePolicy::Default).await.unwrap();
+        assert_eq!(first.provider, "secondary");
+        assert!(!first.cache_hit);
+        assert_eq!(first.cache_policy, CachePolicy::Default);
+        assert_eq!(first.request.symbol(), &symbol);
+        assert!(first.fell_back());
+        assert_eq!(first.attempts[0].provider, "primary");
+        assert!(matches!(
+            first.attempts[0].error,
+            DataError::SymbolNotFound(_)
+        ));
+
+        // Cached data reports when it was originally fetched, and the
+        // per-request policy it was served under
+        let second = fetch(CachePolicy::CacheOnly).await.unwrap();
+        assert_eq!(second.provider, "secondary");
+        assert!(second.cache_hit);
+        assert_eq!(second.cache_policy, CachePolicy::CacheOnly);
+        assert!(!second.fell_back());
+        assert!(second.fetched_at >= first.fetched_at);
+        assert!(second.fetched_at <= Utc::now());
+        assert_eq!(second.value.height(), 2);
+    }
+
+    /// Price provider that answers slowly, counting how often it is asked.
+    #[derive(Debug, Default)]
+    struct SlowPriceProvider {
+        calls: std::sync::atomic::AtomicUsize,
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    impl DataProvider for SlowPriceProvider {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn description(&self) -> &str {
+            "Slow price provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl PriceDataProvider for SlowPriceProvider {
+        async fn fetch_ohlcv(
+            &self,
+            _symbol: &Symbol,
+            _start: NaiveDate,
+            _end: NaiveDate,
+            _frequency: DataFrequency,
+        ) -> Result<DataFrame> {
+            use std::sync::atomic::Ordering::SeqCst;
+            self.calls.fetch_add(1, SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, SeqCst);
+            DataFrame::new(vec![
+                Column::new("date".into(), vec!["2024-01-02"]),
+                Column::new("close".into(), vec![100.0]),
+            ])
+            .map_err(|e| DataError::Other(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_fetches_are_coalesced() {
+        let provider = Arc::new(SlowPriceProvider::default());
+        let mut registry = DataProviderRegistry::new();
+        registry.register_price(provider.clone());
+        let (aapl, msft) = (Symbol::new("AAPL"), Symbol::new("MSFT"));
+        let fetch = |symbol| {
+            registry.fetch_ohlcv(
+                symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+        };
+
+        let results =
+            futures::future::join_all([fetch(&aapl), fetch(&aapl), fetch(&aapl), fetch(&msft)])
+                .await;
+        assert!(
+            results
+                .iter()
+                .all(|r| r.as_ref().is_ok_and(|df| df.height() == 1))
+        );
+        let calls = || provider.calls.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(calls(), 2);
+
+        // Finished requests are not shared
+        fetch(&aapl).await.unwrap();
+        assert_eq!(calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_probe_losing_race_is_released() {
+        let mut registry = DataProviderRegistry::new()
+            .with_fallback_mode(FallbackMode::Race)
+            .with_health_config(HealthConfig {
+                failure_threshold: 1,
+                open_duration: Duration::ZERO,
+                ..Default::default()
+            });
+        registry.register_price(Arc::new(SlowPriceProvider::default()));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "fast",
+            symbols: vec!["AAPL"],
+        }));
+        // Leave the slow provider half-open, so its next call is the probe
+        registry.health.record(
+            "slow",
+            Admission::Request,
+            Duration::ZERO,
+            &Err::<(), _>(DataError::Network("timeout".to_string())),
+        );
+
+        let fetched = registry
+            .fetch_ohlcv_with_provenance(
+                &Symbol::new("AAPL"),
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.provider, "fast");
+        assert_eq!(registry.health.allow("slow"), Some(Admission::Probe));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_modes() {
+        let registry = |mode| {
+            let mut registry = DataProviderRegistry::new().with_fallback_mode(mode);
+            registry.register_price(Arc::new(SlowPriceProvider::default()));
+            registry.register_price(Arc::new(MockPriceProvider {
+                name: "fast",
+                symbols: vec!["AAPL"],
+            }));
+            registry
+        };
+        let served_by = |registry: DataProviderRegistry| async move {
+            registry
+                .fetch_ohlcv_with_provenance(
+                    &Symbol::new("AAPL"),
+                    date(2024, 1, 1),
+                    date(2024, 1, 5),
+                    DataFrequency::Daily,
+                    CachePolicy::Default,
+                )
+                .await
+                .unwrap()
+                .provider
+        };
+
+        assert_eq!(served_by(registry(FallbackMode::Sequential)).await, "slow");
+        let hedged = FallbackMode::Hedged {
+            delay: Duration::from_millis(1),
+        };
+        assert_eq!(served_by(registry(hedged)).await, "fast");
+        assert_eq!(served_by(registry(FallbackMode::Race)).await, "fast");
+
+        // A failure hedges immediately, whatever the delay
+        let mut registry = DataProviderRegistry::new().with_fallback_mode(FallbackMode::Hedged {
+            delay: Duration::from_secs(60),
+        });
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "primary",
+            symbols: vec![],
+        }));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "secondary",
+            symbols: vec!["AAPL"],
+        }));
+        let fetched = tokio::time::timeout(Duration::from_secs(5), served_by(registry)).await;
+        assert_eq!(fetched.unwrap(), "secondary");
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_ohlcv_compares_every_provider() {
+        let mut registry = DataProviderRegistry::new();
+        for (name, symbols) in [
+            ("primary", vec!["AAPL"]),
+            ("unlisted", vec![]),
+            ("secondary", vec!["AAPL"]),
+        ] {
+            registry.register_price(Arc::new(MockPriceProvider { name, symbols }));
+        }
+
+        let policy = ReconcilePolicy::new().with_reference("secondary");
+        let reconciliation = registry
+            .reconcile_ohlcv(
+                &Symbol::new("AAPL"),
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+                &policy,
+            )
+            .await
+            .unwrap();
+        assert_eq!(reconciliation.providers, ["secondary", "primary"]);
+        assert_eq!(reconciliation.failures.len(), 1);
+        assert_eq!(reconciliation.failures[0].provider, "unlisted");
+        assert_eq!(reconciliation.report.height(), 2);
+        assert_eq!(reconciliation.discrepancies().unwrap().height(), 0);
+        // A provider failed, so the sources cannot be called consistent
+        assert!(!reconciliation.is_consistent());
+    }
+
+    #[derive(Debug)]
+    struct UnsortedPriceProvider;
+
+    impl DataProvider for UnsortedPriceProvider {
+        fn name(&self) -> &str {
+            "unsorted"
+        }
+
+        fn description(&self) -> &str {
+            "Price provider returning unsorted bars"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl PriceDataProvider for UnsortedPriceProvider {
+        async fn fetch_ohlcv(
+            &self,
+            _symbol: &Symbol,
+            _start: NaiveDate,
+            _end: NaiveDate,
+            _frequency: DataFrequency,
+        ) -> Result<DataFrame> {
+            DataFrame::new(vec![
+                Column::new("date".into(), vec!["2024-01-03", "2024-01-02"]),
+                Column::new("close".into(), vec![101.0, 100.0]),
+            ])
+            .map_err(|e| DataError::Other(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_rejects_or_repairs_provider_data() {
+        let registry = |action| {
+            let policy = ValidationPolicy::new().with_action(QualityCheck::UnsortedDates, action);
+            let mut registry = DataProviderRegistry::new().with_validation(policy);
+            registry.register_price(Arc::new(UnsortedPriceProvider));
+            registry.register_price(Arc::new(MockPriceProvider {
+                name: "secondary",
+                symbols: vec!["AAPL"],
+            }));
+            registry
+        };
+        let fetch = |registry: DataProviderRegistry| async move {
+            registry
+                .fetch_ohlcv_with_provenance(
+                    &Symbol::new("AAPL"),
+                    date(2024, 1, 1),
+                    date(2024, 1, 5),
+                    DataFrequency::Daily,
+                    CachePolicy::Default,
+                )
+                .await
+                .unwrap()
+        };
+
+        let rejected = fetch(registry(ValidationAction::Error)).await;
+        assert_eq!(rejected.provider, "secondary");
+        assert!(matches!(
+            rejected.attempts[0].error,
+            DataError::Validation(_)
+        ));
+
+        let repaired = fetch(registry(ValidationAction::Fill)).await;
+        assert_eq!(repaired.provider, "unsorted");
+        let closes: Vec<Option<f64>> = repaired
+            .value
+            .column("close")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(closes, [Some(100.0), Some(101.0)]);
+    }
+
+    /// A fundamental provider that, like FMP, only declares daily frequency.
+    #[derive(Debug)]
+    struct DailyFundamentalProvider;
+
+    impl DataProvider for DailyFundamentalProvider {
+        fn name(&self) -> &str {
+            "daily-only"
+        }
+
+        fn description(&self) -> &str {
+            "Fundamental provider declaring daily frequency"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl FundamentalDataProvider for DailyFundamentalProvider {
+        async fn fetch_financials(
+            &self,
+            symbol: &Symbol,
+            period_type: PeriodType,
+            _limit: Option<usize>,
+        ) -> Result<Vec<FinancialStatement>> {
+            Ok(vec![FinancialStatement {
+                revenue: Some(100.0),
+                ..FinancialStatement::new(symbol.clone(), date(2023, 12, 31), period_type)
+            }])
+        }
+
+        async fn fetch_metrics(&self, symbol: &Symbol, date: NaiveDate) -> Result<KeyMetrics> {
+            Ok(KeyMetrics::new(symbol.clone(), date))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fundamentals_are_not_routed_by_price_frequency() {
+        let mut registry = DataProviderRegistry::new();
+        registry.register_fundamental(Arc::new(DailyFundamentalProvider));
+        let symbol = Symbol::new("AAPL");
+
+        for period_type in [PeriodType::Annual, PeriodType::Quarterly] {
+            let statements = registry
+                .fetch_financials(&symbol, period_type, None)
+                .await
+                .unwrap();
+            assert_eq!(statements.len(), 1);
+        }
+        let merged = registry
+            .fetch_financials_merged(
+                &symbol,
+                PeriodType::Annual,
+                None,
+                &MergePolicy::new(),
+                CachePolicy::Default,
+            )
+            .await
+            .unwrap();
+        assert_eq!(merged[0].source("revenue"), Some("daily-only"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_fetches_symbols_concurrently() {
+        let provider = Arc::new(SlowPriceProvider::default());
+        let mut registry = DataProviderRegistry::new();
+        registry.register_price(provider.clone());
+
+        let symbols: Vec<_> = ["AAPL", "MSFT", "GOOG", "AMZN"]
+            .into_iter()
+            .map(Symbol::new)
+            .collect();
+        let result = registry
+            .fetch_ohlcv_batch(
+                &symbols,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.succeeded().count(), 4);
+        let symbol_order: Vec<_> = result
+            .data
+            .column("symbol")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(symbol_order, ["AAPL", "MSFT", "GOOG", "AMZN"]);
+        assert_eq!(
+            provider
+                .max_in_flight
+                .load(std::sync::atomic::Ordering::SeqCst),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_reports_per_symbol_outcomes() {
+        let mut registry = DataProviderRegistry::new();
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "primary",
+            symbols: vec!["AAPL"],
+        }));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "secondary",
+            symbols: vec!["AAPL", "MSFT"],
+        }));
+
+        let symbols = [
+            Symbol::new("AAPL"),
+            Symbol::new("MSFT"),
+            Symbol::new("ZZZZ"),
+            Symbol::new("FAIL"),
+        ];
+        let result = registry
+            .fetch_ohlcv_batch(
+                &symbols,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.data.height(), 4);
+        assert!(result.data.column("symbol").is_ok());
+
+        let aapl = result.outcome(&Symbol::new("AAPL")).unwrap();
+        assert_eq!(aapl.provider.as_deref(), Some("primary"));
+        assert!(!aapl.cache_hit);
+        assert_eq!(aapl.rows, 2);
+
+        let msft = result.outcome(&Symbol::new("MSFT")).unwrap();
+        assert_eq!(msft.provider.as_deref(), Some("secondary"));
+
+        let missing = result.outcome(&Symbol::new("ZZZZ")).unwrap();
+        assert!(matches!(missing.error, Some(DataError::SymbolNotFound(_))));
+
+        let failed = result.outcome(&Symbol::new("FAIL")).unwrap();
+        assert!(matches!(failed.error, Some(DataError::Network(_))));
+        assert!(!result.is_complete());
+    }
+
+    /// Price provider that has no data, counting how often it is asked.
+    #[derive(Debug, Default)]
+    struct MissingPriceProvider {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MissingPriceProvider {
+        fn calls(&self) -> usize {
+            self.calls.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl DataProvider for MissingPriceProvider {
+        fn name(&self) -> &str {
+            "missing"
+        }
+
+        fn description(&self) -> &str {
+            "Missing price provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[DataFrequency::Daily]
+        }
+    }
+
+    #[async_trait]
+    impl PriceDataProvider for MissingPriceProvider {
+        async fn fetch_ohlcv(
+            &self,
+            symbol: &Symbol,
+            start: NaiveDate,
+            end: NaiveDate,
+            _frequency: DataFrequency,
+        ) -> Result<DataFrame> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if symbol.as_str() == "GONE" {
+                return Err(DataError::SymbolNotFound(symbol.to_string()));
+            }
+            Err(DataError::DataNotAvailable {
+                symbol: symbol.to_string(),
+                start: start.to_string(),
+                end: end.to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_data_is_remembered() {
+        let provider = Arc::new(MissingPriceProvider::default());
+        let mut registry = DataProviderRegistry::with_cache(Arc::new(InMemoryCache::new()));
+        registry.register_price(provider.clone());
+        let fetch = |symbol: &'static str, end: NaiveDate| {
+            let registry = &registry;
+            async move {
+                registry
+                    .fetch_ohlcv(
+                        &Symbol::new(symbol),
+                        date(2024, 1, 1),
+                        end,
+                        DataFrequency::Daily,
+                    )
+                    .await
+            }
+        };
+
+        // An unknown symbol is skipped for every request
+        for end in [date(2024, 1, 5), date(2024, 1, 5), date(2024, 2, 1)] {
+            let result = fetch("GONE", end).await;
+            assert!(matches!(result, Err(DataError::SymbolNotFound(_))));
+        }
+        assert_eq!(provider.calls(), 1);
+
+        // Missing data is only skipped for the same request
+        for _ in 0..2 {
+            let result = fetch("AAPL", date(2024, 1, 5)).await;
+            assert!(
+                matches!(result, Err(DataError::DataNotAvailable { end, .. }) if end == "2024-01-05")
+            );
+        }
+        assert_eq!(provider.calls(), 2);
+        assert!(fetch("AAPL", date(2024, 2, 1)).await.is_err());
+        assert_eq!(provider.calls(), 3);
+
+        // A zero negative TTL asks the provider every time
+        let registry = registry
+            .with_freshness_policy(FreshnessPolicy::default().with_negative_ttl(Duration::ZERO));
+        for _ in 0..2 {
+            let result = registry
+                .fetch_ohlcv(
+                    &Symbol::new("GONE"),
+                    date(2024, 1, 1),
+                    date(2024, 1, 5),
+                    DataFrequency::Daily,
+                )
+                .await;
+            assert!(result.is_err());
+        }
+        assert_eq!(provider.calls(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_routing_skips_incapable_and_applies_rules() {
+        let mut registry =
+            DataProviderRegistry::new().with_routing_rule(RoutingRule::only("*.L", ["secondary"]));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "primary",
+            symbols: vec!["VOD.L"],
+        }));
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "secondary",
+            symbols: vec!["VOD.L"],
+        }));
+
+        let err = registry
+            .fetch_ohlcv(
+                &Symbol::new("AAPL"),
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Minute,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DataError::NotSupported(_)));
+
+        let result = registry
+            .fetch_ohlcv_batch(
+                &[Symbol::new("VOD.L")],
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap();
+        let outcome = result.outcome(&Symbol::new("VOD.L")).unwrap();
+        assert_eq!(outcome.provider.as_deref(), Some("secondary"));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_skips_failing_provider() {
+        let mut registry = DataProviderRegistry::new().with_health_config(HealthConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        });
+        registry.register_price(Arc::new(MockPriceProvider {
+            name: "primary",
+            symbols: vec![],
+        }));
+
+        let symbol = Symbol::new("FAIL");
+        for _ in 0..2 {
+            let err = registry
+                .fetch_ohlcv(
+                    &symbol,
+                    date(2024, 1, 1),
+                    date(2024, 1, 5),
+                    DataFrequency::Daily,
+                )
+                .await
+                .unwrap_err();
+            assert!(matches!(err, DataError::Network(_)));
+        }
+
+        let err = registry
+            .fetch_ohlcv(
+                &symbol,
+                date(2024, 1, 1),
+                date(2024, 1, 5),
+                DataFrequency::Daily,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DataError::Other(_)));
+
+        let health = registry.health_snapshot();
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].provider, "primary");
+        assert_eq!(health[0].state, crate::CircuitState::Open);
+        assert_eq!(health[0].total_requests, 2);
+    }
+
+    /// Reference provider that counts calls and optionally fails.
+    #[derive(Debug)]
+    struct MockReferenceProvider {
+        name: &'static str,
+        fail: bool,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl DataProvider for MockReferenceProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Mock reference provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[]
+        }
+    }
+
+    #[async_trait]
+    impl ReferenceDataProvider for MockReferenceProvider {
+        async fn company_info(&self, symbol: &Symbol) -> Result<CompanyInfo> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail {
+                return Err(DataError::Network("connection reset".to_string()));
+            }
+            Ok(CompanyInfo::new(
+                symbol.clone(),
+                "Apple Inc.",
+                "NASDAQ",
+                "Technology",
+                "Consumer Electronics",
+                "US",
+                "USD",
+            ))
+        }
+
+        async fn universe(&self, universe_id: &str) -> Result<Vec<Symbol>> {
+            Err(DataError::NotSupported(universe_id.to_string()))
+        }
+
+        async fn supports_symbol(&self, symbol: &Symbol) -> Result<bool> {
+            Ok(symbol.as_str() == "AAPL")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_company_info_falls_back_and_caches() {
+        let failing = Arc::new(MockReferenceProvider {
+            name: "failing",
+            fail: true,
+            calls: Default::default(),
+        });
+        let working = Arc::new(MockReferenceProvider {
+            name: "working",
+            fail: false,
+            calls: Default::default(),
+        });
+        let mut registry = DataProviderRegistry::new();
+        registry.register_reference(failing.clone());
+        registry.register_reference(working.clone());
+
+        let symbol = Symbol::new("AAPL");
+        let info = registry.company_info(&symbol).await.unwrap();
+        assert_eq!(info.name, "Apple Inc.");
+        let info = registry.company_info(&symbol).await.unwrap();
+        assert_eq!(info.exchange, "NASDAQ");
+        assert_eq!(working.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        assert!(registry.supports_symbol(&symbol).await.unwrap());
+        assert!(
+            !registry
+                .supports_symbol(&Symbol::new("ZZZZ"))
+                .await
+                .unwrap()
+        );
+
+        // No provider declares the universe, so none are tried
+        let err = registry.universe("sp500").await.unwrap_err();
+        assert!(matches!(err, DataError::NotSupported(_)));
+    }
+
+    #[tokio::test]
+    async fn test_memoised_reference_data_follows_freshness_policy() {
+        let working = Arc::new(MockReferenceProvider {
+            name: "working",
+            fail: false,
+            calls: Default::default(),
+        });
+        let mut registry = DataProviderRegistry::new().with_freshness_policy(
+            FreshnessPolicy::default().with_ttl(CacheDataKind::CompanyInfo, Duration::ZERO),
+        );
+        registry.register_reference(working.clone());
+
+        // An expired company info TTL is not outlived by the in-process memo
+        let symbol = Symbol::new("AAPL");
+        registry.company_info(&symbol).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        registry.company_info(&symbol).await.unwrap();
+        assert_eq!(working.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_only_reference_data_never_calls_providers() {
+        let cache: Arc<dyn DataCache> = Arc::new(InMemoryCache::new());
+        let working = Arc::new(MockReferenceProvider {
+            name: "working",
+            fail: false,
+            calls: Default::default(),
+        });
+        let mut registry = DataProviderRegistry::with_cache(cache.clone())
+            .with_cache_policy(CachePolicy::CacheOnly);
+        registry.register_reference(working.clone());
+
+        let symbol = Symbol::new("AAPL");
+        let err = registry.company_info(&symbol).await.unwrap_err();
+        assert!(matches!(err, DataError::Cache(_)));
+        let err = registry.supports_symbol(&symbol).await.unwrap_err();
+        assert!(matches!(err, DataError::Cache(_)));
+        assert_eq!(working.calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // Cached entries are served whatever their age
+        let info = working.company_info(&symbol).await.unwrap();
+        cache
+            .put_company_info("working", &symbol, &info)
+            .await
+            .unwrap();
+        let registry = registry.with_freshness_policy(
+            FreshnessPolicy::default().with_ttl(CacheDataKind::CompanyInfo, Duration::ZERO),
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(registry.company_info(&symbol).await.unwrap(), info);
+        assert_eq!(working.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_company_info_persists_in_data_cache() {
+        let cache: Arc<dyn DataCache> = Arc::new(InMemoryCache::new());
+        let working = Arc::new(MockReferenceProvider {
+            name: "working",
+            fail: false,
+            calls: Default::default(),
+        });
+        let mut registry = DataProviderRegistry::with_cache(cache.clone());
+        registry.register_reference(working.clone());
+
+        let symbol = Symbol::new("AAPL");
+        registry.company_info(&symbol).await.unwrap();
+
+        // A fresh registry has an empty in-process cache but shares the data cache
+        let mut registry = DataProviderRegistry::with_cache(cache);
+        registry.register_reference(working.clone());
+        let info = registry.company_info(&symbol).await.unwrap();
+        assert_eq!(info.name, "Apple Inc.");
+        assert_eq!(working.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
     }
 }
@@ -0,0 +1,380 @@
+//! Capability-aware provider routing.
+//!
+//! Before the registry tries providers for a request, the [`Router`] drops
+//! providers whose declared [`ProviderCapabilities`](data_core::ProviderCapabilities)
+//! cannot serve it (unsupported frequency, history too deep, wrong asset class,
+//! exchange or universe) and applies user [`RoutingRule`]s that prefer or
+//! restrict providers for matching symbols. The [`FallbackMode`] decides
+//! whether the remaining providers are then called one at a time or raced.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use tracing::debug;
+
+use data_core::{AssetClass, DataFrequency, DataProvider, Symbol};
+
+/// How a routing rule affects the providers it names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoutingMode {
+    /// Try the named providers first, then the remaining ones.
+    #[default]
+    Prefer,
+    /// Only try the named providers.
+    Only,
+}
+
+/// How the registry moves through the routed providers for a request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FallbackMode {
+    /// Call one provider at a time, moving on only when one fails.
+    #[default]
+    Sequential,
+    /// Start the next provider whenever the running ones have not answered
+    /// within `delay`, or as soon as one fails, and take the first success.
+    Hedged {
+        /// How long to wait for the running providers before adding another.
+        delay: Duration,
+    },
+    /// Call every provider at once and take the first success.
+    Race,
+}
+
+/// A user routing rule mapping symbols to providers.
+///
+/// Patterns are matched case-insensitively against the whole symbol and may
+/// use `*` as a wildcard, e.g. `"*.L"` for London listings or `"^*"` for
+/// indices. The first matching rule wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoutingRule {
+    pattern: String,
+    providers: Vec<String>,
+    mode: RoutingMode,
+}
+
+impl RoutingRule {
+    /// Create a rule that tries the given providers first for matching symbols.
+    #[must_use]
+    pub fn prefer<I, S>(pattern: impl Into<String>, providers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(pattern, providers, RoutingMode::Prefer)
+    }
+
+    /// Create a rule that restricts matching symbols to the given providers.
+    #[must_use]
+    pub fn only<I, S>(pattern: impl Into<String>, providers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(pattern, providers, RoutingMode::Only)
+    }
+
+    /// Create a rule with an explicit mode.
+    #[must_use]
+    pub fn new<I, S>(pattern: impl Into<String>, providers: I, mode: RoutingMode) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            pattern: pattern.into().to_uppercase(),
+            providers: providers.into_iter().map(Into::into).collect(),
+            mode,
+        }
+    }
+
+    /// Returns the symbol pattern.
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns the provider names this rule routes to.
+    #[must_use]
+    pub fn providers(&self) -> &[String] {
+        &self.providers
+    }
+
+    /// Returns the routing mode.
+    #[must_use]
+    pub const fn mode(&self) -> RoutingMode {
+        self.mode
+    }
+
+    /// Returns true if the symbol matches this rule's pattern.
+    #[must_use]
+    pub fn matches(&self, symbol: &Symbol) -> bool {
+        glob_match(&self.pattern, symbol.as_str())
+    }
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the prefix must be the whole text
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// What a request needs from a provider.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct RouteRequest<'a> {
+    pub(crate) symbol: Option<&'a Symbol>,
+    pub(crate) frequency: Option<DataFrequency>,
+    pub(crate) start: Option<NaiveDate>,
+    pub(crate) universe: Option<&'a str>,
+}
+
+impl<'a> RouteRequest<'a> {
+    /// A request for data about a single symbol.
+    pub(crate) fn symbol(symbol: &'a Symbol) -> Self {
+        Self {
+            symbol: Some(symbol),
+            ..Default::default()
+        }
+    }
+
+    /// A request to resolve a named universe.
+    pub(crate) fn universe(universe_id: &'a str) -> Self {
+        Self {
+            universe: Some(universe_id),
+            ..Default::default()
+        }
+    }
+
+    /// Require a frequency.
+    pub(crate) const fn frequency(mut self, frequency: DataFrequency) -> Self {
+        self.frequency = Some(frequency);
+        self
+    }
+
+    /// Require history back to `start`.
+    pub(crate) const fn since(mut self, start: NaiveDate) -> Self {
+        self.start = Some(start);
+        self
+    }
+}
+
+/// Orders and filters providers for a request.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Router {
+    rules: Vec<RoutingRule>,
+}
+
+impl Router {
+    /// Add a rule; rules are evaluated in insertion order.
+    pub(crate) fn add_rule(&mut self, rule: RoutingRule) {
+        self.rules.push(rule);
+    }
+
+    /// Returns the configured rules.
+    pub(crate) fn rules(&self) -> &[RoutingRule] {
+        &self.rules
+    }
+
+    /// Returns the providers able to serve the request, in the order they
+    /// should be tried.
+    pub(crate) fn route<'p, P>(
+        &self,
+        providers: &'p [Arc<P>],
+        request: &RouteRequest<'_>,
+    ) -> Vec<&'p Arc<P>>
+    where
+        P: DataProvider + ?Sized,
+    {
+        let mut ordered: Vec<&Arc<P>> = providers.iter().collect();
+
+        if let Some(rule) = request
+            .symbol
+            .and_then(|s| self.rules.iter().find(|r| r.matches(s)))
+        {
+            let mut preferred: Vec<&Arc<P>> = rule
+                .providers
+                .iter()
+                .filter_map(|name| ordered.iter().find(|p| p.name() == name).copied())
+                .collect();
+            if rule.mode == RoutingMode::Prefer {
+                preferred.extend(
+                    ordered
+                        .iter()
+                        .filter(|p| !rule.providers.iter().any(|n| n == p.name()))
+                        .copied(),
+                );
+            }
+            ordered = preferred;
+        }
+
+        ordered.retain(|p| {
+            let capable = is_capable(p.as_ref(), request);
+            if !capable {
+                debug!(
+                    provider = p.name(),
+                    "Provider cannot serve request, skipping"
+                );
+            }
+            capable
+        });
+        ordered
+    }
+}
+
+/// Check a provider's declared capabilities against a request.
+fn is_capable<P>(provider: &P, request: &RouteRequest<'_>) -> bool
+where
+    P: DataProvider + ?Sized,
+{
+    let caps = provider.capabilities();
+
+    if let Some(frequency) = request.frequency {
+        if !caps.supports_frequency(frequency) {
+            return false;
+        }
+        if let Some(start) = request.start {
+            if !caps.covers_history(frequency, start, Utc::now().date_naive()) {
+                return false;
+            }
+        }
+    }
+
+    if let Some(symbol) = request.symbol {
+        if !caps.supports_exchange(symbol) {
+            return false;
+        }
+        if let Some(asset_class) = AssetClass::infer(symbol) {
+            if !caps.supports_asset_class(asset_class) {
+                return false;
+            }
+        }
+    }
+
+    request
+        .universe
+        .is_none_or(|universe| caps.supports_universe(universe))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_core::ProviderCapabilities;
+
+    #[derive(Debug)]
+    struct StubProvider {
+        name: &'static str,
+        capabilities: ProviderCapabilities,
+    }
+
+    impl DataProvider for StubProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Stub provider"
+        }
+
+        fn supported_frequencies(&self) -> &[DataFrequency] {
+            &[]
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            self.capabilities.clone()
+        }
+    }
+
+    fn providers() -> Vec<Arc<StubProvider>> {
+        vec![
+            Arc::new(StubProvider {
+                name: "yahoo",
+                capabilities: ProviderCapabilities::new(&[
+                    DataFrequency::Minute,
+                    DataFrequency::Daily,
+                ])
+                .with_max_history(DataFrequency::Minute, 30),
+            }),
+            Arc::new(StubProvider {
+                name: "fmp",
+                capabilities: ProviderCapabilities::new(&[DataFrequency::Daily])
+                    .with_exchanges(&["", "L"]),
+            }),
+            Arc::new(StubProvider {
+                name: "edgar",
+                capabilities: ProviderCapabilities::new(&[DataFrequency::Annual])
+                    .with_exchanges(&[""]),
+            }),
+        ]
+    }
+
+    fn names<P: DataProvider + ?Sized>(routed: &[&Arc<P>]) -> Vec<String> {
+        routed.iter().map(|p| p.name().to_string()).collect()
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.L", "VOD.L"));
+        assert!(!glob_match("*.L", "AAPL"));
+        assert!(glob_match("^*", "^GSPC"));
+        assert!(glob_match("AAPL", "AAPL"));
+        assert!(!glob_match("AAPL", "AAPLX"));
+        assert!(glob_match("A*L*", "AXXLYY"));
+    }
+
+    #[test]
+    fn test_route_filters_by_frequency_and_history() {
+        let providers = providers();
+        let router = Router::default();
+        let symbol = Symbol::new("AAPL");
+
+        let minute = RouteRequest::symbol(&symbol).frequency(DataFrequency::Minute);
+        assert_eq!(names(&router.route(&providers, &minute)), ["yahoo"]);
+
+        let old_minute = minute.since(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+        assert!(router.route(&providers, &old_minute).is_empty());
+
+        let daily = RouteRequest::symbol(&symbol).frequency(DataFrequency::Daily);
+        assert_eq!(names(&router.route(&providers, &daily)), ["yahoo", "fmp"]);
+    }
+
+    #[test]
+    fn test_route_filters_by_exchange() {
+        let providers = providers();
+        let router = Router::default();
+        let symbol = Symbol::new("VOD.L");
+
+        let request = RouteRequest::symbol(&symbol);
+        assert_eq!(names(&router.route(&providers, &request)), ["yahoo", "fmp"]);
+    }
+
+    #[test]
+    fn test_route_applies_rules() {
+        let providers = providers();
+        let mut router = Router::default();
+        router.add_rule(RoutingRule::prefer("*.L", ["fmp"]));
+        router.add_rule(RoutingRule::only("BRK-*", ["edgar"]));
+
+        let london = Symbol::new("vod.l");
+        let request = RouteRequest::symbol(&london).frequency(DataFrequency::Daily);
+        assert_eq!(names(&router.route(&providers, &request)), ["fmp", "yahoo"]);
+
+        let brk = Symbol::new("BRK-B");
+        let request = RouteRequest::symbol(&brk);
+        assert_eq!(names(&router.route(&providers, &request)), ["edgar"]);
+    }
+}
@@ -0,0 +1,809 @@
+//! Data-quality validation of OHLCV bars.
+//!
+//! Providers do not always return sane bars: Yahoo reports `None` opens for
+//! halted days, feeds occasionally repeat or reorder dates, and a single bad
+//! tick can move a close by an order of magnitude. A [`ValidationPolicy`]
+//! runs a set of [`QualityCheck`]s over a frame and, for each check, warns,
+//! drops the offending bars, repairs them, or rejects the frame.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use polars::prelude::{Column, DataFrame, DataType, IdxCa, IdxSize};
+
+use data_core::{DataError, DataFrequency, Result};
+
+/// Default number of consecutive missing weekdays tolerated between daily bars.
+const DEFAULT_MAX_GAP_WEEKDAYS: usize = 2;
+
+/// Default number of consecutive bars with the same close that count as stale.
+const DEFAULT_STALE_BARS: usize = 5;
+
+/// Default largest one-bar move, as a fraction of the neighbouring closes.
+const DEFAULT_MAX_JUMP: f64 = 0.5;
+
+/// Price columns checked, in the order they are reported.
+const PRICE_COLUMNS: [&str; 4] = ["open", "high", "low", "close"];
+
+/// A data-quality check on OHLCV bars.
+///
+/// Checks run in the order listed here, so later checks see the bars left by
+/// earlier ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum QualityCheck {
+    /// Dates that go backwards. `Fill` sorts the bars by date.
+    UnsortedDates,
+    /// Dates that appear more than once. `Drop` and `Fill` keep the first bar.
+    DuplicateDates,
+    /// Null or NaN prices. `Fill` uses the bar's close, or else the previous
+    /// close, dropping bars with neither.
+    MissingPrices,
+    /// Lows above the open or close, or highs below them. `Fill` widens the
+    /// high and low to cover the open and close.
+    OhlcConsistency,
+    /// Negative volume. `Fill` replaces it with null.
+    NegativeVolume,
+    /// A close that moves more than the policy's maximum jump away from both
+    /// neighbouring closes in the same direction. `Fill` replaces the bar's
+    /// prices with the previous close.
+    JumpOutliers,
+    /// A close that repeats for at least the policy's stale bar count. `Drop`
+    /// keeps the first bar of the run; `Fill` behaves like `Warn`.
+    StalePrices,
+    /// More missing weekdays between daily bars than the policy allows. Only
+    /// checked for daily data; `Drop` and `Fill` behave like `Warn`.
+    CalendarGaps,
+}
+
+impl QualityCheck {
+    /// Every check, in the order they run.
+    pub const ALL: [Self; 8] = [
+        Self::UnsortedDates,
+        Self::DuplicateDates,
+        Self::MissingPrices,
+        Self::OhlcConsistency,
+        Self::NegativeVolume,
+        Self::JumpOutliers,
+        Self::StalePrices,
+        Self::CalendarGaps,
+    ];
+
+    /// Returns the check's name.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnsortedDates => "unsorted_dates",
+            Self::DuplicateDates => "duplicate_dates",
+            Self::MissingPrices => "missing_prices",
+            Self::OhlcConsistency => "ohlc_consistency",
+            Self::NegativeVolume => "negative_volume",
+            Self::JumpOutliers => "jump_outliers",
+            Self::StalePrices => "stale_prices",
+            Self::CalendarGaps => "calendar_gaps",
+        }
+    }
+}
+
+impl fmt::Display for QualityCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What to do with bars that fail a check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationAction {
+    /// Report the issue and keep the bars unchanged.
+    #[default]
+    Warn,
+    /// Remove the offending bars.
+    Drop,
+    /// Repair the offending bars; see each [`QualityCheck`] for how.
+    Fill,
+    /// Reject the whole frame with [`DataError::Validation`].
+    Error,
+}
+
+/// A problem found by a check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualityIssue {
+    /// The check that found it.
+    pub check: QualityCheck,
+    /// Date of the offending bar, as it appears in the frame.
+    pub date: Option<String>,
+    /// What was done about it.
+    pub action: ValidationAction,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for QualityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.date {
+            Some(date) => write!(f, "{} on {date}: {}", self.check, self.message),
+            None => write!(f, "{}: {}", self.check, self.message),
+        }
+    }
+}
+
+/// A validated frame and the issues found in it.
+#[derive(Debug)]
+pub struct Validated {
+    /// The bars left after dropping and repairing.
+    pub data: DataFrame,
+    /// Every issue found, in check order.
+    pub issues: Vec<QualityIssue>,
+}
+
+/// Which quality checks to run on OHLCV data and what to do when they fail.
+///
+/// Frames are expected to have a `date` column and any of `open`, `high`,
+/// `low`, `close` and `volume`; checks whose columns are absent are skipped.
+#[derive(Clone, Debug)]
+pub struct ValidationPolicy {
+    actions: BTreeMap<QualityCheck, ValidationAction>,
+    max_gap_weekdays: usize,
+    stale_bars: usize,
+    max_jump: f64,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            actions: QualityCheck::ALL
+                .into_iter()
+                .map(|check| (check, ValidationAction::Warn))
+                .collect(),
+            max_gap_weekdays: DEFAULT_MAX_GAP_WEEKDAYS,
+            stale_bars: DEFAULT_STALE_BARS,
+            max_jump: DEFAULT_MAX_JUMP,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// Create a policy that runs every check and warns about failures.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the action for a check, enabling it if it was disabled.
+    #[must_use]
+    pub fn with_action(mut self, check: QualityCheck, action: ValidationAction) -> Self {
+        self.actions.insert(check, action);
+        self
+    }
+
+    /// Disable a check.
+    #[must_use]
+    pub fn without(mut self, check: QualityCheck) -> Self {
+        self.actions.remove(&check);
+        self
+    }
+
+    /// Set how many consecutive weekdays may be missing between daily bars
+    /// before it counts as a gap. Holidays make a small allowance necessary.
+    #[must_use]
+    pub const fn with_max_gap(mut self, weekdays: usize) -> Self {
+        self.max_gap_weekdays = weekdays;
+        self
+    }
+
+    /// Set how many consecutive bars with the same close count as stale.
+    #[must_use]
+    pub const fn with_stale_bars(mut self, bars: usize) -> Self {
+        self.stale_bars = bars;
+        self
+    }
+
+    /// Set the largest one-bar move, as a fraction of the neighbouring
+    /// closes (0.5 is 50%), before a bar counts as an outlier.
+    #[must_use]
+    pub const fn with_max_jump(mut self, fraction: f64) -> Self {
+        self.max_jump = fraction;
+        self
+    }
+
+    /// Returns the action for a check, or `None` if it is disabled.
+    #[must_use]
+    pub fn action(&self, check: QualityCheck) -> Option<ValidationAction> {
+        self.actions.get(&check).copied()
+    }
+
+    /// Run the enabled checks over a frame of bars at `frequency`.
+    ///
+    /// Returns the cleaned frame with every issue found, or
+    /// [`DataError::Validation`] for the first issue of a check whose action
+    /// is [`ValidationAction::Error`].
+    pub fn validate(&self, data: DataFrame, frequency: DataFrequency) -> Result<Validated> {
+        let mut bars = Bars::read(&data)?;
+        for (&check, &action) in &self.actions {
+            let flagged = match check {
+                QualityCheck::UnsortedDates => bars.unsorted_dates(),
+                QualityCheck::DuplicateDates => bars.duplicate_dates(),
+                QualityCheck::MissingPrices => bars.missing_prices(),
+                QualityCheck::OhlcConsistency => bars.inconsistent_ohlc(),
+                QualityCheck::NegativeVolume => bars.negative_volume(),
+                QualityCheck::JumpOutliers => bars.jump_outliers(self.max_jump),
+                QualityCheck::StalePrices => bars.stale_prices(self.stale_bars),
+                QualityCheck::CalendarGaps if frequency == DataFrequency::Daily => {
+                    bars.calendar_gaps(self.max_gap_weekdays)
+                }
+                QualityCheck::CalendarGaps => Vec::new(),
+            };
+            bars.apply(check, action, flagged)?;
+        }
+        let issues = std::mem::take(&mut bars.issues);
+        Ok(Validated {
+            data: bars.write(data)?,
+            issues,
+        })
+    }
+}
+
+/// A bar that failed a check: its row in the original frame and why.
+type Flagged = Vec<(usize, String)>;
+
+/// The values of a frame's bars, with the rows kept so far.
+///
+/// Values are indexed by row in the original frame; `order` lists the rows
+/// still kept, in output order.
+struct Bars {
+    dates: Option<Vec<Option<String>>>,
+    prices: [Option<Vec<Option<f64>>>; 4],
+    volume: Option<Vec<Option<f64>>>,
+    order: Vec<usize>,
+    rows_changed: bool,
+    filled: bool,
+    issues: Vec<QualityIssue>,
+}
+
+impl Bars {
+    const OPEN: usize = 0;
+    const HIGH: usize = 1;
+    const LOW: usize = 2;
+    const CLOSE: usize = 3;
+
+    fn read(data: &DataFrame) -> Result<Self> {
+        let dates = match data.column("date") {
+            Ok(column) => {
+                let dates = column
+                    .cast(&DataType::String)
+                    .map_err(|e| DataError::Other(e.to_string()))?;
+                let dates = dates.str().map_err(|e| DataError::Other(e.to_string()))?;
+                Some(dates.into_iter().map(|d| d.map(String::from)).collect())
+            }
+            Err(_) => None,
+        };
+        Ok(Self {
+            dates,
+            prices: [
+                read_values(data, PRICE_COLUMNS[Self::OPEN])?,
+                read_values(data, PRICE_COLUMNS[Self::HIGH])?,
+                read_values(data, PRICE_COLUMNS[Self::LOW])?,
+                read_values(data, PRICE_COLUMNS[Self::CLOSE])?,
+            ],
+            volume: read_values(data, "volume")?,
+            order: (0..data.height()).collect(),
+            rows_changed: false,
+            filled: false,
+            issues: Vec::new(),
+        })
+    }
+
+    /// Write the kept rows, in order, with any repaired values.
+    fn write(self, data: DataFrame) -> Result<DataFrame> {
+        let mut data = if self.rows_changed {
+            let rows: Vec<IdxSize> = self.order.iter().map(|&i| i as IdxSize).collect();
+            data.take(&IdxCa::from_vec("".into(), rows))
+                .map_err(|e| DataError::Other(e.to_string()))?
+        } else {
+            data
+        };
+        if !self.filled {
+            return Ok(data);
+        }
+
+        let columns = PRICE_COLUMNS
+            .iter()
+            .zip(&self.prices)
+            .chain(std::iter::once((&"volume", &self.volume)));
+        for (name, values) in columns {
+            let Some(values) = values else {
+                continue;
+            };
+            let dtype = data
+                .column(name)
+                .map_err(|e| DataError::Other(e.to_string()))?
+                .dtype()
+                .clone();
+            let kept: Vec<Option<f64>> = self.order.iter().map(|&i| values[i]).collect();
+            let column = Column::new((*name).into(), kept)
+                .cast(&dtype)
+                .map_err(|e| DataError::Other(e.to_string()))?;
+            data.with_column(column)
+                .map_err(|e| DataError::Other(e.to_string()))?;
+        }
+        Ok(data)
+    }
+
+    fn date(&self, row: usize) -> Option<&str> {
+        self.dates.as_ref()?[row].as_deref()
+    }
+
+    fn price(&self, column: usize, row: usize) -> Option<f64> {
+        self.prices[column].as_ref()?[row]
+    }
+
+    fn set_price(&mut self, column: usize, row: usize, value: Option<f64>) {
+        if let Some(values) = &mut self.prices[column] {
+            values[row] = value;
+        }
+    }
+
+    /// Record the issues for a check and carry out its action.
+    fn apply(
+        &mut self,
+        check: QualityCheck,
+        action: ValidationAction,
+        flagged: Flagged,
+    ) -> Result<()> {
+        let mut issues = flagged.iter().map(|(row, message)| QualityIssue {
+            check,
+            date: self.date(*row).map(String::from),
+            action,
+            message: message.clone(),
+        });
+        if action == ValidationAction::Error {
+            return match issues.next() {
+                Some(issue) => Err(DataError::Validation(issue.to_string())),
+                None => Ok(()),
+            };
+        }
+        let issues: Vec<QualityIssue> = issues.collect();
+        self.issues.extend(issues);
+        if flagged.is_empty() {
+            return Ok(());
+        }
+
+        let repairable = !matches!(
+            check,
+            QualityCheck::StalePrices | QualityCheck::CalendarGaps
+        );
+        match action {
+            ValidationAction::Drop if check != QualityCheck::CalendarGaps => {
+                let drop: HashSet<usize> = flagged.iter().map(|(row, _)| *row).collect();
+                self.order.retain(|row| !drop.contains(row));
+                self.rows_changed = true;
+            }
+            ValidationAction::Fill if check == QualityCheck::UnsortedDates => {
+                if let Some(dates) = &self.dates {
+                    self.order
+                        .sort_by(|a, b| dates[*a].as_deref().cmp(&dates[*b].as_deref()));
+                    self.rows_changed = true;
+                }
+            }
+            ValidationAction::Fill if repairable => {
+                let mut drop = HashSet::new();
+                for (row, _) in &flagged {
+                    if !self.fill(check, *row) {
+                        drop.insert(*row);
+                    }
+                }
+                if !drop.is_empty() {
+                    self.order.retain(|row| !drop.contains(row));
+                    self.rows_changed = true;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Repair a bar, returning false if it should be dropped instead.
+    fn fill(&mut self, check: QualityCheck, row: usize) -> bool {
+        let previous_close = self
+            .order
+            .iter()
+            .position(|&r| r == row)
+            .and_then(|i| i.checked_sub(1))
+            .and_then(|i| self.price(Self::CLOSE, self.order[i]));
+        match check {
+            QualityCheck::DuplicateDates => false,
+            QualityCheck::MissingPrices => {
+                let Some(fill) = self.price(Self::CLOSE, row).or(previous_close) else {
+                    return false;
+                };
+                for column in 0..PRICE_COLUMNS.len() {
+                    if self.price(column, row).is_none() {
+                        self.set_price(column, row, Some(fill));
+                    }
+                }
+                self.filled = true;
+                true
+            }
+            QualityCheck::OhlcConsistency => {
+                let values = self.ohlc(row).unwrap_or_default();
+                let high = values.iter().copied().fold(f64::MIN, f64::max);
+                let low = values.iter().copied().fold(f64::MAX, f64::min);
+                self.set_price(Self::HIGH, row, Some(high));
+                self.set_price(Self::LOW, row, Some(low));
+                self.filled = true;
+                true
+            }
+            QualityCheck::NegativeVolume => {
+                if let Some(volume) = &mut self.volume {
+                    volume[row] = None;
+                }
+                self.filled = true;
+                true
+            }
+            QualityCheck::JumpOutliers => {
+                let Some(close) = previous_close else {
+                    return false;
+                };
+                for column in 0..PRICE_COLUMNS.len() {
+                    self.set_price(column, row, Some(close));
+                }
+                self.filled = true;
+                true
+            }
+            QualityCheck::UnsortedDates
+            | QualityCheck::StalePrices
+            | QualityCheck::CalendarGaps => true,
+        }
+    }
+
+    /// Returns a bar's open, high, low and close if all are present.
+    fn ohlc(&self, row: usize) -> Option<[f64; 4]> {
+        Some([
+            self.price(Self::OPEN, row)?,
+            self.price(Self::HIGH, row)?,
+            self.price(Self::LOW, row)?,
+            self.price(Self::CLOSE, row)?,
+        ])
+    }
+
+    fn unsorted_dates(&self) -> Flagged {
+        let mut flagged = Vec::new();
+        let mut latest: Option<&str> = None;
+        for &row in &self.order {
+            let Some(date) = self.date(row) else {
+                continue;
+            };
+            match latest {
+                Some(latest) if date < latest => {
+                    flagged.push((row, format!("out of order after {latest}")));
+                }
+                _ => latest = Some(date),
+            }
+        }
+        flagged
+    }
+
+    fn duplicate_dates(&self) -> Flagged {
+        let mut seen = HashSet::new();
+        self.order
+            .iter()
+            .filter(|&&row| self.date(row).is_some_and(|date| !seen.insert(date)))
+            .map(|&row| (row, "duplicate date".to_string()))
+            .collect()
+    }
+
+    fn missing_prices(&self) -> Flagged {
+        self.order
+            .iter()
+            .filter_map(|&row| {
+                let missing: Vec<&str> = PRICE_COLUMNS
+                    .iter()
+                    .enumerate()
+                    .filter(|(column, _)| {
+                        self.prices[*column].is_some() && self.price(*column, row).is_none()
+                    })
+                    .map(|(_, name)| *name)
+                    .collect();
+                (!missing.is_empty()).then(|| (row, format!("missing {}", missing.join(", "))))
+            })
+            .collect()
+    }
+
+    fn inconsistent_ohlc(&self) -> Flagged {
+        self.order
+            .iter()
+            .filter_map(|&row| {
+                let [open, high, low, close] = self.ohlc(row)?;
+                let consistent = low <= open.min(close) && high >= open.max(close);
+                (!consistent).then(|| {
+                    (
+                        row,
+                        format!(
+                            "open {open}, high {high}, low {low}, close {close} are inconsistent"
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn negative_volume(&self) -> Flagged {
+        let Some(volume) = &self.volume else {
+            return Vec::new();
+        };
+        self.order
+            .iter()
+            .filter_map(|&row| {
+                let v = volume[row].filter(|v| *v < 0.0)?;
+                Some((row, format!("negative volume {v}")))
+            })
+            .collect()
+    }
+
+    fn jump_outliers(&self, max_jump: f64) -> Flagged {
+        let closes: Vec<(usize, Option<f64>)> = self
+            .order
+            .iter()
+            .map(|&row| (row, self.price(Self::CLOSE, row)))
+            .collect();
+        closes
+            .windows(3)
+            .filter_map(|window| {
+                let [(_, Some(before)), (row, Some(close)), (_, Some(after))] = *window else {
+                    return None;
+                };
+                let from_before = close / before - 1.0;
+                let from_after = close / after - 1.0;
+                let spike = before > 0.0
+                    && after > 0.0
+                    && from_before.abs() > max_jump
+                    && from_after.abs() > max_jump
+                    && from_before.signum() == from_after.signum();
+                spike.then(|| {
+                    (
+                        row,
+                        format!(
+                            "close {close} jumps {:.1}% from {before} and back to {after}",
+                            from_before * 100.0
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn stale_prices(&self, stale_bars: usize) -> Flagged {
+        let mut flagged = Vec::new();
+        let mut run: Vec<usize> = Vec::new();
+        let mut flush = |run: &mut Vec<usize>| {
+            if run.len() >= stale_bars.max(2) {
+                let close = self.price(Self::CLOSE, run[0]).unwrap_or_default();
+                let message = format!("close unchanged at {close} for {} bars", run.len());
+                flagged.extend(run[1..].iter().map(|&row| (row, message.clone())));
+            }
+            run.clear();
+        };
+        for &row in &self.order {
+            let close = self.price(Self::CLOSE, row);
+            let continues = run
+                .last()
+                .is_some_and(|&last| close.is_some() && self.price(Self::CLOSE, last) == close);
+            if !continues {
+                flush(&mut run);
+            }
+            run.push(row);
+        }
+        flush(&mut run);
+        flagged
+    }
+
+    fn calendar_gaps(&self, max_gap_weekdays: usize) -> Flagged {
+        let days: Vec<(usize, NaiveDate)> = self
+            .order
+            .iter()
+            .filter_map(|&row| {
+                let date = self.date(row)?.get(..10)?;
+                Some((row, NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?))
+            })
+            .collect();
+        days.windows(2)
+            .filter_map(|pair| {
+                let [(_, previous), (row, date)] = *pair else {
+                    return None;
+                };
+                let missing = previous
+                    .iter_days()
+                    .skip(1)
+                    .take_while(|d| *d < date)
+                    .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+                    .count();
+                (missing > max_gap_weekdays)
+                    .then(|| (row, format!("{missing} weekdays missing since {previous}")))
+            })
+            .collect()
+    }
+}
+
+/// Read a numeric column as floats, treating NaN as missing.
+fn read_values(data: &DataFrame, name: &str) -> Result<Option<Vec<Option<f64>>>> {
+    let Ok(column) = data.column(name) else {
+        return Ok(None);
+    };
+    let values = column
+        .cast(&DataType::Float64)
+        .map_err(|e| DataError::Other(e.to_string()))?;
+    let values = values.f64().map_err(|e| DataError::Other(e.to_string()))?;
+    Ok(Some(
+        values
+            .into_iter()
+            .map(|v| v.filter(|v| !v.is_nan()))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars(dates: &[&str], opens: &[Option<f64>], closes: &[f64], volumes: &[f64]) -> DataFrame {
+        let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+        let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+        DataFrame::new(vec![
+            Column::new("date".into(), dates.to_vec()),
+            Column::new("open".into(), opens.to_vec()),
+            Column::new("high".into(), highs),
+            Column::new("low".into(), lows),
+            Column::new("close".into(), closes.to_vec()),
+            Column::new("volume".into(), volumes.to_vec()),
+        ])
+        .unwrap()
+    }
+
+    fn checks(validated: &Validated) -> Vec<QualityCheck> {
+        validated.issues.iter().map(|i| i.check).collect()
+    }
+
+    fn column(df: &DataFrame, name: &str) -> Vec<Option<f64>> {
+        df.column(name)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_checks_flag_bad_bars() {
+        let data = bars(
+            &[
+                "2024-01-02",
+                "2024-01-04",
+                "2024-01-03",
+                "2024-01-04",
+                "2024-01-05",
+            ],
+            &[Some(100.0), None, Some(101.0), Some(102.0), Some(120.0)],
+            &[100.0, 101.0, 102.0, 102.0, 103.0],
+            &[1e6, 1e6, -5.0, 1e6, 1e6],
+        );
+        let validated = ValidationPolicy::new()
+            .validate(data, DataFrequency::Daily)
+            .unwrap();
+
+        assert_eq!(
+            checks(&validated),
+            [
+                QualityCheck::UnsortedDates,
+                QualityCheck::DuplicateDates,
+                QualityCheck::MissingPrices,
+                QualityCheck::OhlcConsistency,
+                QualityCheck::NegativeVolume,
+            ]
+        );
+        assert_eq!(validated.issues[0].date.as_deref(), Some("2024-01-03"));
+        assert_eq!(
+            validated.issues[2].to_string(),
+            "missing_prices on 2024-01-04: missing open"
+        );
+        assert_eq!(validated.data.height(), 5);
+    }
+
+    #[test]
+    fn test_drop_and_fill_repair_bars() {
+        let data = bars(
+            &[
+                "2024-01-03",
+                "2024-01-02",
+                "2024-01-04",
+                "2024-01-04",
+                "2024-01-05",
+                "2024-01-08",
+            ],
+            &[
+                Some(101.0),
+                Some(100.0),
+                None,
+                Some(1.0),
+                Some(150.0),
+                Some(103.0),
+            ],
+            &[101.0, 100.0, 102.0, 1.0, 1020.0, 103.0],
+            &[1e6; 6],
+        );
+        let policy = ValidationPolicy::new()
+            .with_action(QualityCheck::UnsortedDates, ValidationAction::Fill)
+            .with_action(QualityCheck::DuplicateDates, ValidationAction::Drop)
+            .with_action(QualityCheck::MissingPrices, ValidationAction::Fill)
+            .with_action(QualityCheck::OhlcConsistency, ValidationAction::Fill)
+            .with_action(QualityCheck::JumpOutliers, ValidationAction::Fill);
+        let validated = policy.validate(data, DataFrequency::Daily).unwrap();
+
+        assert_eq!(
+            checks(&validated),
+            [
+                QualityCheck::UnsortedDates,
+                QualityCheck::DuplicateDates,
+                QualityCheck::MissingPrices,
+                QualityCheck::OhlcConsistency,
+                QualityCheck::JumpOutliers,
+            ]
+        );
+        let data = &validated.data;
+        let dates: Vec<&str> = data
+            .column("date")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(
+            dates,
+            [
+                "2024-01-02",
+                "2024-01-03",
+                "2024-01-04",
+                "2024-01-05",
+                "2024-01-08"
+            ]
+        );
+        assert_eq!(column(data, "open")[2], Some(102.0));
+        assert_eq!(column(data, "close")[3], Some(102.0));
+        assert_eq!(column(data, "high")[3], Some(102.0));
+    }
+
+    #[test]
+    fn test_error_action_rejects_frame() {
+        let data = bars(
+            &["2024-01-02", "2024-01-03", "2024-01-04", "2024-01-12"],
+            &[Some(100.0); 4],
+            &[100.0; 4],
+            &[1e6; 4],
+        );
+        let validated = ValidationPolicy::new()
+            .with_stale_bars(4)
+            .validate(data.clone(), DataFrequency::Daily)
+            .unwrap();
+        assert_eq!(
+            checks(&validated),
+            [
+                QualityCheck::StalePrices,
+                QualityCheck::StalePrices,
+                QualityCheck::StalePrices,
+                QualityCheck::CalendarGaps,
+            ]
+        );
+        assert_eq!(
+            validated.issues[3].message,
+            "5 weekdays missing since 2024-01-04"
+        );
+
+        // Gaps are only checked for daily bars
+        let policy = ValidationPolicy::new()
+            .without(QualityCheck::StalePrices)
+            .with_action(QualityCheck::CalendarGaps, ValidationAction::Error);
+        assert!(policy.validate(data.clone(), DataFrequency::Weekly).is_ok());
+        let err = policy.validate(data, DataFrequency::Daily).unwrap_err();
+        assert!(matches!(err, DataError::Validation(_)));
+    }
+}